@@ -1,9 +1,12 @@
 use leptos::{
-	component, expect_context, view, Action, IntoView, RwSignal, SignalGet,
-	SignalSet, StoredValue,
+	component, create_memo, event_target_value, expect_context, view, Action,
+	For, IntoView, RwSignal, Show, SignalGet, SignalGetUntracked, SignalSet,
+	SignalWith, StoredValue,
 };
+use phosphor_leptos::{Check, FolderPlus, IconWeight, Plus};
 
 use crate::{
+	contexts::{toast, Toast, ToastType},
 	resources::SubscriptionsCtx,
 	utils::{go_to, i18n},
 };
@@ -35,14 +38,18 @@ pub fn ChannelRoll(
 	let remove_sub =
 		Action::new(|args: &RemoveSubArgs| remove_from_subs(args.clone()));
 
+	// Editing groups changes the subscriptions too, which should not
+	// re-render the roll and close the groups dropdown.
+	let subscribed = create_memo(move |_| {
+		subs_ctx.0.with(|subs| {
+			subs.channels
+				.iter()
+				.any(|sub| sub.id.eq_ignore_ascii_case(&channel_id.get_value()))
+		})
+	});
+
 	move || {
-		if subs_ctx
-			.0
-			.get()
-			.channels
-			.into_iter()
-			.any(|sub| sub.id.eq_ignore_ascii_case(&channel_id.get_value()))
-		{
+		if subscribed.get() {
 			view! {
 				<div class="flex flex-row gap-x-4 justify-between items-center mt-2 w-full">
 					<div class="flex flex-row gap-x-4 items-center">
@@ -73,6 +80,7 @@ pub fn ChannelRoll(
 							</button>
 						</div>
 					</div>
+					<SubscriptionGroupsDropdown channel_id=channel_id/>
 				</div>
 			}
 		} else {
@@ -115,6 +123,109 @@ pub fn ChannelRoll(
 	}
 }
 
+#[component]
+pub fn SubscriptionGroupsDropdown(
+	channel_id: StoredValue<String>,
+) -> impl IntoView {
+	let subs_ctx = expect_context::<SubscriptionsCtx>();
+	let new_group = RwSignal::new(String::new());
+
+	let groups = move || {
+		subs_ctx.0.with(|subs| {
+			subs.groups
+				.iter()
+				.map(|group| group.name.clone())
+				.collect::<Vec<_>>()
+		})
+	};
+
+	let create_group = move |_| {
+		let name = new_group.get_untracked().trim().to_string();
+		match subs_ctx.add_group(&name) {
+			Ok(()) => {
+				subs_ctx.toggle_group_channel(&name, &channel_id.get_value());
+				new_group.set(String::new());
+			}
+			Err(err) => {
+				toast(Toast::new(err.to_string(), None, Some(ToastType::Error)))
+			}
+		}
+	};
+
+	view! {
+		<div class="z-20 dropdown dropdown-bottom dropdown-end">
+			<div
+				tabindex="0"
+				role="button"
+				class="btn btn-circle btn-accent btn-outline btn-sm"
+			>
+				<FolderPlus weight=IconWeight::Regular class="w-5 h-5 base-content"/>
+			</div>
+			<div
+				tabindex="0"
+				class="p-4 mt-2 space-y-4 w-max rounded-lg dropdown-content h-max bg-base-200 shadow-dropdown"
+			>
+				<h1 class="font-semibold">{i18n("channel.groups")}</h1>
+				<ul class="flex overflow-y-scroll flex-col max-h-64">
+					<For each=groups key=|name| name.clone() let:name>
+						<SubscriptionGroupItem name=name channel_id=channel_id/>
+					</For>
+				</ul>
+				<div class="flex flex-row gap-x-2 items-center">
+					<input
+						type="text"
+						placeholder=i18n("channel.new_group")
+						class="input input-sm input-bordered"
+						prop:value=new_group
+						on:input=move |ev| new_group.set(event_target_value(&ev))
+					/>
+					<button
+						on:click=create_group
+						class="btn btn-sm btn-circle btn-ghost"
+					>
+						<Plus weight=IconWeight::Regular class="w-4 h-4 base-content"/>
+					</button>
+				</div>
+			</div>
+		</div>
+	}
+}
+
+#[component]
+pub fn SubscriptionGroupItem(
+	name: String,
+	channel_id: StoredValue<String>,
+) -> impl IntoView {
+	let subs_ctx = expect_context::<SubscriptionsCtx>();
+	let group = StoredValue::new(name.clone());
+
+	let in_group = move || {
+		subs_ctx.0.with(|subs| {
+			subs.group(&group.get_value())
+				.is_some_and(|group| group.contains(&channel_id.get_value()))
+		})
+	};
+
+	let toggle = move |_| {
+		subs_ctx
+			.toggle_group_channel(&group.get_value(), &channel_id.get_value());
+	};
+
+	view! {
+		<li>
+			<button
+				on:click=toggle
+				class="flex flex-row justify-between items-center btn btn-sm btn-ghost"
+			>
+				<span>{name}</span>
+				<Show when=in_group>
+					<Check weight=IconWeight::Bold class="w-4 h-4 base-content"/>
+				</Show>
+			</button>
+		</li>
+	}
+}
+
 pub type AddSubArgs = (String, String, SubscriptionsCtx);
 pub type RemoveSubArgs = (String, SubscriptionsCtx);
 
@@ -2,32 +2,54 @@ use invidious::{LocalPlaylist, Video};
 use leptos::*;
 use leptos_router::create_query_signal;
 use num_format::ToFormattedString;
+use phosphor_leptos::{DotsSixVertical, IconWeight, PencilSimple, Trash, X};
 
 use crate::{
 	components::FerrisError,
-	contexts::{NetworkConfigCtx, RegionConfigCtx},
-	utils::i18n,
+	contexts::{toast, NetworkConfigCtx, RegionConfigCtx, Toast, ToastType},
+	resources::PlaylistsCtx,
+	utils::{go_to, i18n},
 };
 
 #[component]
-pub fn LocalPlaylistSectionCollapsible(
-	playlist: LocalPlaylist,
-) -> impl IntoView {
+pub fn LocalPlaylistSectionCollapsible(id: String) -> impl IntoView {
 	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let playlists = expect_context::<PlaylistsCtx>();
+	let id = StoredValue::new(id);
+	let dragged = create_rw_signal(None::<usize>);
+	let title =
+		move || playlists.get(&id.get_value()).map(|playlist| playlist.title);
+
+	let video_ids = move || {
+		playlists.get(&id.get_value()).map_or(vec![], |playlist| {
+			playlist.videos.into_iter().map(|video| video.id).collect()
+		})
+	};
 
 	let playlist_videos = Resource::local(
-		move || (playlist.clone(), server.get()),
-		|(playlist, server)| async move {
-			playlist.fetch_playlist_videos(&server).await
+		move || (video_ids(), server.get()),
+		|(ids, server)| async move {
+			LocalPlaylist::fetch_videos(&server, &ids).await
 		},
 	);
 
-	let recommended_view = move || {
+	let playlist_view = move || {
 		playlist_videos.get().map(|results| {
+			if results.is_empty() {
+				return view! { <p>{i18n("playlist.empty")}</p> }.into_view();
+			}
 			results
 				.into_iter()
-				.map(|video| match video {
-					Ok(video) => view! { <PlaylistVideo video=video/> },
+				.enumerate()
+				.map(|(index, video)| match video {
+					Ok(video) => view! {
+						<PlaylistVideo
+							video=video
+							index=index
+							playlist_id=id
+							dragged=dragged
+						/>
+					},
 					Err(err) => view! { <FerrisError error=err/> },
 				})
 				.collect_view()
@@ -37,27 +59,25 @@ pub fn LocalPlaylistSectionCollapsible(
 	view! {
 		<div>
 			<div class="hidden flex-col p-4 space-y-4 h-auto rounded-lg bg-base-200 lg:!flex">
-				<h1 class="text-xl font-semibold">
-					{i18n("video.info.recommended")}
-				</h1>
+				<PlaylistHeader id=id/>
 				<div class="flex flex-col pr-4 space-y-4 rounded-lg bg-base-200">
 					<Suspense fallback=move || {
 						view! { <PlaylistSectionPlaceholder/> }
-					}>{recommended_view}</Suspense>
+					}>{playlist_view}</Suspense>
 				</div>
 			</div>
 
 			<div class="rounded-lg lg:hidden collapse collapse-arrow bg-base-200">
 				<input type="checkbox"/>
 				<div class="text-xl font-medium collapse-title">
-					<span>{i18n("video.info.recommended")}</span>
-
+					<span>{title}</span>
 				</div>
 				<div class="collapse-content">
-					<div class="flex flex-col pr-4 space-y-4 rounded-lg bg-base-200">
+					<PlaylistHeader id=id/>
+					<div class="flex flex-col pr-4 mt-4 space-y-4 rounded-lg bg-base-200">
 						<Suspense fallback=move || {
 							view! { <PlaylistSectionPlaceholder/> }
-						}>{recommended_view}</Suspense>
+						}>{playlist_view}</Suspense>
 					</div>
 				</div>
 			</div>
@@ -66,15 +86,110 @@ pub fn LocalPlaylistSectionCollapsible(
 }
 
 #[component]
-pub fn PlaylistVideo(video: Video) -> impl IntoView {
+pub fn PlaylistHeader(id: StoredValue<String>) -> impl IntoView {
+	let playlists = expect_context::<PlaylistsCtx>();
+	let editing = create_rw_signal(false);
+	let new_title = create_rw_signal(String::new());
+	let title =
+		move || playlists.get(&id.get_value()).map(|playlist| playlist.title);
+
+	let start_editing = move |_| {
+		new_title.set(title().unwrap_or_default());
+		editing.set(true);
+	};
+
+	let rename = move || match playlists
+		.rename(&id.get_value(), &new_title.get_untracked())
+	{
+		Ok(()) => editing.set(false),
+		Err(err) => {
+			toast(Toast::new(err.to_string(), None, Some(ToastType::Error)))
+		}
+	};
+
+	let delete = move |_| {
+		playlists.delete(&id.get_value());
+		go_to("/playlists");
+	};
+
+	view! {
+		<div class="flex flex-row gap-x-2 justify-between items-center">
+			<Show
+				when=move || editing.get()
+				fallback=move || {
+					view! { <h1 class="text-xl font-semibold">{title}</h1> }
+				}
+			>
+				<input
+					type="text"
+					class="input input-sm input-bordered"
+					prop:value=new_title
+					on:input=move |ev| new_title.set(event_target_value(&ev))
+					on:keydown=move |ev| {
+						if ev.key() == "Enter" {
+							rename();
+						}
+					}
+				/>
+			</Show>
+			<div class="flex flex-row">
+				<div class="tooltip" data-tip=i18n("playlist.rename")>
+					<button on:click=start_editing class="btn btn-ghost btn-xs">
+						<PencilSimple
+							weight=IconWeight::Regular
+							class="w-4 h-4 base-content"
+						/>
+					</button>
+				</div>
+				<div class="tooltip" data-tip=i18n("playlist.delete")>
+					<button on:click=delete class="btn btn-ghost btn-xs">
+						<Trash
+							weight=IconWeight::Regular
+							class="w-4 h-4 base-content"
+						/>
+					</button>
+				</div>
+			</div>
+		</div>
+	}
+}
+
+#[component]
+pub fn PlaylistVideo(
+	video: Video,
+	index: usize,
+	playlist_id: StoredValue<String>,
+	dragged: RwSignal<Option<usize>>,
+) -> impl IntoView {
+	let playlists = expect_context::<PlaylistsCtx>();
+
 	let src = video
 		.thumbnails
 		.get(4)
-		.map_or(String::new(), |thumbnail| thumbnail.url.clone());
+		.map_or(String::new(), |thumb| thumb.url.clone());
 
-	let video_id = video.id;
+	let video_id = StoredValue::new(video.id);
 	let open_video = move |_| {
-		create_query_signal::<String>("id").1.set(Some(video_id.clone()));
+		create_query_signal::<String>("id").1.set(Some(video_id.get_value()));
+	};
+	let remove_video = move |_| {
+		playlists.remove_video(&playlist_id.get_value(), &video_id.get_value());
+	};
+
+	let on_drop = move |ev: ev::DragEvent| {
+		ev.prevent_default();
+		if let Some(from) = dragged.get_untracked() {
+			playlists.move_video(&playlist_id.get_value(), from, index);
+		}
+		dragged.set(None);
+	};
+
+	let row_classes = move || {
+		if dragged.get() == Some(index) {
+			"flex flex-row gap-x-4 items-center opacity-50"
+		} else {
+			"flex flex-row gap-x-4 items-center"
+		}
 	};
 
 	let img_loaded = create_rw_signal(false);
@@ -92,14 +207,25 @@ pub fn PlaylistVideo(video: Video) -> impl IntoView {
 		move || video.views.to_formatted_string(&locale.get().to_num_fmt());
 
 	view! {
-		<div class="flex flex-row gap-x-4">
+		<div
+			draggable="true"
+			on:dragstart=move |_| dragged.set(Some(index))
+			on:dragend=move |_| dragged.set(None)
+			on:dragover=move |ev: ev::DragEvent| ev.prevent_default()
+			on:drop=on_drop
+			class=row_classes
+		>
+			<DotsSixVertical
+				weight=IconWeight::Bold
+				class="w-4 h-4 cursor-grab shrink-0 base-content"
+			/>
 			<img
 				on:click=open_video
 				on:load=move |_| img_loaded.set(true)
 				src=src
 				class=image_classes
 			/>
-			<div class="flex flex-col w-[70%]">
+			<div class="flex flex-col w-[60%]">
 				<p class="text-sm">{video.title}</p>
 				<div class="flex flex-row flex-wrap gap-x-1 mt-2 text-sm">
 					<p>{video.author}</p>
@@ -107,6 +233,9 @@ pub fn PlaylistVideo(video: Video) -> impl IntoView {
 					<p>{views}</p>
 				</div>
 			</div>
+			<button on:click=remove_video class="btn btn-ghost btn-xs">
+				<X weight=IconWeight::Regular class="w-4 h-4 base-content"/>
+			</button>
 		</div>
 	}
 }
@@ -1,7 +1,9 @@
 mod local_playlist;
 mod playlist;
+mod queue;
 mod recommended;
 
 pub use local_playlist::LocalPlaylistSectionCollapsible;
 // pub use playlist::PlaylistSectionCollapsible;
+pub use queue::{QueueDropdown, QueueSection};
 pub use recommended::RecommendedSectionCollapsible;
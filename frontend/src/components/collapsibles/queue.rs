@@ -0,0 +1,135 @@
+use leptos::*;
+use leptos_router::create_query_signal;
+use phosphor_leptos::{ArrowDown, ArrowUp, DotsThreeVertical, IconWeight, X};
+
+use crate::{
+	contexts::{toast, QueueCtx, QueueItem, Toast, ToastType},
+	resources::block_channel,
+	utils::i18n,
+};
+
+#[component]
+pub fn QueueSection() -> impl IntoView {
+	let queue = expect_context::<QueueCtx>();
+
+	view! {
+		<Show when=move || !queue.0.get().is_empty()>
+			<div class="flex flex-col p-4 mb-4 space-y-4 h-auto rounded-lg bg-base-200">
+				<div class="flex flex-row justify-between items-center">
+					<h1 class="text-xl font-semibold">{i18n("video.queue.queue")}</h1>
+					<button
+						on:click=move |_| queue.clear()
+						class="btn btn-ghost btn-xs md:btn-sm"
+					>
+						{i18n("video.queue.clear")}
+					</button>
+				</div>
+				<div class="flex flex-col space-y-4 rounded-lg ltr:pr-4 rtl:pl-4 bg-base-200">
+					<For
+						each=move || queue.0.get()
+						key=|item| item.id.clone()
+						let:item
+					>
+						<QueueVideo item=item/>
+					</For>
+				</div>
+			</div>
+		</Show>
+	}
+}
+
+#[component]
+pub fn QueueVideo(item: QueueItem) -> impl IntoView {
+	let queue = expect_context::<QueueCtx>();
+	let id = StoredValue::new(item.id);
+
+	let open_video = move |_| {
+		queue.remove(&id.get_value());
+		create_query_signal::<String>("id").1.set(Some(id.get_value()));
+	};
+
+	view! {
+		<div class="flex flex-row gap-x-4 items-center">
+			<img
+				on:click=open_video
+				src=item.thumbnail
+				class="object-cover object-center rounded-lg cursor-pointer w-[30%] aspect-video bg-neutral"
+			/>
+			<div class="flex flex-col w-[55%]">
+				<p class="text-sm line-clamp-2">{item.title}</p>
+				<p class="mt-2 text-sm">{item.author}</p>
+			</div>
+			<div class="flex flex-col">
+				<button
+					on:click=move |_| queue.move_up(&id.get_value())
+					class="btn btn-ghost btn-xs"
+				>
+					<ArrowUp weight=IconWeight::Regular class="w-4 h-4 base-content"/>
+				</button>
+				<button
+					on:click=move |_| queue.remove(&id.get_value())
+					class="btn btn-ghost btn-xs"
+				>
+					<X weight=IconWeight::Regular class="w-4 h-4 base-content"/>
+				</button>
+				<button
+					on:click=move |_| queue.move_down(&id.get_value())
+					class="btn btn-ghost btn-xs"
+				>
+					<ArrowDown weight=IconWeight::Regular class="w-4 h-4 base-content"/>
+				</button>
+			</div>
+		</div>
+	}
+}
+
+#[component]
+pub fn QueueDropdown(item: QueueItem) -> impl IntoView {
+	let queue = expect_context::<QueueCtx>();
+	let item = StoredValue::new(item);
+
+	let block = move |_| {
+		item.with_value(|item| block_channel(&item.author_id, &item.author));
+		toast(Toast::new(
+			i18n("channel.blocked")(),
+			None,
+			Some(ToastType::Info),
+		));
+	};
+	let block_item = move || {
+		item.with_value(|item| !item.author_id.is_empty()).then(|| {
+			view! {
+				<li>
+					<a on:click=block>{i18n("channel.block")}</a>
+				</li>
+			}
+		})
+	};
+
+	view! {
+		<div class="dropdown dropdown-end">
+			<div tabindex="0" role="button" class="btn btn-ghost btn-xs btn-circle">
+				<DotsThreeVertical
+					weight=IconWeight::Bold
+					class="w-5 h-5 base-content"
+				/>
+			</div>
+			<ul
+				tabindex="0"
+				class="z-20 p-2 w-max rounded-xl menu dropdown-content shadow-dropdown bg-base-200"
+			>
+				<li>
+					<a on:click=move |_| queue.play_next(item.get_value())>
+						{i18n("video.queue.play_next")}
+					</a>
+				</li>
+				<li>
+					<a on:click=move |_| queue.add(item.get_value())>
+						{i18n("video.queue.add")}
+					</a>
+				</li>
+				{block_item}
+			</ul>
+		</div>
+	}
+}
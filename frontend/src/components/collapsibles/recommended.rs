@@ -2,23 +2,34 @@ use invidious::VideoShort;
 use leptos::*;
 use leptos_router::create_query_signal;
 
-use crate::{components::FerrisError, resources::VideoResource, utils::i18n};
+use crate::{
+	components::{FerrisError, QueueDropdown},
+	contexts::{FilterConfigCtx, QueueItem},
+	resources::VideoResource,
+	utils::i18n,
+};
 
-#[component]
-pub fn RecommendedSection() -> impl IntoView {
-	let recommended_view = move || {
-		expect_context::<VideoResource>().resource.get().map(|res| match res {
-			Ok(video) => video
+/// Recommendations from blocked channels are left out, and disappear as soon
+/// as their channel is blocked.
+fn recommended_view() -> Option<View> {
+	let blocklist = expect_context::<FilterConfigCtx>().blocklist_slice.0;
+	expect_context::<VideoResource>().resource.get().map(|res| match res {
+		Ok(video) => blocklist.with(|blocklist| {
+			video
 				.recommended_videos
 				.into_iter()
+				.filter(|video| !blocklist.blocks(video))
 				.map(|video_short| {
 					view! { <RecommendedVideo video=video_short/> }
 				})
-				.collect_view(),
-			Err(err) => view! { <FerrisError error=err/> },
-		})
-	};
+				.collect_view()
+		}),
+		Err(err) => view! { <FerrisError error=err/> },
+	})
+}
 
+#[component]
+pub fn RecommendedSection() -> impl IntoView {
 	view! {
 		<div class="flex flex-col p-4 space-y-4 h-auto rounded-lg bg-base-200">
 			<h1 class="text-xl font-semibold">
@@ -35,19 +46,6 @@ pub fn RecommendedSection() -> impl IntoView {
 
 #[component]
 pub fn RecommendedSectionCollapsible() -> impl IntoView {
-	let recommended_view = move || {
-		expect_context::<VideoResource>().resource.get().map(|res| match res {
-			Ok(video) => video
-				.recommended_videos
-				.into_iter()
-				.map(|video_short| {
-					view! { <RecommendedVideo video=video_short/> }
-				})
-				.collect_view(),
-			Err(err) => view! { <FerrisError error=err/> },
-		})
-	};
-
 	view! {
 		<div>
 			<div class="hidden flex-col p-4 space-y-4 h-auto rounded-lg bg-base-200 lg:!flex">
@@ -86,6 +84,7 @@ pub fn RecommendedVideo(video: VideoShort) -> impl IntoView {
 		.get(4)
 		.map_or(String::new(), |thumb| thumb.url.clone());
 
+	let queue_item = QueueItem::from(&video);
 	let video_id = video.id;
 	let open_video = move |_| {
 		create_query_signal::<String>("id").1.set(Some(video_id.clone()));
@@ -109,7 +108,7 @@ pub fn RecommendedVideo(video: VideoShort) -> impl IntoView {
 				src=src
 				class=image_classes
 			/>
-			<div class="flex flex-col w-[70%]">
+			<div class="flex flex-col w-[60%]">
 				<p class="text-sm">{video.title}</p>
 				<div class="flex flex-row flex-wrap gap-x-1 mt-2 text-sm">
 					<p>{video.author}</p>
@@ -117,6 +116,7 @@ pub fn RecommendedVideo(video: VideoShort) -> impl IntoView {
 					<p>{video.views_text}</p>
 				</div>
 			</div>
+			<QueueDropdown item=queue_item/>
 		</div>
 	}
 }
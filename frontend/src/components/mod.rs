@@ -13,7 +13,9 @@ mod video_player;
 
 pub use card_grid::{CardGrid, GridContainer};
 pub use channel_roll::ChannelRoll;
-pub use collapsibles::RecommendedSectionCollapsible;
+pub use collapsibles::{
+	QueueDropdown, QueueSection, RecommendedSectionCollapsible,
+};
 pub use drawer::Drawer;
 pub use error::*;
 pub use header::Header;
@@ -6,7 +6,7 @@ use leptos_router::Outlet;
 
 use crate::{
 	components::{Drawer, ExpandedCtx, Header, Sidebar, Toaster},
-	contexts::{RegionConfigCtx, Toast, UiConfigCtx},
+	contexts::{RegionConfigCtx, Toast, Toaster as ToasterCtx, UiConfigCtx},
 };
 
 #[component]
@@ -60,6 +60,7 @@ fn RustyTubeToaster() -> impl IntoView {
 				view! {
 					<div class="alert">
 						<span>{toast.message.get_value()}</span>
+						<ToastActionBtn toast=toast/>
 					</div>
 				}
 			}
@@ -68,6 +69,7 @@ fn RustyTubeToaster() -> impl IntoView {
 				view! {
 					<div class="alert alert-info">
 						<span>{toast.message.get_value()}</span>
+						<ToastActionBtn toast=toast/>
 					</div>
 				}
 			}
@@ -76,6 +78,7 @@ fn RustyTubeToaster() -> impl IntoView {
 				view! {
 					<div class="alert alert-warning">
 						<span>{toast.message.get_value()}</span>
+						<ToastActionBtn toast=toast/>
 					</div>
 				}
 			}
@@ -84,6 +87,7 @@ fn RustyTubeToaster() -> impl IntoView {
 				view! {
 					<div class="alert alert-error">
 						<span>{toast.message.get_value()}</span>
+						<ToastActionBtn toast=toast/>
 					</div>
 				}
 			}
@@ -91,6 +95,24 @@ fn RustyTubeToaster() -> impl IntoView {
 	}
 }
 
+#[component]
+fn ToastActionBtn(toast: Toast) -> impl IntoView {
+	let toaster = expect_context::<ToasterCtx>();
+
+	toast.action.map(|action| {
+		let run = move |_| {
+			(action.get_value().run)();
+			toaster.remove_toast(&toast);
+		};
+
+		view! {
+			<button on:click=run class="btn btn-sm btn-ghost">
+				{action.get_value().label}
+			</button>
+		}
+	})
+}
+
 pub const PAGE_CLASSES: &str = "
 flex flex-col w-dvw h-dvh \
 \
@@ -2,9 +2,12 @@ use invidious::{LocalPlaylist, Video};
 use leptos::*;
 use num_format::ToFormattedString;
 
+use rustytube_error::RustyTubeError;
+
 use crate::{
+	components::FerrisError,
 	contexts::{NetworkConfigCtx, RegionConfigCtx},
-	utils::go_to,
+	utils::{go_to, i18n},
 };
 
 #[component]
@@ -15,10 +18,7 @@ pub fn LocalPlaylistPreviewCard(playlist: LocalPlaylist) -> impl IntoView {
 	let video = Resource::local(
 		move || (server.get(), playlist.get_value()),
 		|(server, playlist)| async move {
-			playlist
-				.fetch_first_playlist_video(&server)
-				.await
-				.expect("First playlist video should load.")
+			playlist.fetch_first_playlist_video(&server).await
 		},
 	);
 
@@ -31,8 +31,18 @@ pub fn LocalPlaylistPreviewCard(playlist: LocalPlaylist) -> impl IntoView {
 				{move || {
 					video
 						.get()
-						.map(|video| {
-							view! {
+						.map(|video| match video {
+							Err(RustyTubeError::EmptyPlaylist) => {
+								view! {
+									<h1 class="px-2 mt-3 font-sans text-base font-semibold">
+										{playlist.get_value().title}
+									</h1>
+									<p class="px-2 text-sm">{i18n("playlist.empty")}</p>
+								}
+									.into_view()
+							}
+							Err(err) => view! { <FerrisError error=err/> },
+							Ok(video) => view! {
 								<Thumbnail
 									id=video.id.clone()
 									url=video
@@ -45,6 +55,7 @@ pub fn LocalPlaylistPreviewCard(playlist: LocalPlaylist) -> impl IntoView {
 								/>
 								<Info video=video playlist=playlist/>
 							}
+								.into_view(),
 						})
 				}}
 
@@ -71,17 +82,7 @@ pub fn Info(
 	};
 
 	let go_to_local_playlist_page = move |_| {
-		go_to(format!(
-			"/playlist?title={}&videos=[{{{}}}]",
-			playlist.get_value().title,
-			playlist
-				.get_value()
-				.videos
-				.into_iter()
-				.map(|video| video.id)
-				.collect::<Vec<String>>()
-				.join(",")
-		));
+		go_to(format!("/playlist?playlist={}", playlist.get_value().id));
 	};
 
 	view! {
@@ -128,17 +129,7 @@ pub fn Thumbnail(
 	};
 
 	let go_to_local_playlist_page = move |_| {
-		go_to(format!(
-			"/playlist?title={}&videos=[{{{}}}]",
-			playlist.get_value().title,
-			playlist
-				.get_value()
-				.videos
-				.into_iter()
-				.map(|video| video.id)
-				.collect::<Vec<String>>()
-				.join(",")
-		));
+		go_to(format!("/playlist?playlist={}", playlist.get_value().id));
 	};
 
 	view! {
@@ -78,7 +78,7 @@ pub fn Thumbnail(playlist_id: String, url: String) -> impl IntoView {
 		}
 	};
 
-	let open_playlist = move |_| go_to(format!("playlist?id={playlist_id}"));
+	let open_playlist = move |_| go_to(format!("/playlist?list={playlist_id}"));
 
 	view! {
 		<div
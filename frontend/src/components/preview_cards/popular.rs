@@ -3,7 +3,11 @@ use leptos::*;
 use num_format::ToFormattedString;
 use phosphor_leptos::{Eye, IconWeight};
 
-use crate::{contexts::RegionConfigCtx, utils::go_to};
+use crate::{
+	components::QueueDropdown,
+	contexts::{QueueItem, RegionConfigCtx},
+	utils::go_to,
+};
 
 #[component]
 pub fn PopularPreviewCard(video: PopularItem) -> impl IntoView {
@@ -22,6 +26,7 @@ pub fn PopularPreviewCard(video: PopularItem) -> impl IntoView {
 pub fn Info(video: PopularItem) -> impl IntoView {
 	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
 
+	let queue_item = QueueItem::from(&video);
 	let title = video.title;
 	let author = video.author;
 	let published = video.published_text;
@@ -37,9 +42,12 @@ pub fn Info(video: PopularItem) -> impl IntoView {
 
 	view! {
 		<div class="flex flex-col px-2 mt-3 space-y-3 w-full cursor-text">
-			<h1 class="font-sans text-base font-semibold line-clamp-2">
-				{title}
-			</h1>
+			<div class="flex flex-row gap-2 justify-between items-start">
+				<h1 class="font-sans text-base font-semibold line-clamp-2">
+					{title}
+				</h1>
+				<QueueDropdown item=queue_item/>
+			</div>
 			<div class="flex flex-row flex-wrap gap-1 items-center text-sm font-normal">
 				<h2
 					on:click=go_to_channel_page
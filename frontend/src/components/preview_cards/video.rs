@@ -4,7 +4,11 @@ use leptos_router::NavigateOptions;
 use num_format::ToFormattedString;
 use phosphor_leptos::{CheckCircle, Eye, IconWeight};
 
-use crate::{contexts::RegionConfigCtx, utils::go_to};
+use crate::{
+	components::QueueDropdown,
+	contexts::{QueueItem, RegionConfigCtx},
+	utils::go_to,
+};
 
 #[component]
 pub fn VideoPreviewCard(video: CommonVideo) -> impl IntoView {
@@ -23,6 +27,7 @@ pub fn VideoPreviewCard(video: CommonVideo) -> impl IntoView {
 pub fn Info(video: CommonVideo) -> impl IntoView {
 	let locale = expect_context::<RegionConfigCtx>().locale_slice.0;
 
+	let queue_item = QueueItem::from(&video);
 	let title = video.title;
 	let author = video.author;
 	let author_id = video.author_id;
@@ -43,9 +48,12 @@ pub fn Info(video: CommonVideo) -> impl IntoView {
 
 	view! {
 		<div class="flex flex-col px-2 mt-3 space-y-3 w-full cursor-text">
-			<h1 class="font-sans text-base font-semibold line-clamp-2">
-				{title}
-			</h1>
+			<div class="flex flex-row gap-2 justify-between items-start">
+				<h1 class="font-sans text-base font-semibold line-clamp-2">
+					{title}
+				</h1>
+				<QueueDropdown item=queue_item/>
+			</div>
 			<div class="flex flex-row flex-wrap gap-1 items-center text-sm font-normal">
 				<h2
 					on:click=go_to_channel_page
@@ -1,7 +1,8 @@
 use invidious::{ChannelThumb, Subscription, Subscriptions};
 use leptos::*;
 use phosphor_leptos::{
-	FireSimple, GearSix, Heart, IconWeight, Queue, RssSimple, TrendUp,
+	ClockCounterClockwise, FireSimple, Folder, GearSix, Heart, IconWeight,
+	Queue, RssSimple, TrendUp,
 };
 use rustytube_error::RustyTubeError;
 use utils::get_element_by_id;
@@ -13,7 +14,8 @@ use crate::{
 		FerrisError,
 	},
 	icons::FerrisIcon,
-	resources::SubscriptionsThumbnailsResource,
+	pages::group_path,
+	resources::{SubscriptionsCtx, SubscriptionsThumbnailsResource},
 	utils::{go_to, i18n},
 };
 
@@ -32,8 +34,10 @@ pub fn Sidebar() -> impl IntoView {
 					<SubscriptionsButton/>
 					<TrendingButton/>
 					<PopularButton/>
+					<HistoryButton/>
 				// <PlaylistsButton/>
 				</div>
+				<GroupButtons/>
 				<Subs/>
 				<div class="border-t-[1px] border-t-primary">
 					<SettingsButton/>
@@ -150,6 +154,34 @@ pub fn PopularButton() -> impl IntoView {
 	}
 }
 
+#[component]
+pub fn HistoryButton() -> impl IntoView {
+	let expanded = expect_context::<ExpandedCtx>().0;
+
+	view! {
+		<div
+			data-expanded=expanded
+			data-tip=i18n("sidebar.history")
+			class=SIDEBAR_TOOLTIP_CLASSES
+		>
+			<button
+				on:click=move |_| go_to("/history")
+				data-expanded=expanded
+				class=SIDEBAR_ITEM_CLASSES
+			>
+				<ClockCounterClockwise
+					weight=IconWeight::Regular
+					class="base-content"
+					size="24px"
+				/>
+				<p data-expanded=expanded class=SIDEBAR_ITEM_TEXT_CLASSES>
+					{i18n("sidebar.history")}
+				</p>
+			</button>
+		</div>
+	}
+}
+
 #[component]
 pub fn PlaylistsButton() -> impl IntoView {
 	let expanded = expect_context::<ExpandedCtx>().0;
@@ -178,6 +210,58 @@ pub fn PlaylistsButton() -> impl IntoView {
 	}
 }
 
+#[component]
+pub fn GroupButtons() -> impl IntoView {
+	let subscriptions = expect_context::<SubscriptionsCtx>().0;
+	let groups = move || {
+		subscriptions.with(|subs| {
+			subs.groups
+				.iter()
+				.map(|group| group.name.clone())
+				.collect::<Vec<_>>()
+		})
+	};
+
+	view! {
+		<Show when=move || subscriptions.with(|subs| !subs.groups.is_empty())>
+			<div class="border-b-[1px] border-b-primary">
+				<For each=groups key=|name| name.clone() let:name>
+					<GroupButton name=name/>
+				</For>
+			</div>
+		</Show>
+	}
+}
+
+#[component]
+pub fn GroupButton(name: String) -> impl IntoView {
+	let expanded = expect_context::<ExpandedCtx>().0;
+	let path = group_path(&name);
+
+	view! {
+		<div
+			data-expanded=expanded
+			data-tip=name.clone()
+			class=SIDEBAR_TOOLTIP_CLASSES
+		>
+			<button
+				on:click=move |_| go_to(&path)
+				data-expanded=expanded
+				class=SIDEBAR_ITEM_CLASSES
+			>
+				<Folder
+					weight=IconWeight::Regular
+					class="base-content"
+					size="24px"
+				/>
+				<p data-expanded=expanded class=SIDEBAR_ITEM_TEXT_CLASSES>
+					{name}
+				</p>
+			</button>
+		</div>
+	}
+}
+
 #[component]
 pub fn Subs() -> impl IntoView {
 	let expanded = expect_context::<ExpandedCtx>().0;
@@ -1,14 +1,27 @@
-use config::{Config, HomepageCategory, RememberPosition};
+use config::{
+	BlocklistConfig, CaptionsConfig, Config, FilterConfig, HomepageCategory,
+	KeybindingsConfig, RememberPosition, SponsorBlockConfig,
+};
 use invidious::{AudioQuality, VideoQuality};
 use leptos::{
-	create_effect, provide_context, slice, RwSignal, Signal, SignalGet,
-	SignalSetter,
+	create_effect, provide_context, slice, spawn_local, RwSignal, Signal,
+	SignalGet, SignalSetter,
 };
 use locales::RustyTubeLocale;
 
+/// The whole config, for replacing it at once when restoring a backup.
+#[derive(Clone, Copy)]
+pub struct ConfigCtx(pub RwSignal<Config>);
+
 pub fn provide_config_context_slices(config: Config) {
 	let config = RwSignal::new(config);
-	create_effect(move |_| config.get().save());
+	provide_context(ConfigCtx(config));
+	create_effect(move |_| {
+		let config = config.get();
+		spawn_local(async move {
+			let _ = config.save().await;
+		});
+	});
 
 	let server_ctx = NetworkConfigCtx {
 		server_slice: slice!(config.network.server),
@@ -35,6 +48,7 @@ pub fn provide_config_context_slices(config: Config) {
 		),
 		remember_position_slice: slice!(config.player.remember_position),
 		volume_slice: slice!(config.player.volume),
+		keybindings_slice: slice!(config.player.keybindings),
 	};
 
 	let region_ctx = RegionConfigCtx {
@@ -47,25 +61,25 @@ pub fn provide_config_context_slices(config: Config) {
 	};
 
 	let sponsorblock_ctx = SponsorBlockConfigCtx {
-		enabled: slice!(config.sponsorblock.enabled),
-		skip_sponsors: slice!(config.sponsorblock.skip_sponsors),
-		skip_selfpromos: slice!(config.sponsorblock.skip_selfpromos),
-		skip_interactions: slice!(config.sponsorblock.skip_interactions),
-		skip_intros: slice!(config.sponsorblock.skip_intros),
-		skip_outros: slice!(config.sponsorblock.skip_outros),
-		skip_previews: slice!(config.sponsorblock.skip_previews),
-		skip_irrelevant_music: slice!(
-			config.sponsorblock.skip_irrelevant_music
-		),
-		skip_filler: slice!(config.sponsorblock.skip_filler),
+		sponsorblock_slice: slice!(config.sponsorblock),
 	};
 
+	let filter_ctx = FilterConfigCtx {
+		filters_slice: slice!(config.filters),
+		blocklist_slice: slice!(config.blocklist),
+	};
+
+	let captions_ctx =
+		CaptionsConfigCtx { captions_slice: slice!(config.captions) };
+
 	provide_context(server_ctx);
 	provide_context(ui_ctx);
 	provide_context(player_ctx);
 	provide_context(region_ctx);
 	provide_context(privacy_ctx);
 	provide_context(sponsorblock_ctx);
+	provide_context(filter_ctx);
+	provide_context(captions_ctx);
 }
 
 #[allow(clippy::struct_field_names)]
@@ -98,6 +112,8 @@ pub struct PlayerConfigCtx {
 	pub remember_position_slice:
 		(Signal<RememberPosition>, SignalSetter<RememberPosition>),
 	pub volume_slice: (Signal<f64>, SignalSetter<f64>),
+	pub keybindings_slice:
+		(Signal<KeybindingsConfig>, SignalSetter<KeybindingsConfig>),
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -116,13 +132,18 @@ pub struct PrivacyConfigCtx {
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct SponsorBlockConfigCtx {
-	pub enabled: (Signal<bool>, SignalSetter<bool>),
-	pub skip_sponsors: (Signal<bool>, SignalSetter<bool>),
-	pub skip_selfpromos: (Signal<bool>, SignalSetter<bool>),
-	pub skip_interactions: (Signal<bool>, SignalSetter<bool>),
-	pub skip_intros: (Signal<bool>, SignalSetter<bool>),
-	pub skip_outros: (Signal<bool>, SignalSetter<bool>),
-	pub skip_previews: (Signal<bool>, SignalSetter<bool>),
-	pub skip_irrelevant_music: (Signal<bool>, SignalSetter<bool>),
-	pub skip_filler: (Signal<bool>, SignalSetter<bool>),
+	pub sponsorblock_slice:
+		(Signal<SponsorBlockConfig>, SignalSetter<SponsorBlockConfig>),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct FilterConfigCtx {
+	pub filters_slice: (Signal<FilterConfig>, SignalSetter<FilterConfig>),
+	pub blocklist_slice:
+		(Signal<BlocklistConfig>, SignalSetter<BlocklistConfig>),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct CaptionsConfigCtx {
+	pub captions_slice: (Signal<CaptionsConfig>, SignalSetter<CaptionsConfig>),
 }
@@ -1,9 +1,11 @@
 mod config;
 mod player;
+mod queue;
 mod toaster;
 mod user;
 
 pub use config::*;
 pub use player::*;
+pub use queue::*;
 pub use toaster::*;
 pub use user::*;
@@ -1,27 +1,44 @@
-use std::ops::RangeBounds;
+use std::collections::HashSet;
 
-use invidious::Format;
+use config::{RememberPosition, SegmentAction};
+use invidious::{Format, Video};
 use leptos::{
-	create_rw_signal, error::Result, expect_context, web_sys, RwSignal,
-	SignalGet, SignalSet,
+	create_rw_signal, error::Result, expect_context, use_context, web_sys,
+	RwSignal, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate,
+	SignalWithUntracked,
 };
+use leptos_router::create_query_signal;
 use rustytube_error::RustyTubeError;
+use sponsorblock_rs::Segment;
 use utils::get_element_by_id;
 use web_sys::{HtmlAudioElement, HtmlVideoElement};
 
 use crate::{
-	contexts::PlayerConfigCtx,
-	resources::SponsorBlockResource,
+	contexts::{
+		FilterConfigCtx, PlayerConfigCtx, PrivacyConfigCtx, QueueCtx,
+		SponsorBlockConfigCtx,
+	},
+	resources::{
+		segment_action, CaptionsCtx, HistoryCtx, PlaylistVideosResource,
+		PositionsCtx, SponsorBlockResource, TimeSavedCtx, VideoResource,
+	},
 	utils::{i18n, is_webkit},
 };
 
-use super::{toast, Toast};
+use super::{toast, Toast, ToastDuration, ToastType};
 
 pub const VIDEO_CONTAINER_ID: &str = "video_container";
 pub const VIDEO_PLAYER_ID: &str = "video_player";
 pub const VIDEO_CONTROLS_ID: &str = "video_controls";
 pub const AUDIO_PLAYER_ID: &str = "audio_player";
 
+/// Seconds of playback between writes to the position store.
+const POSITION_SAVE_INTERVAL: f64 = 5f64;
+/// Positions closer than this to either end of a video are not kept.
+const POSITION_MARGIN: f64 = 10f64;
+/// Videos no longer than this are treated as shorts.
+const SHORT_VIDEO_LENGTH: u32 = 180;
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub enum PlaybackState {
 	Playing,
@@ -41,6 +58,24 @@ pub struct PlayerState {
 	pub duration: RwSignal<f64>,
 	pub current_time_str: RwSignal<String>,
 	pub duration_str: RwSignal<String>,
+	video_id: RwSignal<Option<String>>,
+	remember_position: RwSignal<bool>,
+	resume_time: RwSignal<Option<f64>>,
+	saved_time: RwSignal<f64>,
+	/// Muted by the user, leaving the configured volume as it is.
+	pub muted: RwSignal<bool>,
+	queue: QueueCtx,
+	positions: PositionsCtx,
+	time_saved: TimeSavedCtx,
+	captions: CaptionsCtx,
+	sponsorblock: SponsorBlockResource,
+	/// Segments of the current video that were skipped, or unskipped.
+	skipped_segments: RwSignal<HashSet<String>>,
+	/// The segment playing, if its category is set to be skipped manually.
+	pub skip_prompt: RwSignal<Option<Segment>>,
+	segment_muted: RwSignal<bool>,
+	video_resource: Option<VideoResource>,
+	playlist_videos: Option<PlaylistVideosResource>,
 }
 
 impl PlayerState {
@@ -56,6 +91,21 @@ impl PlayerState {
 		let duration_str = create_rw_signal(String::from("0:00"));
 		let current_time = create_rw_signal(0f64);
 		let duration = create_rw_signal(0f64);
+		let video_id = create_rw_signal(None);
+		let remember_position = create_rw_signal(false);
+		let resume_time = create_rw_signal(None);
+		let saved_time = create_rw_signal(0f64);
+		let muted = create_rw_signal(false);
+		let queue = expect_context::<QueueCtx>();
+		let positions = expect_context::<PositionsCtx>();
+		let time_saved = expect_context::<TimeSavedCtx>();
+		let captions = expect_context::<CaptionsCtx>();
+		let sponsorblock = expect_context::<SponsorBlockResource>();
+		let skipped_segments = create_rw_signal(HashSet::new());
+		let skip_prompt = create_rw_signal(None);
+		let segment_muted = create_rw_signal(false);
+		let video_resource = use_context::<VideoResource>();
+		let playlist_videos = use_context::<PlaylistVideosResource>();
 
 		Self {
 			format,
@@ -67,6 +117,89 @@ impl PlayerState {
 			duration,
 			current_time_str,
 			duration_str,
+			video_id,
+			remember_position,
+			resume_time,
+			saved_time,
+			muted,
+			queue,
+			positions,
+			time_saved,
+			captions,
+			sponsorblock,
+			skipped_segments,
+			skip_prompt,
+			segment_muted,
+			video_resource,
+			playlist_videos,
+		}
+	}
+
+	pub fn load_position(&self, video: &Video) {
+		let remember = match expect_context::<PlayerConfigCtx>()
+			.remember_position_slice
+			.0
+			.get_untracked()
+		{
+			RememberPosition::Always => true,
+			RememberPosition::VideosOnly => {
+				!video.genre.eq("Music") && video.length > SHORT_VIDEO_LENGTH
+			}
+			RememberPosition::Never => false,
+		};
+
+		let remember = remember && !video.live;
+		self.skipped_segments.set(HashSet::new());
+		self.skip_prompt.set(None);
+		self.segment_muted.set(false);
+		self.saved_time.set(0f64);
+		self.video_id.set(Some(video.id.clone()));
+		self.remember_position.set(remember);
+		self.resume_time
+			.set(remember.then(|| self.positions.get(&video.id)).flatten());
+	}
+
+	pub fn restore_position(&self) -> Result<(), RustyTubeError> {
+		if let Some(time) = self.resume_time.get_untracked() {
+			let video = get_element_by_id::<HtmlVideoElement>(VIDEO_PLAYER_ID)?;
+			let audio = get_element_by_id::<HtmlAudioElement>(AUDIO_PLAYER_ID)?;
+
+			self.resume_time.set(None);
+			self.saved_time.set(time);
+			video.set_current_time(time);
+			audio.set_current_time(time);
+			self.current_time.set(time);
+			self.current_time_str.set(utils::unix_to_hours_secs_mins(time));
+		}
+		Ok(())
+	}
+
+	fn save_progress(&self, time: f64, duration: f64) {
+		let Some(id) = self.video_id.get_untracked() else {
+			return;
+		};
+		if self.resume_time.get_untracked().is_some()
+			|| !duration.is_finite()
+			|| (time - self.saved_time.get_untracked()).abs()
+				< POSITION_SAVE_INTERVAL
+		{
+			return;
+		}
+
+		self.saved_time.set(time);
+		if expect_context::<PrivacyConfigCtx>()
+			.keep_history_slice
+			.0
+			.get_untracked()
+		{
+			expect_context::<HistoryCtx>().set_progress(&id, time);
+		}
+		if self.remember_position.get_untracked() {
+			if time < POSITION_MARGIN || time > duration - POSITION_MARGIN {
+				self.positions.remove(&id);
+			} else {
+				self.positions.set(&id, time);
+			}
 		}
 	}
 
@@ -250,9 +383,61 @@ impl PlayerState {
 		self.current_time_str.set(utils::unix_to_hours_secs_mins(current_time));
 		self.duration_str.set(utils::unix_to_hours_secs_mins(total_time));
 		self.check_sponsorblock(current_time);
+		self.save_progress(current_time, total_time);
 		Ok(())
 	}
 
+	/// Advances to the next queued video, falling back to the next playlist
+	/// entry or first unblocked recommended video when autoplay is enabled.
+	pub fn ended(&self) {
+		if let Some(id) = self.next_video_id() {
+			self.playback_state.set(PlaybackState::Loading);
+			create_query_signal::<String>("id").1.set(Some(id));
+		} else {
+			self.playback_state.set(PlaybackState::Paused);
+		}
+	}
+
+	fn next_video_id(&self) -> Option<String> {
+		if let Some(item) = self.queue.pop_next() {
+			return Some(item.id);
+		}
+		if !expect_context::<PlayerConfigCtx>()
+			.auto_play_slice
+			.0
+			.get_untracked()
+		{
+			return None;
+		}
+
+		let current_id = self.video_id.get_untracked()?;
+		let playlist_next = self
+			.playlist_videos
+			.and_then(|playlist| playlist.resource.get_untracked())
+			.and_then(|result| result.ok())
+			.and_then(|ids| {
+				let index = ids.iter().position(|id| id.eq(&current_id))?;
+				ids.get(index + 1).cloned()
+			});
+
+		playlist_next.or_else(|| {
+			self.video_resource
+				.and_then(|video| video.resource.get_untracked())
+				.and_then(|result| result.ok())
+				.and_then(|video| {
+					let blocklist = expect_context::<FilterConfigCtx>()
+						.blocklist_slice
+						.0
+						.get_untracked();
+					video
+						.recommended_videos
+						.into_iter()
+						.find(|video| !blocklist.blocks(video))
+						.map(|video| video.id)
+				})
+		})
+	}
+
 	pub fn change_format(&self, format: Format) -> Result<(), RustyTubeError> {
 		let video = get_element_by_id::<HtmlVideoElement>(VIDEO_PLAYER_ID)?;
 		let audio = get_element_by_id::<HtmlAudioElement>(AUDIO_PLAYER_ID)?;
@@ -279,27 +464,150 @@ impl PlayerState {
 		audio.set_volume(volume);
 		self.volume.set(volume);
 		expect_context::<PlayerConfigCtx>().volume_slice.1.set(volume);
+		if self.muted.get_untracked() {
+			self.muted.set(false);
+			self.apply_muted()?;
+		}
 
 		Ok(())
 	}
 
+	/// Seeks by `offset` seconds, clamped to the bounds of the video.
+	pub fn seek_by(&self, offset: f64) -> Result<(), RustyTubeError> {
+		let duration = self.duration.get_untracked();
+		let time = (self.current_time.get_untracked() + offset).max(0f64);
+		let time = if duration.is_finite() && duration > 0f64 {
+			time.min(duration)
+		} else {
+			time
+		};
+		self.seek(time)
+	}
+
+	/// Seeks to `fraction` of the video, where `0.5` is the halfway point.
+	pub fn seek_to_fraction(
+		&self,
+		fraction: f64,
+	) -> Result<(), RustyTubeError> {
+		let duration = self.duration.get_untracked();
+		if duration.is_finite() && duration > 0f64 {
+			self.seek(duration * fraction.clamp(0f64, 1f64))?;
+		}
+		Ok(())
+	}
+
+	pub fn change_volume_by(&self, offset: f64) -> Result<(), RustyTubeError> {
+		self.set_volume(
+			(self.volume.get_untracked() + offset).clamp(0f64, 1f64),
+		)
+	}
+
+	pub fn toggle_mute(&self) -> Result<(), RustyTubeError> {
+		self.muted.set(!self.muted.get_untracked());
+		self.apply_muted()
+	}
+
+	/// Shows the next captions, turning them off after the last ones.
+	pub fn cycle_captions(&self) {
+		self.captions.cycle();
+	}
+
+	/// Applies the configured action of the segments playing at `time`.
+	/// Each segment is skipped at most once per video, so seeking back into
+	/// one plays it.
 	pub fn check_sponsorblock(&self, time: f64) {
-		if let Some(segments) =
-			expect_context::<SponsorBlockResource>().get_segments()
-		{
-			for segment in segments {
-				let range = (segment.timeframe.0.round() - 1f64)
-					..=(segment.timeframe.0.round() + 1f64);
-				if range.contains(&time) {
-					self.seek(segment.timeframe.1);
-					toast(Toast::new(
-						i18n("sponsorblock.skipped")(),
-						Some(super::ToastDuration::Normal),
-						Some(super::ToastType::Info),
-					));
+		let Some(segments) = self.sponsorblock.get_segments() else {
+			return;
+		};
+		let config = expect_context::<SponsorBlockConfigCtx>()
+			.sponsorblock_slice
+			.0
+			.get_untracked();
+
+		let mut prompt = None;
+		let mut mute = false;
+		for segment in segments {
+			let (start, end) = segment.timeframe;
+			if time < start || time >= end {
+				continue;
+			}
+			let skipped = self
+				.skipped_segments
+				.with_untracked(|skipped| skipped.contains(&segment.uuid));
+			match segment_action(&config, &segment) {
+				SegmentAction::AutoSkip if !skipped => {
+					self.auto_skip_segment(&segment);
+					return;
 				}
+				SegmentAction::ManualSkip if !skipped => {
+					prompt = Some(segment);
+				}
+				SegmentAction::Mute => mute = true,
+				_ => (),
 			}
 		}
+
+		let prompt_changed = self.skip_prompt.with_untracked(|current| {
+			current.as_ref().map(|segment| &segment.uuid)
+				!= prompt.as_ref().map(|segment| &segment.uuid)
+		});
+		if prompt_changed {
+			self.skip_prompt.set(prompt);
+		}
+		let _ = self.set_segment_muted(mute);
+	}
+
+	/// Seeks past `segment`, adding the skipped time to the time saved.
+	///
+	/// Returns the seconds skipped.
+	pub fn skip_segment(&self, segment: &Segment) -> f64 {
+		let saved =
+			(segment.timeframe.1 - self.current_time.get_untracked()).max(0f64);
+		self.skipped_segments.update(|skipped| {
+			skipped.insert(segment.uuid.clone());
+		});
+		self.skip_prompt.set(None);
+		self.time_saved.add(saved);
+		let _ = self.seek(segment.timeframe.1);
+		saved
+	}
+
+	fn auto_skip_segment(&self, segment: &Segment) {
+		let saved = self.skip_segment(segment);
+		let state = *self;
+		let start = segment.timeframe.0;
+
+		toast(
+			Toast::new(
+				i18n("sponsorblock.skipped")(),
+				Some(ToastDuration::Long),
+				Some(ToastType::Info),
+			)
+			.with_action(i18n("sponsorblock.unskip")(), move || {
+				state.time_saved.add(-saved);
+				let _ = state.seek(start);
+			}),
+		);
+	}
+
+	fn set_segment_muted(&self, muted: bool) -> Result<(), RustyTubeError> {
+		if self.segment_muted.get_untracked() == muted {
+			return Ok(());
+		}
+		self.segment_muted.set(muted);
+		self.apply_muted()
+	}
+
+	/// Mutes the players while the user or a segment mutes them.
+	fn apply_muted(&self) -> Result<(), RustyTubeError> {
+		let video = get_element_by_id::<HtmlVideoElement>(VIDEO_PLAYER_ID)?;
+		let audio = get_element_by_id::<HtmlAudioElement>(AUDIO_PLAYER_ID)?;
+
+		let muted =
+			self.muted.get_untracked() || self.segment_muted.get_untracked();
+		video.set_muted(muted);
+		audio.set_muted(muted);
+		Ok(())
 	}
 }
 
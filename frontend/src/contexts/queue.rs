@@ -0,0 +1,126 @@
+use invidious::{CommonVideo, PlaylistItem, PopularItem, VideoShort};
+use leptos::{provide_context, RwSignal, SignalUpdate};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QueueItem {
+	pub id: String,
+	pub title: String,
+	pub author: String,
+	pub author_id: String,
+	pub thumbnail: Option<String>,
+}
+
+impl From<&CommonVideo> for QueueItem {
+	fn from(video: &CommonVideo) -> Self {
+		Self {
+			id: video.id.clone(),
+			title: video.title.clone(),
+			author: video.author.clone(),
+			author_id: video.author_id.clone(),
+			thumbnail: video.thumbnails.get(4).map(|thumb| thumb.url.clone()),
+		}
+	}
+}
+
+impl From<&VideoShort> for QueueItem {
+	fn from(video: &VideoShort) -> Self {
+		Self {
+			id: video.id.clone(),
+			title: video.title.clone(),
+			author: video.author.clone(),
+			author_id: video.author_id.clone(),
+			thumbnail: video.thumbnails.get(4).map(|thumb| thumb.url.clone()),
+		}
+	}
+}
+
+impl From<&PopularItem> for QueueItem {
+	fn from(video: &PopularItem) -> Self {
+		Self {
+			id: video.id.clone(),
+			title: video.title.clone(),
+			author: video.author.clone(),
+			author_id: video.author_id.clone(),
+			thumbnail: video.thumbnails.get(4).map(|thumb| thumb.url.clone()),
+		}
+	}
+}
+
+impl From<&PlaylistItem> for QueueItem {
+	fn from(video: &PlaylistItem) -> Self {
+		Self {
+			id: video.id.clone(),
+			title: video.title.clone(),
+			author: video.author.clone(),
+			author_id: video.author_id.clone(),
+			thumbnail: video.thumbnails.get(4).map(|thumb| thumb.url.clone()),
+		}
+	}
+}
+
+/// User-managed list of videos played before any autoplay source.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct QueueCtx(pub RwSignal<Vec<QueueItem>>);
+
+impl QueueCtx {
+	pub fn play_next(&self, item: QueueItem) {
+		self.0.update(|queue| {
+			queue.retain(|queued| !queued.id.eq(&item.id));
+			queue.insert(0, item);
+		});
+	}
+
+	pub fn add(&self, item: QueueItem) {
+		self.0.update(|queue| {
+			if !queue.iter().any(|queued| queued.id.eq(&item.id)) {
+				queue.push(item);
+			}
+		});
+	}
+
+	pub fn remove(&self, id: &str) {
+		self.0.update(|queue| queue.retain(|queued| !queued.id.eq(id)));
+	}
+
+	pub fn move_up(&self, id: &str) {
+		self.0.update(|queue| {
+			if let Some(index) =
+				queue.iter().position(|queued| queued.id.eq(id))
+			{
+				if index > 0 {
+					queue.swap(index, index - 1);
+				}
+			}
+		});
+	}
+
+	pub fn move_down(&self, id: &str) {
+		self.0.update(|queue| {
+			if let Some(index) =
+				queue.iter().position(|queued| queued.id.eq(id))
+			{
+				if index + 1 < queue.len() {
+					queue.swap(index, index + 1);
+				}
+			}
+		});
+	}
+
+	pub fn pop_next(&self) -> Option<QueueItem> {
+		let mut next = None;
+		self.0.update(|queue| {
+			if !queue.is_empty() {
+				next = Some(queue.remove(0));
+			}
+		});
+		next
+	}
+
+	pub fn clear(&self) {
+		self.0.update(Vec::clear);
+	}
+}
+
+pub fn provide_queue_ctx() {
+	provide_context(QueueCtx(RwSignal::new(vec![])));
+}
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{rc::Rc, time::Duration};
 
 use leptos::{
 	expect_context, provide_context, RwSignal, SignalUpdate, StoredValue,
@@ -33,6 +33,7 @@ pub struct Toast {
 	pub message: StoredValue<String>,
 	pub duration: Option<ToastDuration>,
 	pub r#type: Option<ToastType>,
+	pub action: Option<StoredValue<ToastAction>>,
 }
 
 impl Toast {
@@ -41,10 +42,28 @@ impl Toast {
 		duration: Option<ToastDuration>,
 		r#type: Option<ToastType>,
 	) -> Self {
-		Self { message: StoredValue::new(message), duration, r#type }
+		Self {
+			message: StoredValue::new(message),
+			duration,
+			r#type,
+			action: None,
+		}
+	}
+
+	/// Adds a button that runs `run` and dismisses the toast.
+	#[must_use]
+	pub fn with_action(self, label: String, run: impl Fn() + 'static) -> Self {
+		let action = ToastAction { label, run: Rc::new(run) };
+		Self { action: Some(StoredValue::new(action)), ..self }
 	}
 }
 
+#[derive(Clone)]
+pub struct ToastAction {
+	pub label: String,
+	pub run: Rc<dyn Fn()>,
+}
+
 #[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub enum ToastType {
 	#[default]
@@ -11,46 +11,55 @@ mod resources;
 mod themes;
 mod utils;
 
-use config::Config;
-
-use leptos::{component, mount_to_body, provide_context, view, IntoView};
+use invidious::prune_cache;
+use leptos::{
+	component, mount_to_body, provide_context, spawn_local, view, IntoView,
+};
 use leptos_router::{Route, Router, Routes};
 pub use themes::*;
 
 use crate::{
-	components::Page,
-	contexts::{provide_config_context_slices, provide_toaster_ctx},
+	components::{FerrisError, Page},
+	contexts::{
+		provide_config_context_slices, provide_queue_ctx, provide_toaster_ctx,
+	},
 	pages::{
-		ChannelPage, PlaylistsSection, PopularSection, SearchSection,
-		SettingsPage, SubscriptionsSection, TrendingSection, VideoPage,
+		ChannelPage, HistorySection, HomepageSection, PlaylistsSection,
+		PopularSection, SearchSection, SettingsPage, SubscriptionsSection,
+		TrendingSection, VideoPage,
 	},
 	resources::{
-		InstancesResource, PlaylistsCtx, SponsorBlockResource,
-		SubscriptionsCtx, SubscriptionsThumbnailsResource,
-		SubscriptionsVideosResource,
+		sync_server_pool, HistoryCtx, InstancesResource, PlaylistsCtx,
+		PositionsCtx, SponsorBlockResource, StoredData, SubscriptionsCtx,
+		SubscriptionsThumbnailsResource, SubscriptionsVideosResource,
+		TimeSavedCtx,
 	},
 };
 
 #[component]
-fn App() -> impl IntoView {
-	console_error_panic_hook::set_once();
+fn App(stored: StoredData) -> impl IntoView {
 	provide_toaster_ctx();
+	provide_queue_ctx();
 	provide_context::<SponsorBlockResource>(SponsorBlockResource::empty());
 
-	provide_config_context_slices(Config::load().unwrap_or_default());
+	provide_config_context_slices(stored.config);
+	provide_context(PlaylistsCtx::initialise(stored.playlists));
+	provide_context(HistoryCtx::initialise(stored.history));
+	provide_context(PositionsCtx::initialise(stored.positions));
+	provide_context(TimeSavedCtx::initialise(stored.time_saved));
 
-	let subscriptions = SubscriptionsCtx::initialise();
+	let subscriptions = SubscriptionsCtx::initialise(stored.subscriptions);
 	provide_context(subscriptions);
 	provide_context(SubscriptionsVideosResource::initialise(subscriptions));
 	provide_context(SubscriptionsThumbnailsResource::initialise(subscriptions));
 	provide_context(InstancesResource::initialise());
-	provide_context(PlaylistsCtx::initialise());
+	sync_server_pool();
 
 	view! {
 		<Router>
 			<Routes>
 				<Route path="" view=move || view! { <Page/> }>
-					<Route path="/" view=move || view! { <TrendingSection/> }/>
+					<Route path="/" view=move || view! { <HomepageSection/> }/>
 					<Route path="/player" view=move || view! { <VideoPage/> }/>
 					<Route
 						path="/channel"
@@ -80,6 +89,10 @@ fn App() -> impl IntoView {
 						path="/playlists"
 						view=move || view! { <PlaylistsSection/> }
 					/>
+					<Route
+						path="/history"
+						view=move || view! { <HistorySection/> }
+					/>
 					<Route
 						path="/settings"
 						view=move || view! { <SettingsPage/> }
@@ -92,5 +105,20 @@ fn App() -> impl IntoView {
 }
 
 fn main() {
-	mount_to_body(|| view! { <App/> });
+	console_error_panic_hook::set_once();
+	spawn_local(async {
+		match StoredData::load().await {
+			Ok(stored) => {
+				mount_to_body(move || view! { <App stored=stored/> });
+				let _ = prune_cache().await;
+			}
+			Err(error) => mount_to_body(move || {
+				view! {
+					<div class="flex justify-center items-center w-screen h-screen">
+						<FerrisError error=error/>
+					</div>
+				}
+			}),
+		}
+	});
 }
@@ -0,0 +1,192 @@
+use chrono::DateTime;
+use invidious::HistoryEntry;
+use leptos::*;
+use phosphor_leptos::{IconWeight, Trash};
+use urlencoding::encode;
+use utils::get_element_by_id;
+use web_sys::{HtmlDialogElement, MouseEvent};
+
+use crate::{
+	components::{CardGrid, GridContainer},
+	resources::HistoryCtx,
+	utils::{go_to, i18n},
+};
+
+#[component]
+pub fn HistorySection() -> impl IntoView {
+	let history = expect_context::<HistoryCtx>().0;
+	let query = RwSignal::new(String::new());
+
+	let entries = move || history.get().search(&query.get());
+
+	view! {
+		<GridContainer>
+			<div class="flex flex-row flex-wrap gap-4 justify-between items-center">
+				<h1 class="text-2xl font-semibold">{i18n("history.history")}</h1>
+				<div class="flex flex-row flex-wrap gap-4 items-center">
+					<input
+						on:input=move |ev| query.set(event_target_value(&ev))
+						type="text"
+						placeholder=i18n("history.search_placeholder")
+						class="w-48 md:w-60 lg:w-72 input input-sm input-bordered input-primary md:input-md"
+					/>
+					<ExportHistoryButton/>
+					<ClearHistoryButton/>
+				</div>
+			</div>
+			<Show
+				when=move || !entries().is_empty()
+				fallback=move || view! { <p>{i18n("history.empty")}</p> }
+			>
+				<CardGrid>
+					<For
+						each=entries
+						key=|entry| (entry.id.clone(), entry.watched)
+						let:entry
+					>
+						<HistoryPreviewCard entry=entry/>
+					</For>
+				</CardGrid>
+			</Show>
+		</GridContainer>
+	}
+}
+
+#[component]
+pub fn HistoryPreviewCard(entry: HistoryEntry) -> impl IntoView {
+	let history = expect_context::<HistoryCtx>();
+	let img_loaded = create_rw_signal(false);
+	let image_classes = move || {
+		if img_loaded.get() {
+			"w-full aspect-video object-center object-cover bg-neutral rounded-xl"
+		} else {
+			"animate-pulse w-full aspect-video bg-neutral rounded-xl"
+		}
+	};
+
+	let id = StoredValue::new(entry.id);
+	let author_id = StoredValue::new(entry.author_id);
+	let open_video = move |_| go_to(format!("/player?id={}", id.get_value()));
+	let go_to_channel_page =
+		move |_| go_to(format!("/channel?id={}", author_id.get_value()));
+	let remove = move |_| history.remove(&id.get_value());
+
+	let watched = i64::try_from(entry.watched)
+		.ok()
+		.and_then(|watched| DateTime::from_timestamp(watched, 0))
+		.map(|watched| watched.format("%Y-%m-%d %H:%M").to_string())
+		.unwrap_or_default();
+
+	view! {
+		<div class="flex overflow-hidden flex-col">
+			<div
+				on:click=open_video
+				class="overflow-hidden w-full max-w-full rounded-xl cursor-pointer"
+			>
+				<img
+					decoding="async"
+					on:load=move |_| img_loaded.set(true)
+					src=entry.thumbnail
+					class=image_classes
+				/>
+			</div>
+			<progress
+				class="w-full progress progress-primary"
+				value=entry.progress
+				max=entry.length
+			></progress>
+			<div class="flex flex-row justify-between px-2 mt-3 w-full">
+				<div class="flex flex-col space-y-3 cursor-text">
+					<h1 class="font-sans text-base font-semibold line-clamp-2">
+						{entry.title}
+					</h1>
+					<div class="flex flex-row flex-wrap gap-1 items-center text-sm font-normal">
+						<h2
+							on:click=go_to_channel_page
+							class="cursor-pointer text-primary"
+						>
+							{entry.author}
+						</h2>
+						<p>{"•"}</p>
+						<p>{watched}</p>
+					</div>
+				</div>
+				<div
+					class="tooltip tooltip-left tooltip-info"
+					data-tip=i18n("history.remove")
+				>
+					<button on:click=remove class="btn btn-ghost btn-sm">
+						<Trash
+							weight=IconWeight::Regular
+							class="w-5 h-5 base-content"
+						/>
+					</button>
+				</div>
+			</div>
+		</div>
+	}
+}
+
+#[component]
+pub fn ExportHistoryButton() -> impl IntoView {
+	let history = expect_context::<HistoryCtx>().0;
+
+	let href = move || {
+		let history_json = history.get().to_json_string().unwrap_or_default();
+		let encoded_history = encode(&history_json);
+		format!("data:attachment/text,{encoded_history}")
+	};
+
+	view! {
+		<a
+			href=href
+			download="rustytube_history.json"
+			class="btn btn-sm btn-primary md:btn-md"
+		>
+			{i18n("settings.export")}
+		</a>
+	}
+}
+
+#[component]
+pub fn ClearHistoryButton() -> impl IntoView {
+	let history = expect_context::<HistoryCtx>();
+
+	let modal_id = StoredValue::new("clear_history_modal");
+	let open_modal = move |_| {
+		get_element_by_id::<HtmlDialogElement>(modal_id.get_value())
+			.expect("clear history modal should exist")
+			.set_open(true);
+	};
+
+	let close_modal = move |_| {
+		get_element_by_id::<HtmlDialogElement>(modal_id.get_value())
+			.expect("clear history modal should exist")
+			.set_open(false);
+	};
+
+	let clear_history = move |ev: MouseEvent| {
+		history.clear();
+		close_modal(ev);
+	};
+
+	view! {
+		<button on:click=open_modal class="btn btn-sm btn-error md:btn-md">
+			{i18n("settings.delete_all")}
+		</button>
+		<dialog id=modal_id.get_value() class="modal">
+			<div class="modal-box">
+				<h3 class="text-lg font-bold">{i18n("history.clear")}</h3>
+				<p class="py-4">{i18n("history.clear_description")}</p>
+				<div class="modal-action">
+					<button on:click=close_modal class="btn btn-ghost">
+						{i18n("settings.close")}
+					</button>
+					<button on:click=clear_history class="btn btn-error">
+						{i18n("settings.delete_all")}
+					</button>
+				</div>
+			</div>
+		</dialog>
+	}
+}
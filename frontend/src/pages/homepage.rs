@@ -0,0 +1,25 @@
+use config::HomepageCategory;
+use leptos::{component, expect_context, view, IntoView, SignalGet};
+
+use crate::{
+	contexts::UiConfigCtx,
+	pages::{
+		HistorySection, LocalPlaylistSection, PopularSection,
+		SubscriptionsSection, TrendingSection,
+	},
+};
+
+#[component]
+pub fn HomepageSection() -> impl IntoView {
+	let homepage = expect_context::<UiConfigCtx>().homepage_slice.0;
+
+	move || match homepage.get() {
+		HomepageCategory::Trending => view! { <TrendingSection/> },
+		HomepageCategory::Popular => view! { <PopularSection/> },
+		HomepageCategory::Subscriptions => view! { <SubscriptionsSection/> },
+		HomepageCategory::History => view! { <HistorySection/> },
+		HomepageCategory::Playlist(id) => {
+			view! { <LocalPlaylistSection id=id/> }
+		}
+	}
+}
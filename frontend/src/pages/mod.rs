@@ -1,4 +1,6 @@
 mod channel;
+mod history;
+mod homepage;
 mod playlists;
 mod popular;
 mod search;
@@ -8,10 +10,12 @@ mod trending;
 mod video;
 
 pub use channel::ChannelPage;
-pub use playlists::PlaylistsSection;
+pub use history::HistorySection;
+pub use homepage::HomepageSection;
+pub use playlists::{LocalPlaylistSection, PlaylistsSection};
 pub use popular::PopularSection;
 pub use search::SearchSection;
 pub use settings::SettingsPage;
-pub use subscriptions::SubscriptionsSection;
+pub use subscriptions::{group_path, SubscriptionsSection};
 pub use trending::TrendingSection;
 pub use video::VideoPage;
@@ -1,16 +1,22 @@
-use leptos::{component, view, For, IntoView, Props, SignalGet, Suspense};
+use invidious::{CommonVideo, LocalPlaylist};
+use leptos::{
+	component, expect_context, view, CollectView, For, IntoView, Props,
+	Resource, SignalGet, StoredValue, Suspense,
+};
 
 use crate::{
 	components::{
-		CardGrid, GridContainer, LocalPlaylistPreviewCard, PlaceholderCardArray,
+		CardGrid, FerrisError, GridContainer, LocalPlaylistPreviewCard,
+		PlaceholderCardArray, VideoPreviewCard,
 	},
+	contexts::NetworkConfigCtx,
 	resources::PlaylistsCtx,
 	utils::i18n,
 };
 
 #[component]
 pub fn PlaylistsSection() -> impl IntoView {
-	let playlists = PlaylistsCtx::initialise();
+	let playlists = expect_context::<PlaylistsCtx>();
 
 	view! {
 		<GridContainer>
@@ -23,7 +29,7 @@ pub fn PlaylistsSection() -> impl IntoView {
 						<CardGrid>
 							<For
 								each=move || playlists.playlists.get()
-								key=|playlist| playlist.created
+								key=|playlist| playlist.id.clone()
 								let:playlist
 							>
 								<LocalPlaylistPreviewCard playlist=playlist/>
@@ -36,3 +42,60 @@ pub fn PlaylistsSection() -> impl IntoView {
 		</GridContainer>
 	}
 }
+
+#[component]
+pub fn LocalPlaylistSection(id: String) -> impl IntoView {
+	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let playlists = expect_context::<PlaylistsCtx>();
+	let id = StoredValue::new(id);
+
+	let title =
+		move || playlists.get(&id.get_value()).map(|playlist| playlist.title);
+
+	// Playlists compare by id, so the source is the video ids, to refetch
+	// when videos are added, removed or moved.
+	let video_ids = move || {
+		playlists.get(&id.get_value()).map_or(vec![], |playlist| {
+			playlist.videos.into_iter().map(|video| video.id).collect()
+		})
+	};
+
+	let videos = Resource::local(
+		move || (video_ids(), server.get()),
+		|(ids, server)| async move {
+			LocalPlaylist::fetch_videos(&server, &ids).await
+		},
+	);
+
+	view! {
+		<GridContainer>
+			<h1 class="text-2xl font-semibold">{title}</h1>
+			<Suspense fallback=move || {
+				view! { <PlaceholderCardArray/> }
+			}>
+				{move || {
+					videos
+						.get()
+						.map(|videos| {
+							view! {
+								<CardGrid>
+									{videos
+										.into_iter()
+										.map(|video| match video {
+											Ok(video) => {
+												view! {
+													<VideoPreviewCard video=CommonVideo::from(video)/>
+												}
+											}
+											Err(err) => view! { <FerrisError error=err/> },
+										})
+										.collect_view()}
+								</CardGrid>
+							}
+						})
+				}}
+
+			</Suspense>
+		</GridContainer>
+	}
+}
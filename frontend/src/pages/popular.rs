@@ -1,18 +1,21 @@
 use invidious::Popular;
-use leptos::{component, view, For, IntoView, Props, SignalGet, Suspense};
+use leptos::{
+	component, view, For, IntoView, Props, SignalGet, SignalWith, Suspense,
+};
 
 use crate::{
 	components::{
 		CardGrid, FerrisError, GridContainer, PlaceholderCardArray,
 		PopularPreviewCard,
 	},
-	resources::PopularResource,
+	resources::{FeedFilter, PopularResource},
 	utils::i18n,
 };
 
 #[component]
 pub fn PopularSection() -> impl IntoView {
 	let popular = PopularResource::initialise();
+	let filter = FeedFilter::memo();
 
 	view! {
 		<GridContainer>
@@ -30,7 +33,11 @@ pub fn PopularSection() -> impl IntoView {
 									view! {
 										<CardGrid>
 											<For
-												each=move || popular.items.clone()
+												each=move || {
+													let mut items = popular.items.clone();
+													filter.with(|filter| filter.apply(&mut items));
+													items
+												}
 												key=|video| video.id.clone()
 												let:video
 											>
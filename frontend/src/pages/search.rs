@@ -1,7 +1,7 @@
 use invidious::{SearchArgs, SearchResult};
 use leptos::{
 	component, view, CollectView, IntoView, RwSignal, Signal, SignalGet,
-	Suspense,
+	SignalWith, Suspense,
 };
 
 use crate::{
@@ -10,8 +10,8 @@ use crate::{
 		PlaceholderCardArray, PlaylistPreviewCard, VideoPreviewCard,
 	},
 	resources::{
-		get_search_args_from_query_map, SearchAction, SearchActionArgs,
-		SearchResource,
+		get_search_args_from_query_map, FeedFilter, SearchAction,
+		SearchActionArgs, SearchResource,
 	},
 	utils::i18n,
 };
@@ -61,6 +61,7 @@ pub fn SearchResults(
 ) -> impl IntoView {
 	let pages = RwSignal::new(vec![search_results]);
 	let search_action = SearchAction::new();
+	let filter = FeedFilter::memo();
 
 	view! {
 		<CardGrid>
@@ -69,7 +70,8 @@ pub fn SearchResults(
 				pages
 					.get()
 					.into_iter()
-					.map(|result_page| {
+					.map(|mut result_page| {
+						filter.with(|filter| filter.apply_search(&mut result_page));
 						result_page
 							.into_iter()
 							.map(|result| match result {
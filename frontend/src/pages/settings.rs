@@ -1,30 +1,47 @@
 use std::str::FromStr;
 
-use gloo::{
-	file::Blob,
-	storage::{LocalStorage, Storage},
+use config::{
+	CaptionsPosition, FilterConfig, FilterField, FilterKind, FilterRule,
+	HomepageCategory, KeybindingsConfig, SegmentAction, SponsorBlockConfig,
+};
+use gloo::file::{
+	futures::{read_as_bytes, read_as_text},
+	Blob, File,
+};
+use invidious::{
+	cache_size, clear_cache, write_freetube_playlists,
+	write_freetube_subscriptions, write_libretube_playlists,
+	write_playlist_csv, CachedChannelFeed, ImportReport, ImportStrategy,
+	LocalPlaylist, NewpipeSubscriptions, ProfileImport, Subscriptions,
 };
-use invidious::{LocalPlaylist, NewpipeSubscriptions, Subscriptions, SUBS_KEY};
 use leptos::{
-	component, create_action, expect_context, view, wasm_bindgen, web_sys,
-	Children, CollectView, For, IntoView, Props, SignalGet, SignalSet,
-	SignalUpdate, StoredValue,
+	component, create_action, event_target_value, expect_context, spawn_local,
+	view, wasm_bindgen, web_sys, Children, CollectView, For, IntoView, Props,
+	Resource, RwSignal, Show, Signal, SignalGet, SignalGetUntracked, SignalSet,
+	SignalSetter, SignalUpdate, SignalWith, StoredValue,
 };
 use locales::RustyTubeLocale;
+use phosphor_leptos::{IconWeight, X};
 use rustytube_error::RustyTubeError;
 use urlencoding::encode;
-use utils::get_element_by_id;
+use utils::{get_element_by_id, is_tauri, unix_to_hours_secs_mins};
 use wasm_bindgen::JsCast;
 use web_sys::{Event, HtmlDialogElement, HtmlInputElement, MouseEvent};
 
 use crate::{
-	contexts::{RegionConfigCtx, SponsorBlockConfigCtx, UiConfigCtx},
+	contexts::{
+		toast, CaptionsConfigCtx, FilterConfigCtx, NetworkConfigCtx,
+		PlayerConfigCtx, PrivacyConfigCtx, RegionConfigCtx,
+		SponsorBlockConfigCtx, Toast, ToastType, UiConfigCtx,
+	},
 	resources::{
-		save_playlists, save_subs, PlaylistsCtx, SubscriptionsCtx,
+		save_subs, BackupPreview, BackupSections, HistoryCtx, ImportPreview,
+		PendingBackup, PlaylistsCtx, ProfileImportReport, SubscriptionsCtx,
 		SubscriptionsThumbnailsResource, SubscriptionsVideosResource,
+		TimeSavedCtx, UserData,
 	},
 	themes::{DARK_THEMES, LIGHT_THEMES},
-	utils::i18n,
+	utils::{i18n, save_file},
 };
 
 #[component]
@@ -33,8 +50,18 @@ pub fn SettingsPage() -> impl IntoView {
 		<div class="flex flex-col items-center w-full h-full">
 			<div class="flex overflow-visible overflow-y-auto overscroll-contain flex-col gap-16 px-6 w-[95vw] my-[3vh] sm:w-[95vw] md:w-[90vw] lg:w-[85vw] xl:w-[50vw]">
 				<SubscriptionsSettings/>
-				// <PlaylistsSettings/>
+				<FilterSettings/>
+				<BlocklistSettings/>
+				<PlaylistsSettings/>
+				<BackupSettings/>
+				<GeneralSettings/>
+				<InstanceSettings/>
+				<PlayerSettings/>
+				<CaptionsSettings/>
+				<KeybindingsSettings/>
 				<SponsorBlockSettings/>
+				<PrivacySettings/>
+				<CacheSettings/>
 				<RegionSettings/>
 				<ThemeSettings/>
 			</div>
@@ -44,7 +71,61 @@ pub fn SettingsPage() -> impl IntoView {
 
 #[component]
 pub fn InstanceSettings() -> impl IntoView {
-	view! {}
+	let custom_servers_slice =
+		expect_context::<NetworkConfigCtx>().custom_servers_slice;
+	let new_server = RwSignal::new(String::new());
+
+	let add_server = move |_| {
+		let server = new_server.get_untracked().trim().to_owned();
+		if server.starts_with("https://") || server.starts_with("http://") {
+			let mut servers =
+				custom_servers_slice.0.get_untracked().unwrap_or_default();
+			if !servers.contains(&server) {
+				servers.push(server);
+				custom_servers_slice.1.set(Some(servers));
+			}
+			new_server.set(String::new());
+		}
+	};
+
+	let remove_server = move |server: String| {
+		let mut servers =
+			custom_servers_slice.0.get_untracked().unwrap_or_default();
+		servers.retain(|existing| !existing.eq(&server));
+		custom_servers_slice.1.set((!servers.is_empty()).then_some(servers));
+	};
+
+	view! {
+		<SettingsSection title=i18n("settings.instances")()>
+			<Setting title=i18n("settings.fallback_servers")()>
+				<For
+					each=move || custom_servers_slice.0.get().unwrap_or_default()
+					key=|server| server.clone()
+					let:server
+				>
+					<div class="gap-2 badge badge-lg badge-outline">
+						{server.clone()}
+						<button
+							on:click=move |_| remove_server(server.clone())
+							class="btn btn-ghost btn-xs btn-circle"
+						>
+							<X weight=IconWeight::Regular class="w-3 h-3 base-content"/>
+						</button>
+					</div>
+				</For>
+				<input
+					on:input=move |ev| new_server.set(event_target_value(&ev))
+					type="url"
+					placeholder="https://"
+					class="w-48 md:w-60 input input-sm input-bordered input-primary md:input-md"
+					prop:value=new_server
+				/>
+				<button on:click=add_server class="btn btn-sm btn-primary md:btn-md">
+					{i18n("settings.add")}
+				</button>
+			</Setting>
+		</SettingsSection>
+	}
 }
 
 #[component]
@@ -75,8 +156,32 @@ fn Setting(children: Children, title: String) -> impl IntoView {
 
 #[component]
 pub fn SubscriptionsSettings() -> impl IntoView {
+	let network = expect_context::<NetworkConfigCtx>();
+	let auto_fetch_subs_slice = network.auto_fetch_subs_slice;
+	let fetch_rss_slice = network.fetch_rss_slice;
+
 	view! {
 		<SettingsSection title=i18n("settings.subscriptions")()>
+			<Setting title=i18n("settings.auto_fetch_subs")()>
+				<input
+					on:input=move |_| {
+						auto_fetch_subs_slice.1.set(!auto_fetch_subs_slice.0.get())
+					}
+					type="checkbox"
+					class="toggle toggle-primary lg:toggle-lg"
+					checked=auto_fetch_subs_slice.0
+				/>
+			</Setting>
+			<Show when=is_tauri>
+				<Setting title=i18n("settings.fetch_rss")()>
+					<input
+						on:input=move |_| fetch_rss_slice.1.set(!fetch_rss_slice.0.get())
+						type="checkbox"
+						class="toggle toggle-primary lg:toggle-lg"
+						checked=fetch_rss_slice.0
+					/>
+				</Setting>
+			</Show>
 			<Setting title=i18n("settings.manage")()>
 				<ImportSubsButton/>
 				<DeleteAllSubsButton/>
@@ -90,123 +195,1361 @@ pub fn SubscriptionsSettings() -> impl IntoView {
 	}
 }
 
+#[component]
+pub fn FilterSettings() -> impl IntoView {
+	let filters_slice = expect_context::<FilterConfigCtx>().filters_slice;
+
+	let set_shorts_max_length = move |ev| {
+		if let Ok(length) = event_target_value(&ev).parse::<u32>() {
+			update_section(filters_slice, |filters| {
+				filters.shorts_max_length = length;
+			});
+		}
+	};
+
+	view! {
+		<SettingsSection title=i18n("settings.filters")()>
+			<FilterToggle
+				title="settings.hide_live"
+				field=|filters| &mut filters.hide_live
+			/>
+			<FilterToggle
+				title="settings.hide_upcoming"
+				field=|filters| &mut filters.hide_upcoming
+			/>
+			<FilterToggle
+				title="settings.hide_premium"
+				field=|filters| &mut filters.hide_premium
+			/>
+			<FilterToggle
+				title="settings.hide_shorts"
+				field=|filters| &mut filters.hide_shorts
+			/>
+			<Setting title=i18n("settings.shorts_max_length")()>
+				<input
+					on:change=set_shorts_max_length
+					type="number"
+					min="1"
+					class="w-24 input input-sm input-bordered input-primary md:input-md"
+					prop:value=move || {
+						filters_slice.0.get().shorts_max_length.to_string()
+					}
+				/>
+			</Setting>
+			<FilterToggle
+				title="settings.hide_watched"
+				field=|filters| &mut filters.hide_watched
+			/>
+			<FilterRules/>
+		</SettingsSection>
+	}
+}
+
+#[component]
+fn FilterToggle(
+	title: &'static str,
+	field: fn(&mut FilterConfig) -> &mut bool,
+) -> impl IntoView {
+	let filters_slice = expect_context::<FilterConfigCtx>().filters_slice;
+	let checked = move || *field(&mut filters_slice.0.get());
+	let toggle = move |_| {
+		update_section(filters_slice, |filters| {
+			let value = field(filters);
+			*value = !*value;
+		});
+	};
+
+	view! {
+		<Setting title=i18n(title)()>
+			<input
+				on:input=toggle
+				type="checkbox"
+				class="toggle toggle-primary lg:toggle-lg"
+				checked=checked
+			/>
+		</Setting>
+	}
+}
+
+#[component]
+fn FilterRules() -> impl IntoView {
+	let filters_slice = expect_context::<FilterConfigCtx>().filters_slice;
+	let field = RwSignal::new(FilterField::Title);
+	let kind = RwSignal::new(FilterKind::Keyword);
+	let pattern = RwSignal::new(String::new());
+
+	let add_rule = move |_| match FilterRule::new(
+		field.get_untracked(),
+		kind.get_untracked(),
+		&pattern.get_untracked(),
+	) {
+		Ok(rule) => {
+			update_section(filters_slice, |filters| {
+				if !filters.rules.contains(&rule) {
+					filters.rules.push(rule);
+				}
+			});
+			pattern.set(String::new());
+		}
+		Err(err) => {
+			toast(Toast::new(err.to_string(), None, Some(ToastType::Error)));
+		}
+	};
+
+	let remove_rule = move |rule: FilterRule| {
+		update_section(filters_slice, |filters| {
+			filters.rules.retain(|existing| *existing != rule);
+		});
+	};
+
+	let set_field = move |ev| {
+		field.set(match event_target_value(&ev).as_str() {
+			"author" => FilterField::Author,
+			_ => FilterField::Title,
+		});
+	};
+
+	let set_kind = move |ev| {
+		kind.set(match event_target_value(&ev).as_str() {
+			"regex" => FilterKind::Regex,
+			_ => FilterKind::Keyword,
+		});
+	};
+
+	view! {
+		<Setting title=i18n("settings.filter_rules")()>
+			<For
+				each=move || filters_slice.0.get().rules
+				key=|rule| format!("{rule:?}")
+				let:rule
+			>
+				<div class="gap-2 badge badge-lg badge-outline">
+					{filter_rule_label(&rule)}
+					<button
+						on:click=move |_| remove_rule(rule.clone())
+						class="btn btn-ghost btn-xs btn-circle"
+					>
+						<X weight=IconWeight::Regular class="w-3 h-3 base-content"/>
+					</button>
+				</div>
+			</For>
+			<select
+				on:change=set_field
+				class="select select-bordered select-sm md:select-md"
+			>
+				<option value="title" selected=true>
+					{i18n("settings.filter_title")}
+				</option>
+				<option value="author">{i18n("settings.filter_author")}</option>
+			</select>
+			<select
+				on:change=set_kind
+				class="select select-bordered select-sm md:select-md"
+			>
+				<option value="keyword" selected=true>
+					{i18n("settings.filter_keyword")}
+				</option>
+				<option value="regex">{i18n("settings.filter_regex")}</option>
+			</select>
+			<input
+				on:input=move |ev| pattern.set(event_target_value(&ev))
+				type="text"
+				class="w-48 input input-sm input-bordered input-primary md:input-md"
+				prop:value=pattern
+			/>
+			<button on:click=add_rule class="btn btn-sm btn-primary md:btn-md">
+				{i18n("settings.add")}
+			</button>
+		</Setting>
+	}
+}
+
+#[component]
+pub fn BlocklistSettings() -> impl IntoView {
+	let blocklist_slice = expect_context::<FilterConfigCtx>().blocklist_slice;
+	let new_keyword = RwSignal::new(String::new());
+
+	let add_keyword = move |_| {
+		update_section(blocklist_slice, |blocklist| {
+			blocklist.add_keyword(&new_keyword.get_untracked());
+		});
+		new_keyword.set(String::new());
+	};
+
+	view! {
+		<SettingsSection title=i18n("settings.blocklist")()>
+			<Setting title=i18n("settings.blocked_channels")()>
+				<For
+					each=move || blocklist_slice.0.get().channels
+					key=|channel| channel.id.clone()
+					let:channel
+				>
+					<div class="gap-2 badge badge-lg badge-outline">
+						{channel.name.clone()}
+						<button
+							on:click=move |_| {
+								update_section(
+									blocklist_slice,
+									|blocklist| blocklist.unblock_channel(&channel.id),
+								);
+							}
+							class="btn btn-ghost btn-xs btn-circle"
+						>
+							<X weight=IconWeight::Regular class="w-3 h-3 base-content"/>
+						</button>
+					</div>
+				</For>
+			</Setting>
+			<Setting title=i18n("settings.blocked_keywords")()>
+				<For
+					each=move || blocklist_slice.0.get().keywords
+					key=|keyword| keyword.clone()
+					let:keyword
+				>
+					<div class="gap-2 badge badge-lg badge-outline">
+						{keyword.clone()}
+						<button
+							on:click=move |_| {
+								update_section(
+									blocklist_slice,
+									|blocklist| blocklist.remove_keyword(&keyword),
+								);
+							}
+							class="btn btn-ghost btn-xs btn-circle"
+						>
+							<X weight=IconWeight::Regular class="w-3 h-3 base-content"/>
+						</button>
+					</div>
+				</For>
+				<input
+					on:input=move |ev| new_keyword.set(event_target_value(&ev))
+					type="text"
+					class="w-48 input input-sm input-bordered input-primary md:input-md"
+					prop:value=new_keyword
+				/>
+				<button on:click=add_keyword class="btn btn-sm btn-primary md:btn-md">
+					{i18n("settings.add")}
+				</button>
+			</Setting>
+		</SettingsSection>
+	}
+}
+
+/// Edits a copy of the config section and sets it.
+fn update_section<T: Clone>(
+	slice: (Signal<T>, SignalSetter<T>),
+	update: impl FnOnce(&mut T),
+) {
+	let mut section = slice.0.get_untracked();
+	update(&mut section);
+	slice.1.set(section);
+}
+
+fn filter_rule_label(rule: &FilterRule) -> String {
+	let field = match rule.field {
+		FilterField::Title => i18n("settings.filter_title")(),
+		FilterField::Author => i18n("settings.filter_author")(),
+	};
+	match rule.kind {
+		FilterKind::Keyword => format!("{field}: {}", rule.pattern),
+		FilterKind::Regex => format!("{field}: /{}/", rule.pattern),
+	}
+}
+
 #[component]
 pub fn PlaylistsSettings() -> impl IntoView {
 	view! {
 		<SettingsSection title=i18n("settings.playlists")()>
 			<Setting title=i18n("settings.manage")()>
 				<ImportPlaylistsButton/>
-				<DeleteAllSubsButton/>
 			</Setting>
 			<Setting title=i18n("settings.export")()>
-				<ExportSubsFreeTubeButton/>
-				<ExportSubsNewPipeButton/>
-				<ExportSubsLibreTubeButton/>
+				<ExportPlaylistsButton format=PlaylistExport::FreeTube/>
+				<ExportPlaylistsButton format=PlaylistExport::LibreTube/>
+				<ExportPlaylistsButton format=PlaylistExport::Csv/>
 			</Setting>
 		</SettingsSection>
 	}
 }
 
 #[component]
-pub fn SponsorBlockSettings() -> impl IntoView {
-	let ctx = expect_context::<SponsorBlockConfigCtx>();
+pub fn BackupSettings() -> impl IntoView {
+	let user_data = UserData::from_context();
+	let sections = RwSignal::new(BackupSections::default());
+
+	let export = create_action(move |sections: &BackupSections| {
+		let sections = *sections;
+		async move {
+			if let Err(err) = write_backup_file(user_data, sections).await {
+				toast(Toast::new(
+					err.to_string(),
+					None,
+					Some(ToastType::Error),
+				));
+			}
+		}
+	});
+
+	let import_profile = create_action(move |event: &Event| {
+		let event = event.clone();
+		async move {
+			match read_profile_file(user_data, event).await {
+				Ok(report) => toast(Toast::new(
+					profile_report_message(&report),
+					None,
+					Some(ToastType::Info),
+				)),
+				Err(err) => toast(Toast::new(
+					err.to_string(),
+					None,
+					Some(ToastType::Error),
+				)),
+			}
+		}
+	});
+
+	let section_toggle =
+		move |key: &'static str,
+		      get: fn(&BackupSections) -> bool,
+		      toggle: fn(&mut BackupSections)| {
+			view! {
+				<label class="gap-2 cursor-pointer label">
+					<span class="label-text">{i18n(key)}</span>
+					<input
+						on:input=move |_| sections.update(toggle)
+						type="checkbox"
+						class="checkbox checkbox-primary"
+						checked=move || sections.with(get)
+					/>
+				</label>
+			}
+		};
 
 	view! {
-		<SettingsSection title=i18n("settings.sponsorblock.title")()>
-			<Setting title=i18n("settings.sponsorblock.sponsor")()>
+		<SettingsSection title=i18n("settings.backup")()>
+			<Setting title=i18n("settings.backup_sections")()>
+				{section_toggle(
+					"settings.backup_config",
+					|sections| sections.config,
+					|sections| sections.config = !sections.config,
+				)}
+				{section_toggle(
+					"settings.subscriptions",
+					|sections| sections.subscriptions,
+					|sections| sections.subscriptions = !sections.subscriptions,
+				)}
+				{section_toggle(
+					"settings.playlists",
+					|sections| sections.playlists,
+					|sections| sections.playlists = !sections.playlists,
+				)}
+				{section_toggle(
+					"settings.backup_history",
+					|sections| sections.history,
+					|sections| sections.history = !sections.history,
+				)}
+				{section_toggle(
+					"settings.backup_positions",
+					|sections| sections.positions,
+					|sections| sections.positions = !sections.positions,
+				)}
+			</Setting>
+			<Setting title=i18n("settings.manage")()>
+				<button
+					on:click=move |_| export.dispatch(sections.get_untracked())
+					class="btn btn-sm btn-primary md:btn-md lg:btn-lg"
+				>
+					{i18n("settings.export")}
+				</button>
+				<BackupRestore/>
+			</Setting>
+			<Setting title=i18n("settings.import_profile")()>
+				<label
+					class="btn btn-sm btn-primary md:btn-md lg:btn-lg"
+					for="profile_upload"
+				>
+					{i18n("settings.import")}
+				</label>
 				<input
-					on:click=move |_| toggle_skip_sponsors(&ctx)
-					type="checkbox"
-					class="toggle toggle-primary lg:toggle-lg"
-					checked=ctx.skip_sponsors.0
+					id="profile_upload"
+					type="file"
+					accept=".json,.db,.csv"
+					multiple=false
+					on:change=move |event| import_profile.dispatch(event)
+					class="hidden"
 				/>
 			</Setting>
-			<Setting title=i18n("settings.sponsorblock.selfpromo")()>
+			<Setting title=i18n("settings.import_takeout")()>
+				<TakeoutImport/>
+			</Setting>
+		</SettingsSection>
+	}
+}
+
+async fn write_backup_file(
+	user_data: UserData,
+	sections: BackupSections,
+) -> Result<(), RustyTubeError> {
+	let backup = user_data.export_backup(sections)?;
+	save_file("rustytube_backup.json", "application/json", &backup).await
+}
+
+async fn read_backup_file(
+	event: Event,
+) -> Result<PendingBackup, RustyTubeError> {
+	let input = event
+		.target()
+		.expect("backup button should exist")
+		.dyn_into::<HtmlInputElement>()
+		.expect("backup button should be an input element");
+	let filelist = input.files().ok_or(RustyTubeError::NoFileSelected)?;
+	let file = filelist.get(0).ok_or(RustyTubeError::NoFileSelected)?;
+	input.set_value("");
+	let json = read_as_text(&Blob::from(file)).await?;
+	PendingBackup::read(&json)
+}
+
+/// Reads a backup and shows what it contains, restoring the chosen sections
+/// once confirmed.
+#[component]
+pub fn BackupRestore() -> impl IntoView {
+	let user_data = UserData::from_context();
+
+	let backup = StoredValue::new(None::<PendingBackup>);
+	let preview = RwSignal::new(BackupPreview::default());
+	let sections = RwSignal::new(BackupSections::default());
+
+	let modal_id = StoredValue::new("backup_restore_modal");
+	let set_modal_open = move |open: bool| {
+		get_element_by_id::<HtmlDialogElement>(modal_id.get_value())
+			.expect("backup restore modal should exist")
+			.set_open(open);
+	};
+
+	let read_backup = create_action(move |event: &Event| {
+		let event = event.clone();
+		async move {
+			match read_backup_file(event).await {
+				Ok(pending) => {
+					preview.set(pending.preview());
+					backup.set_value(Some(pending));
+					sections.set(BackupSections::default());
+					set_modal_open(true);
+				}
+				Err(err) => toast(Toast::new(
+					err.to_string(),
+					None,
+					Some(ToastType::Error),
+				)),
+			}
+		}
+	});
+
+	let restore = move |_| {
+		set_modal_open(false);
+		let Some(pending) = backup.get_value() else {
+			return;
+		};
+		backup.set_value(None);
+		user_data.restore_backup(pending, sections.get_untracked());
+		toast(Toast::new(
+			i18n("settings.backup_restored")(),
+			None,
+			Some(ToastType::Info),
+		));
+	};
+
+	let cancel = move |_| {
+		backup.set_value(None);
+		set_modal_open(false);
+	};
+
+	let section_row =
+		move |key: &'static str,
+		      count: fn(&BackupPreview) -> Option<usize>,
+		      get: fn(&BackupSections) -> bool,
+		      toggle: fn(&mut BackupSections)| {
+			view! {
+				<Show when=move || preview.with(count).is_some()>
+					<label class="gap-2 cursor-pointer label">
+						<span class="label-text">{i18n(key)}</span>
+						<span class="ml-auto">
+							{move || preview.with(count).unwrap_or_default()}
+						</span>
+						<input
+							on:input=move |_| sections.update(toggle)
+							type="checkbox"
+							class="checkbox checkbox-primary"
+							checked=move || sections.with(get)
+						/>
+					</label>
+				</Show>
+			}
+		};
+
+	view! {
+		<label
+			class="btn btn-sm btn-primary md:btn-md lg:btn-lg"
+			for="backup_upload"
+		>
+			{i18n("settings.import")}
+		</label>
+		<input
+			id="backup_upload"
+			type="file"
+			accept=".json"
+			multiple=false
+			on:change=move |event| read_backup.dispatch(event)
+			class="hidden"
+		/>
+		<dialog id=modal_id.get_value() class="modal">
+			<div class="modal-box">
+				<h3 class="text-lg font-bold">{i18n("settings.backup_preview")}</h3>
+				<p class="py-2">{i18n("settings.backup_replaces")}</p>
+				<div class="flex flex-col gap-2 py-4">
+					<Show when=move || preview.with(|preview| preview.config)>
+						<label class="gap-2 cursor-pointer label">
+							<span class="label-text">{i18n("settings.backup_config")}</span>
+							<input
+								on:input=move |_| {
+									sections.update(|sections| sections.config = !sections.config)
+								}
+								type="checkbox"
+								class="checkbox checkbox-primary"
+								checked=move || sections.with(|sections| sections.config)
+							/>
+						</label>
+					</Show>
+					{section_row(
+						"settings.subscriptions",
+						|preview| preview.subscriptions,
+						|sections| sections.subscriptions,
+						|sections| sections.subscriptions = !sections.subscriptions,
+					)}
+					{section_row(
+						"settings.playlists",
+						|preview| preview.playlists,
+						|sections| sections.playlists,
+						|sections| sections.playlists = !sections.playlists,
+					)}
+					{section_row(
+						"settings.backup_history",
+						|preview| preview.history,
+						|sections| sections.history,
+						|sections| sections.history = !sections.history,
+					)}
+					{section_row(
+						"settings.backup_positions",
+						|preview| preview.positions,
+						|sections| sections.positions,
+						|sections| sections.positions = !sections.positions,
+					)}
+				</div>
+				<div class="modal-action">
+					<button on:click=cancel class="btn btn-ghost">
+						{i18n("settings.cancel")}
+					</button>
+					<button on:click=restore class="btn btn-primary">
+						{i18n("settings.restore")}
+					</button>
+				</div>
+			</div>
+		</dialog>
+	}
+}
+
+async fn read_profile_file(
+	user_data: UserData,
+	event: Event,
+) -> Result<ProfileImportReport, RustyTubeError> {
+	let input = event
+		.target()
+		.expect("profile button should exist")
+		.dyn_into::<HtmlInputElement>()
+		.expect("profile button should be an input element");
+	let filelist = input.files().ok_or(RustyTubeError::NoFileSelected)?;
+	let file = filelist.get(0).ok_or(RustyTubeError::NoFileSelected)?;
+	input.set_value("");
+	let profile = ProfileImport::read(&File::from(file)).await?;
+	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let report = user_data.import_profile(
+		profile,
+		ImportStrategy::Merge,
+		&server.get_untracked(),
+	);
+	if report.subscriptions > 0 {
+		expect_context::<SubscriptionsVideosResource>().resource.refetch();
+		expect_context::<SubscriptionsThumbnailsResource>().resource.refetch();
+	}
+	Ok(report)
+}
+
+#[component]
+pub fn TakeoutImport() -> impl IntoView {
+	let user_data = UserData::from_context();
+	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let subs_videos = expect_context::<SubscriptionsVideosResource>();
+	let subs_thumbs = expect_context::<SubscriptionsThumbnailsResource>();
+
+	let takeout = StoredValue::new(None::<ProfileImport>);
+	let preview = RwSignal::new(ImportPreview::default());
+	let sections = RwSignal::new(BackupSections::default());
+	let strategy = RwSignal::new(ImportStrategy::default());
+
+	let modal_id = StoredValue::new("takeout_import_modal");
+	let set_modal_open = move |open: bool| {
+		get_element_by_id::<HtmlDialogElement>(modal_id.get_value())
+			.expect("takeout import modal should exist")
+			.set_open(open);
+	};
+
+	let read_takeout = create_action(move |event: &Event| {
+		let event = event.clone();
+		async move {
+			match read_takeout_files(event).await {
+				Ok(import) => {
+					preview.set(user_data.preview_import(&import));
+					takeout.set_value(Some(import));
+					set_modal_open(true);
+				}
+				Err(err) => toast(Toast::new(
+					err.to_string(),
+					None,
+					Some(ToastType::Error),
+				)),
+			}
+		}
+	});
+
+	let apply = move |_| {
+		set_modal_open(false);
+		let Some(mut import) = takeout.get_value() else {
+			return;
+		};
+		takeout.set_value(None);
+		let sections = sections.get_untracked();
+		if !sections.subscriptions {
+			import.subscriptions.channels.clear();
+		}
+		if !sections.playlists {
+			import.playlists.clear();
+		}
+		if !sections.history {
+			import.history.clear();
+		}
+		let report = user_data.import_profile(
+			import,
+			strategy.get_untracked(),
+			&server.get_untracked(),
+		);
+		if report.subscriptions > 0 {
+			subs_videos.resource.refetch();
+			subs_thumbs.resource.refetch();
+		}
+		toast(Toast::new(
+			profile_report_message(&report),
+			None,
+			Some(ToastType::Info),
+		));
+	};
+
+	let cancel = move |_| {
+		takeout.set_value(None);
+		set_modal_open(false);
+	};
+
+	let set_strategy = move |ev: Event| {
+		strategy.set(match event_target_value(&ev).as_str() {
+			"replace" => ImportStrategy::Replace,
+			"keep_both" => ImportStrategy::KeepBoth,
+			_ => ImportStrategy::Merge,
+		});
+	};
+
+	let section_row =
+		move |key: &'static str,
+		      counts: fn(&ImportPreview) -> (usize, usize),
+		      get: fn(&BackupSections) -> bool,
+		      toggle: fn(&mut BackupSections)| {
+			view! {
+				<label class="gap-2 cursor-pointer label">
+					<span class="label-text">{i18n(key)}</span>
+					<span class="ml-auto">
+						{move || {
+							let (new, known) = preview.with(counts);
+							format!(
+								"{new} {}, {known} {}",
+								i18n("settings.takeout_new")(),
+								i18n("settings.takeout_duplicates")(),
+							)
+						}}
+					</span>
+					<input
+						on:input=move |_| sections.update(toggle)
+						type="checkbox"
+						class="checkbox checkbox-primary"
+						checked=move || sections.with(get)
+					/>
+				</label>
+			}
+		};
+
+	view! {
+		<label
+			class="btn btn-sm btn-primary md:btn-md lg:btn-lg"
+			for="takeout_zip_upload"
+		>
+			{i18n("settings.takeout_zip")}
+		</label>
+		<input
+			id="takeout_zip_upload"
+			type="file"
+			accept=".zip"
+			multiple=false
+			on:change=move |event| read_takeout.dispatch(event)
+			class="hidden"
+		/>
+		<label
+			class="btn btn-sm btn-primary md:btn-md lg:btn-lg"
+			for="takeout_folder_upload"
+		>
+			{i18n("settings.takeout_folder")}
+		</label>
+		<input
+			id="takeout_folder_upload"
+			type="file"
+			webkitdirectory=true
+			multiple=true
+			on:change=move |event| read_takeout.dispatch(event)
+			class="hidden"
+		/>
+		<dialog id=modal_id.get_value() class="modal">
+			<div class="modal-box">
+				<h3 class="text-lg font-bold">{i18n("settings.takeout_preview")}</h3>
+				<div class="flex flex-col gap-2 py-4">
+					{section_row(
+						"settings.subscriptions",
+						|preview| (preview.new_subscriptions, preview.known_subscriptions),
+						|sections| sections.subscriptions,
+						|sections| sections.subscriptions = !sections.subscriptions,
+					)}
+					{section_row(
+						"settings.backup_history",
+						|preview| (preview.new_history, preview.known_history),
+						|sections| sections.history,
+						|sections| sections.history = !sections.history,
+					)}
+					<label class="gap-2 cursor-pointer label">
+						<span class="label-text">{i18n("settings.playlists")}</span>
+						<input
+							on:input=move |_| {
+								sections.update(|sections| sections.playlists = !sections.playlists)
+							}
+							type="checkbox"
+							class="checkbox checkbox-primary"
+							checked=move || sections.with(|sections| sections.playlists)
+						/>
+					</label>
+					<ul class="overflow-y-auto max-h-48">
+						{move || {
+							preview
+								.with(|preview| preview.playlists.clone())
+								.into_iter()
+								.map(|playlist| {
+									view! {
+										<li class="flex flex-row gap-2 justify-between">
+											<span>{playlist.title}</span>
+											<span class="flex flex-row gap-2">
+												<Show when=move || playlist.exists>
+													<span class="badge badge-outline">
+														{i18n("settings.takeout_exists")}
+													</span>
+												</Show>
+												{playlist.videos}
+											</span>
+										</li>
+									}
+								})
+								.collect_view()
+						}}
+					</ul>
+					<select
+						on:change=set_strategy
+						class="select select-bordered select-sm"
+					>
+						<option value="merge" selected=true>
+							{i18n("settings.import_merge")}
+						</option>
+						<option value="replace">{i18n("settings.import_replace")}</option>
+						<option value="keep_both">{i18n("settings.import_keep_both")}</option>
+					</select>
+				</div>
+				<div class="modal-action">
+					<button on:click=cancel class="btn btn-ghost">
+						{i18n("settings.cancel")}
+					</button>
+					<button on:click=apply class="btn btn-primary">
+						{i18n("settings.import")}
+					</button>
+				</div>
+			</div>
+		</dialog>
+	}
+}
+
+/// Reads a Takeout zip, or every export file of a selected Takeout folder.
+async fn read_takeout_files(
+	event: Event,
+) -> Result<ProfileImport, RustyTubeError> {
+	let input = event
+		.target()
+		.expect("takeout button should exist")
+		.dyn_into::<HtmlInputElement>()
+		.expect("takeout button should be an input element");
+	let filelist = input.files().ok_or(RustyTubeError::NoFileSelected)?;
+	let files: Vec<File> = (0..filelist.length())
+		.filter_map(|index| filelist.get(index))
+		.map(File::from)
+		.collect();
+	input.set_value("");
+
+	if let [file] = files.as_slice() {
+		if file.name().to_lowercase().ends_with(".zip") {
+			let bytes = read_as_bytes(file).await?;
+			return ProfileImport::read_takeout_zip(&bytes).await;
+		}
+	}
+
+	let mut contents = Vec::new();
+	for file in files {
+		let name = file.name();
+		let lowercase = name.to_lowercase();
+		if lowercase.ends_with(".csv") || lowercase.ends_with(".json") {
+			contents.push((name, read_as_text(&file).await?));
+		}
+	}
+	ProfileImport::read_takeout_files(contents).await
+}
+
+fn profile_report_message(report: &ProfileImportReport) -> String {
+	let playlists = report.playlists.added
+		+ report.playlists.merged
+		+ report.playlists.replaced
+		+ report.playlists.kept_both;
+	format!(
+		"{}: {}, {}: {}, {}: {}",
+		i18n("settings.subscriptions")(),
+		report.subscriptions,
+		i18n("settings.playlists")(),
+		playlists,
+		i18n("settings.backup_history")(),
+		report.history,
+	)
+}
+
+#[component]
+pub fn GeneralSettings() -> impl IntoView {
+	view! {
+		<SettingsSection title=i18n("settings.general")()>
+			<Setting title=i18n("settings.homepage")()>
+				<HomepageDropdown/>
+			</Setting>
+		</SettingsSection>
+	}
+}
+
+#[component]
+pub fn HomepageDropdown() -> impl IntoView {
+	let homepage_slice = expect_context::<UiConfigCtx>().homepage_slice;
+	let playlists = expect_context::<PlaylistsCtx>();
+
+	let categories = move || {
+		let mut categories = vec![
+			HomepageCategory::Subscriptions,
+			HomepageCategory::Trending,
+			HomepageCategory::Popular,
+			HomepageCategory::History,
+		];
+		categories.extend(
+			playlists
+				.playlists
+				.get()
+				.into_iter()
+				.map(|playlist| HomepageCategory::Playlist(playlist.id)),
+		);
+		categories
+	};
+
+	view! {
+		<div class="dropdown dropdown-end">
+			<div tabindex="0" role="button" class="m-1 btn btn-secondary">
+				{move || homepage_name(&homepage_slice.0.get(), playlists)}
+			</div>
+			<ul
+				tabindex="0"
+				class="overflow-y-auto z-10 p-3 w-64 max-h-80 rounded-xl shadow dropdown-content bg-base-300 h-fit"
+			>
+				<For
+					each=categories
+					key=|category| format!("{category:?}")
+					let:category
+				>
+					<li>
+						<a
+							class="justify-start text-left btn btn-xs btn-ghost h-fit btn-block md:btn-sm"
+							on:click={
+								let category = category.clone();
+								move |_| homepage_slice.1.set(category.clone())
+							}
+						>
+
+							<p>{move || homepage_name(&category, playlists)}</p>
+						</a>
+					</li>
+				</For>
+			</ul>
+		</div>
+	}
+}
+
+fn homepage_name(
+	category: &HomepageCategory,
+	playlists: PlaylistsCtx,
+) -> String {
+	match category {
+		HomepageCategory::Trending => i18n("sidebar.trending")(),
+		HomepageCategory::Popular => i18n("sidebar.popular")(),
+		HomepageCategory::Subscriptions => i18n("sidebar.subscriptions")(),
+		HomepageCategory::History => i18n("sidebar.history")(),
+		HomepageCategory::Playlist(id) => {
+			playlists.get(id).map(|playlist| playlist.title).unwrap_or_default()
+		}
+	}
+}
+
+#[component]
+pub fn PlayerSettings() -> impl IntoView {
+	let auto_play_slice = expect_context::<PlayerConfigCtx>().auto_play_slice;
+	let fast_forward_interval_slice =
+		expect_context::<PlayerConfigCtx>().fast_forward_interval_slice;
+
+	let set_fast_forward_interval = move |ev| {
+		if let Ok(interval) = event_target_value(&ev).parse::<u8>() {
+			fast_forward_interval_slice.1.set(interval.max(1));
+		}
+	};
+
+	view! {
+		<SettingsSection title=i18n("settings.player")()>
+			<Setting title=i18n("settings.autoplay")()>
 				<input
-					on:input=move |_| toggle_skip_selfpromos(&ctx)
+					on:input=move |_| auto_play_slice.1.set(!auto_play_slice.0.get())
 					type="checkbox"
 					class="toggle toggle-primary lg:toggle-lg"
-					checked=ctx.skip_selfpromos.0
+					checked=auto_play_slice.0
 				/>
 			</Setting>
-			<Setting title=i18n("settings.sponsorblock.intro")()>
+			<Setting title=i18n("settings.fast_forward_interval")()>
 				<input
-					on:input=move |_| toggle_skip_intros(&ctx)
-					type="checkbox"
-					class="toggle toggle-primary lg:toggle-lg"
-					checked=ctx.skip_intros.0
+					on:change=set_fast_forward_interval
+					type="number"
+					min="1"
+					max="255"
+					class="w-24 input input-sm input-bordered input-primary md:input-md"
+					prop:value=move || fast_forward_interval_slice.0.get().to_string()
 				/>
 			</Setting>
-			<Setting title=i18n("settings.sponsorblock.outro")()>
+		</SettingsSection>
+	}
+}
+
+#[component]
+pub fn CaptionsSettings() -> impl IntoView {
+	let captions_slice = expect_context::<CaptionsConfigCtx>().captions_slice;
+
+	let set_font_size = move |ev| {
+		if let Ok(size) = event_target_value(&ev).parse::<u16>() {
+			update_section(captions_slice, |captions| {
+				captions.font_size = size.clamp(25, 400);
+			});
+		}
+	};
+
+	let set_colour = move |ev| {
+		update_section(captions_slice, |captions| {
+			captions.colour = event_target_value(&ev);
+		});
+	};
+
+	let set_background_opacity = move |ev| {
+		if let Ok(opacity) = event_target_value(&ev).parse::<u8>() {
+			update_section(captions_slice, |captions| {
+				captions.background_opacity = opacity.min(100);
+			});
+		}
+	};
+
+	let set_position = move |ev| {
+		update_section(captions_slice, |captions| {
+			captions.position = match event_target_value(&ev).as_str() {
+				"top" => CaptionsPosition::Top,
+				_ => CaptionsPosition::Bottom,
+			};
+		});
+	};
+	let is_top =
+		move || captions_slice.0.get().position == CaptionsPosition::Top;
+
+	view! {
+		<SettingsSection title=i18n("settings.captions")()>
+			<Setting title=i18n("settings.captions_font_size")()>
 				<input
-					on:input=move |_| toggle_skip_outros(&ctx)
-					type="checkbox"
-					class="toggle toggle-primary lg:toggle-lg"
-					checked=move || ctx.skip_outros.0.get()
+					on:change=set_font_size
+					type="number"
+					min="25"
+					max="400"
+					step="5"
+					class="w-24 input input-sm input-bordered input-primary md:input-md"
+					prop:value=move || captions_slice.0.get().font_size.to_string()
 				/>
 			</Setting>
-			<Setting title=i18n("settings.sponsorblock.interaction")()>
+			<Setting title=i18n("settings.captions_colour")()>
 				<input
-					on:input=move |_| toggle_skip_interactions(&ctx)
-					type="checkbox"
-					class="toggle toggle-primary lg:toggle-lg"
-					checked=ctx.skip_interactions.0
+					on:change=set_colour
+					type="color"
+					class="w-16 input input-sm input-bordered input-primary md:input-md"
+					prop:value=move || captions_slice.0.get().colour
 				/>
 			</Setting>
-			<Setting title=i18n("settings.sponsorblock.preview")()>
+			<Setting title=i18n("settings.captions_background_opacity")()>
 				<input
-					on:input=move |_| toggle_skip_previews(&ctx)
-					type="checkbox"
-					class="toggle toggle-primary lg:toggle-lg"
-					checked=ctx.skip_irrelevant_music.0
+					on:change=set_background_opacity
+					type="range"
+					min="0"
+					max="100"
+					step="5"
+					class="w-48 range range-primary range-sm"
+					prop:value=move || {
+						captions_slice.0.get().background_opacity.to_string()
+					}
 				/>
 			</Setting>
-			<Setting title=i18n("settings.sponsorblock.filler")()>
+			<Setting title=i18n("settings.captions_position")()>
+				<select
+					on:change=set_position
+					class="select select-bordered select-sm md:select-md"
+				>
+					<option value="bottom" selected=move || !is_top()>
+						{i18n("settings.captions_bottom")}
+					</option>
+					<option value="top" selected=is_top>
+						{i18n("settings.captions_top")}
+					</option>
+				</select>
+			</Setting>
+		</SettingsSection>
+	}
+}
+
+#[component]
+pub fn KeybindingsSettings() -> impl IntoView {
+	view! {
+		<SettingsSection title=i18n("video.shortcuts.title")()>
+			<KeybindingSetting
+				title="video.shortcuts.toggle_playback"
+				keys=|bindings| &mut bindings.toggle_playback
+			/>
+			<KeybindingSetting
+				title="video.shortcuts.seek_backward"
+				keys=|bindings| &mut bindings.seek_backward
+			/>
+			<KeybindingSetting
+				title="video.shortcuts.seek_forward"
+				keys=|bindings| &mut bindings.seek_forward
+			/>
+			<KeybindingSetting
+				title="video.shortcuts.volume_up"
+				keys=|bindings| &mut bindings.volume_up
+			/>
+			<KeybindingSetting
+				title="video.shortcuts.volume_down"
+				keys=|bindings| &mut bindings.volume_down
+			/>
+			<KeybindingSetting
+				title="video.shortcuts.fullscreen"
+				keys=|bindings| &mut bindings.fullscreen
+			/>
+			<KeybindingSetting
+				title="video.shortcuts.full_window"
+				keys=|bindings| &mut bindings.full_window
+			/>
+			<KeybindingSetting
+				title="video.shortcuts.mute"
+				keys=|bindings| &mut bindings.mute
+			/>
+			<KeybindingSetting
+				title="video.shortcuts.cycle_captions"
+				keys=|bindings| &mut bindings.cycle_captions
+			/>
+			<KeybindingSetting
+				title="video.shortcuts.show_shortcuts"
+				keys=|bindings| &mut bindings.show_shortcuts
+			/>
+		</SettingsSection>
+	}
+}
+
+/// Edits one binding as a comma separated list of `KeyboardEvent.key` values.
+#[component]
+fn KeybindingSetting(
+	title: &'static str,
+	keys: fn(&mut KeybindingsConfig) -> &mut Vec<String>,
+) -> impl IntoView {
+	let keybindings_slice =
+		expect_context::<PlayerConfigCtx>().keybindings_slice;
+
+	let value = move || keys(&mut keybindings_slice.0.get()).join(", ");
+
+	let set_keys = move |ev| {
+		let mut bindings = keybindings_slice.0.get_untracked();
+		*keys(&mut bindings) = event_target_value(&ev)
+			.split(',')
+			.map(str::trim)
+			.filter(|key| !key.is_empty())
+			.map(ToString::to_string)
+			.collect();
+		keybindings_slice.1.set(bindings);
+	};
+
+	view! {
+		<Setting title=i18n(title)()>
+			<input
+				on:change=set_keys
+				type="text"
+				class="w-48 input input-sm input-bordered input-primary md:input-md"
+				prop:value=value
+			/>
+		</Setting>
+	}
+}
+
+#[component]
+pub fn SponsorBlockSettings() -> impl IntoView {
+	let sponsorblock_slice =
+		expect_context::<SponsorBlockConfigCtx>().sponsorblock_slice;
+	let enabled = move || sponsorblock_slice.0.get().enabled;
+	let toggle_enabled = move |_| {
+		update_section(sponsorblock_slice, |sponsorblock| {
+			sponsorblock.enabled = !sponsorblock.enabled;
+		});
+	};
+	let time_saved = expect_context::<TimeSavedCtx>().0;
+	let time_saved = move || unix_to_hours_secs_mins(time_saved.get());
+
+	view! {
+		<SettingsSection title=i18n("settings.sponsorblock.title")()>
+			<Setting title=i18n("settings.sponsorblock.enabled")()>
 				<input
-					on:input=move |_| toggle_skip_filler(&ctx)
+					on:input=toggle_enabled
 					type="checkbox"
 					class="toggle toggle-primary lg:toggle-lg"
-					checked=ctx.skip_filler.0
+					checked=enabled
 				/>
 			</Setting>
+			<SegmentActionSetting
+				title="settings.sponsorblock.sponsor"
+				field=|sponsorblock| &mut sponsorblock.sponsor
+			/>
+			<SegmentActionSetting
+				title="settings.sponsorblock.selfpromo"
+				field=|sponsorblock| &mut sponsorblock.selfpromo
+			/>
+			<SegmentActionSetting
+				title="settings.sponsorblock.intro"
+				field=|sponsorblock| &mut sponsorblock.intro
+			/>
+			<SegmentActionSetting
+				title="settings.sponsorblock.outro"
+				field=|sponsorblock| &mut sponsorblock.outro
+			/>
+			<SegmentActionSetting
+				title="settings.sponsorblock.interaction"
+				field=|sponsorblock| &mut sponsorblock.interaction
+			/>
+			<SegmentActionSetting
+				title="settings.sponsorblock.preview"
+				field=|sponsorblock| &mut sponsorblock.preview
+			/>
+			<SegmentActionSetting
+				title="settings.sponsorblock.offtopic_music"
+				field=|sponsorblock| &mut sponsorblock.music_offtopic
+			/>
+			<SegmentActionSetting
+				title="settings.sponsorblock.filler"
+				field=|sponsorblock| &mut sponsorblock.filler
+			/>
+			<Setting title=i18n("settings.sponsorblock.time_saved")()>
+				<p class="font-mono">{time_saved}</p>
+			</Setting>
 		</SettingsSection>
 	}
 }
 
-fn toggle_enabled(ctx: &SponsorBlockConfigCtx) {
-	ctx.enabled.1.set(!ctx.enabled.0.get());
-}
+#[component]
+fn SegmentActionSetting(
+	title: &'static str,
+	field: fn(&mut SponsorBlockConfig) -> &mut SegmentAction,
+) -> impl IntoView {
+	let sponsorblock_slice =
+		expect_context::<SponsorBlockConfigCtx>().sponsorblock_slice;
+	let current = move || *field(&mut sponsorblock_slice.0.get());
+	let set_action = move |ev| {
+		let action = match event_target_value(&ev).as_str() {
+			"auto_skip" => SegmentAction::AutoSkip,
+			"manual_skip" => SegmentAction::ManualSkip,
+			"show" => SegmentAction::Show,
+			"mute" => SegmentAction::Mute,
+			_ => SegmentAction::Disabled,
+		};
+		update_section(sponsorblock_slice, |sponsorblock| {
+			*field(sponsorblock) = action;
+		});
+	};
 
-fn toggle_skip_sponsors(ctx: &SponsorBlockConfigCtx) {
-	ctx.skip_sponsors.1.set(!ctx.skip_sponsors.0.get());
-}
+	let options = [
+		(
+			SegmentAction::AutoSkip,
+			"auto_skip",
+			"settings.sponsorblock.auto_skip",
+		),
+		(
+			SegmentAction::ManualSkip,
+			"manual_skip",
+			"settings.sponsorblock.manual_skip",
+		),
+		(SegmentAction::Show, "show", "settings.sponsorblock.show"),
+		(SegmentAction::Mute, "mute", "settings.sponsorblock.mute"),
+		(SegmentAction::Disabled, "disabled", "settings.sponsorblock.disabled"),
+	]
+	.into_iter()
+	.map(|(action, value, label)| {
+		view! {
+			<option value=value selected=move || current() == action>
+				{i18n(label)}
+			</option>
+		}
+	})
+	.collect_view();
 
-fn toggle_skip_selfpromos(ctx: &SponsorBlockConfigCtx) {
-	ctx.skip_selfpromos.1.set(!ctx.skip_selfpromos.0.get());
+	view! {
+		<Setting title=i18n(title)()>
+			<select
+				on:change=set_action
+				class="select select-bordered select-sm md:select-md"
+			>
+				{options}
+			</select>
+		</Setting>
+	}
 }
 
-fn toggle_skip_interactions(ctx: &SponsorBlockConfigCtx) {
-	ctx.skip_interactions.1.set(!ctx.skip_interactions.0.get());
-}
+#[component]
+pub fn PrivacySettings() -> impl IntoView {
+	let keep_history_slice =
+		expect_context::<PrivacyConfigCtx>().keep_history_slice;
+	let history = expect_context::<HistoryCtx>();
 
-fn toggle_skip_intros(ctx: &SponsorBlockConfigCtx) {
-	ctx.skip_intros.1.set(!ctx.skip_intros.0.get());
-}
+	let modal_id = StoredValue::new("delete_history_modal");
+	let close_modal = move |_| {
+		get_element_by_id::<HtmlDialogElement>(modal_id.get_value())
+			.expect("delete history modal should exist")
+			.set_open(false);
+	};
 
-fn toggle_skip_outros(ctx: &SponsorBlockConfigCtx) {
-	ctx.skip_outros.1.set(!ctx.skip_outros.0.get());
-}
+	let toggle_keep_history = move |_| {
+		let keep_history = !keep_history_slice.0.get();
+		keep_history_slice.1.set(keep_history);
+		if !keep_history && !history.0.get().entries.is_empty() {
+			get_element_by_id::<HtmlDialogElement>(modal_id.get_value())
+				.expect("delete history modal should exist")
+				.set_open(true);
+		}
+	};
+
+	let delete_history = move |ev: MouseEvent| {
+		history.clear();
+		close_modal(ev);
+	};
 
-fn toggle_skip_previews(ctx: &SponsorBlockConfigCtx) {
-	ctx.skip_previews.1.set(!ctx.skip_previews.0.get());
+	view! {
+		<SettingsSection title=i18n("settings.privacy")()>
+			<Setting title=i18n("settings.keep_history")()>
+				<input
+					on:input=toggle_keep_history
+					type="checkbox"
+					class="toggle toggle-primary lg:toggle-lg"
+					checked=keep_history_slice.0
+				/>
+			</Setting>
+		</SettingsSection>
+		<dialog id=modal_id.get_value() class="modal">
+			<div class="modal-box">
+				<h3 class="text-lg font-bold">
+					{i18n("settings.delete_history")}
+				</h3>
+				<p class="py-4">{i18n("settings.delete_history_description")}</p>
+				<div class="modal-action">
+					<button on:click=close_modal class="btn btn-ghost">
+						{i18n("settings.keep")}
+					</button>
+					<button on:click=delete_history class="btn btn-error">
+						{i18n("settings.delete_all")}
+					</button>
+				</div>
+			</div>
+		</dialog>
+	}
 }
 
-fn toggle_skip_irrelevant_music(ctx: &SponsorBlockConfigCtx) {
-	ctx.skip_irrelevant_music.1.set(!ctx.skip_irrelevant_music.0.get());
+#[component]
+pub fn CacheSettings() -> impl IntoView {
+	let size_resource = Resource::local(|| (), |()| cache_size());
+	let clear = create_action(move |(): &()| async move {
+		let _ = clear_cache().await;
+		size_resource.refetch();
+	});
+
+	let size = move || {
+		size_resource
+			.get()
+			.and_then(|size| size.ok())
+			.map(format_size)
+			.unwrap_or_default()
+	};
+
+	view! {
+		<SettingsSection title=i18n("settings.cache")()>
+			<Setting title=i18n("settings.cache_size")()>
+				<p class="font-mono">{size}</p>
+				<button
+					on:click=move |_| clear.dispatch(())
+					class="btn btn-sm btn-error md:btn-md"
+				>
+					{i18n("settings.clear")}
+				</button>
+			</Setting>
+		</SettingsSection>
+	}
 }
 
-fn toggle_skip_filler(ctx: &SponsorBlockConfigCtx) {
-	ctx.skip_filler.1.set(!ctx.skip_filler.0.get());
+#[allow(clippy::cast_precision_loss)]
+fn format_size(bytes: usize) -> String {
+	let kilobytes = bytes as f64 / 1024f64;
+	if kilobytes < 1024f64 {
+		format!("{kilobytes:.1} KB")
+	} else {
+		format!("{:.1} MB", kilobytes / 1024f64)
+	}
 }
 
 #[component]
@@ -348,14 +1691,9 @@ async fn get_subs_from_file(
 	let filelist = input.files().ok_or(RustyTubeError::NoFileSelected)?;
 	let file = filelist.get(0).ok_or(RustyTubeError::NoFileSelected)?;
 	let blob: Blob = file.into();
-	let mut subscriptions = Subscriptions::read_subs(blob).await?;
-	subs_resource.0.update(|subs| {
-		subs.channels.append(&mut subscriptions.channels);
-		subs.channels
-			.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-		subs.channels.dedup_by(|a, b| a.name.eq_ignore_ascii_case(&b.name));
-		save_subs(subs);
-	});
+	let subscriptions = Subscriptions::read_subs(blob).await?;
+	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	subs_resource.import(subscriptions, server.get_untracked());
 	expect_context::<SubscriptionsVideosResource>().resource.refetch();
 	expect_context::<SubscriptionsThumbnailsResource>().resource.refetch();
 	Ok(())
@@ -379,8 +1717,11 @@ pub fn DeleteAllSubsButton() -> impl IntoView {
 	};
 
 	let delete_all_subs = move |ev: MouseEvent| {
-		LocalStorage::set(SUBS_KEY, "");
 		subs_ctx.0.set(Subscriptions::default());
+		save_subs(&Subscriptions::default());
+		spawn_local(async {
+			let _ = CachedChannelFeed::delete_all().await;
+		});
 		close_modal(ev);
 	};
 
@@ -442,16 +1783,16 @@ pub fn ExportSubsFreeTubeButton() -> impl IntoView {
 	let current_subs = expect_context::<SubscriptionsCtx>().0;
 
 	let href = move || {
-		let subs: NewpipeSubscriptions = current_subs.get().into();
-		let subs_json = subs.to_json_string().unwrap_or_default();
-		let encoded_subs = encode(&subs_json);
+		let subs_db = write_freetube_subscriptions(&current_subs.get())
+			.unwrap_or_default();
+		let encoded_subs = encode(&subs_db);
 		format!("data:attachment/text,{encoded_subs}")
 	};
 
 	view! {
 		<a
 			href=href
-			download="freetube_subscriptions.json"
+			download="freetube_subscriptions.db"
 			class="btn btn-sm bg-[#E4E4E4] border-[#E4E4E4] md:btn-md lg:btn-lg hover:bg-[#E4E4E4] hover:border-[#E4E4E4]"
 		>
 			<div class="flex flex-row">
@@ -484,23 +1825,188 @@ pub fn ExportSubsNewPipeButton() -> impl IntoView {
 	}
 }
 
+/// NewPipe only imports local playlists as part of a full database backup,
+/// which replaces everything else in the app, so it has no export here.
+#[derive(Clone, Copy)]
+pub enum PlaylistExport {
+	FreeTube,
+	LibreTube,
+	Csv,
+}
+
+#[component]
+pub fn ExportPlaylistsButton(format: PlaylistExport) -> impl IntoView {
+	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	let playlists = expect_context::<PlaylistsCtx>().playlists;
+
+	let export = create_action(move |format: &PlaylistExport| {
+		let format = *format;
+		async move {
+			let result = export_playlists(
+				format,
+				&server.get_untracked(),
+				&playlists.get_untracked(),
+			)
+			.await;
+			if let Err(err) = result {
+				toast(Toast::new(
+					err.to_string(),
+					None,
+					Some(ToastType::Error),
+				));
+			}
+		}
+	});
+
+	let on_click = move |_| export.dispatch(format);
+
+	match format {
+		PlaylistExport::FreeTube => view! {
+			<button
+				on:click=on_click
+				class="btn btn-sm bg-[#E4E4E4] border-[#E4E4E4] md:btn-md lg:btn-lg hover:bg-[#E4E4E4] hover:border-[#E4E4E4]"
+			>
+				<div class="flex flex-row">
+					<p class="text-[#F04242]">Free</p>
+					<p class="text-[#29ABE1]">Tube</p>
+				</div>
+			</button>
+		},
+		PlaylistExport::LibreTube => view! {
+			<button
+				on:click=on_click
+				class="btn btn-sm bg-[#000] border-[#000] md:btn-md lg:btn-lg hover:bg-[#000] hover:border-[#000]"
+			>
+				<div class="flex flex-row">
+					<p class="text-[#FF9698]">Libre</p>
+					<p class="text-white">Tube</p>
+				</div>
+			</button>
+		},
+		PlaylistExport::Csv => view! {
+			<button
+				on:click=on_click
+				class="btn btn-sm btn-primary md:btn-md lg:btn-lg"
+			>
+				CSV
+			</button>
+		},
+	}
+}
+
+async fn export_playlists(
+	format: PlaylistExport,
+	server: &str,
+	playlists: &[LocalPlaylist],
+) -> Result<(), RustyTubeError> {
+	match format {
+		PlaylistExport::FreeTube => {
+			let db = write_freetube_playlists(server, playlists).await?;
+			save_file("freetube-playlists.db", "application/json", &db).await
+		}
+		PlaylistExport::LibreTube => {
+			let json = write_libretube_playlists(playlists)?;
+			save_file("libretube_playlists.json", "application/json", &json)
+				.await
+		}
+		PlaylistExport::Csv => {
+			for playlist in playlists {
+				let csv = write_playlist_csv(playlist)?;
+				let name =
+					format!("{}.csv", playlist_file_name(&playlist.title));
+				save_file(&name, "text/csv", &csv).await?;
+			}
+			Ok(())
+		}
+	}
+}
+
+/// Replaces the characters file systems reject in a playlist title.
+fn playlist_file_name(title: &str) -> String {
+	let name = title
+		.chars()
+		.map(|character| match character {
+			'/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+			character if character.is_control() => '_',
+			character => character,
+		})
+		.collect::<String>();
+	let name = name.trim().trim_matches('.');
+	if name.is_empty() {
+		String::from("playlist")
+	} else {
+		name.to_string()
+	}
+}
+
 #[component]
 pub fn ImportPlaylistsButton() -> impl IntoView {
 	let playlists = expect_context::<PlaylistsCtx>();
+	let strategy = RwSignal::new(ImportStrategy::default());
+	let report = RwSignal::new(ImportReport::default());
 
-	let parse_playlists_file =
-		create_action(|input: &(PlaylistsCtx, Event)| {
-			let playlists = input.0;
-			let event = input.1.clone();
+	let modal_id = StoredValue::new("playlist_import_modal");
+	let close_modal = move |_| {
+		get_element_by_id::<HtmlDialogElement>(modal_id.get_value())
+			.expect("playlist import modal should exist")
+			.set_open(false);
+	};
 
-			get_playlists_from_file(playlists, event)
-		});
+	let parse_playlists_file = create_action(move |event: &Event| {
+		let event = event.clone();
+		async move {
+			let strategy = strategy.get_untracked();
+			match get_playlists_from_file(playlists, strategy, event).await {
+				Ok(import_report) => {
+					report.set(import_report);
+					get_element_by_id::<HtmlDialogElement>(
+						modal_id.get_value(),
+					)
+					.expect("playlist import modal should exist")
+					.set_open(true);
+				}
+				Err(err) => toast(Toast::new(
+					err.to_string(),
+					None,
+					Some(ToastType::Error),
+				)),
+			}
+		}
+	});
 
 	let on_file_upload = move |event: Event| {
-		parse_playlists_file.dispatch((playlists, event));
+		parse_playlists_file.dispatch(event);
 	};
 
+	let set_strategy = move |ev: Event| {
+		strategy.set(match event_target_value(&ev).as_str() {
+			"replace" => ImportStrategy::Replace,
+			"keep_both" => ImportStrategy::KeepBoth,
+			_ => ImportStrategy::Merge,
+		});
+	};
+
+	let report_row =
+		move |key: &'static str, count: fn(&ImportReport) -> usize| {
+			view! {
+				<div class="flex flex-row justify-between">
+					<p>{i18n(key)}</p>
+					<p>{move || report.with(count)}</p>
+				</div>
+			}
+		};
+
 	view! {
+		<select
+			on:change=set_strategy
+			class="select select-bordered select-sm md:select-md lg:select-lg"
+		>
+			<option value="merge" selected=true>
+				{i18n("settings.import_merge")}
+			</option>
+			<option value="replace">{i18n("settings.import_replace")}</option>
+			<option value="keep_both">{i18n("settings.import_keep_both")}</option>
+		</select>
 		<div>
 			<label
 				class="btn btn-sm btn-primary md:btn-md lg:btn-lg"
@@ -511,19 +2017,37 @@ pub fn ImportPlaylistsButton() -> impl IntoView {
 			<input
 				id="playlists_upload"
 				type="file"
-				accept=".ron,.json,.csv"
+				accept=".db,.json,.csv"
 				multiple=false
 				on:change=on_file_upload
 				class="hidden"
 			/>
 		</div>
+		<dialog id=modal_id.get_value() class="modal">
+			<div class="modal-box">
+				<h3 class="text-lg font-bold">{i18n("settings.import_report")}</h3>
+				<div class="flex flex-col gap-2 py-4">
+					{report_row("settings.import_added", |report| report.added)}
+					{report_row("settings.import_merged", |report| report.merged)}
+					{report_row("settings.import_replaced", |report| report.replaced)}
+					{report_row("settings.import_kept_both", |report| report.kept_both)}
+					{report_row("settings.import_videos", |report| report.videos_added)}
+				</div>
+				<div class="modal-action">
+					<button on:click=close_modal class="btn btn-ghost">
+						{i18n("settings.close")}
+					</button>
+				</div>
+			</div>
+		</dialog>
 	}
 }
 
 async fn get_playlists_from_file(
-	playlists_resource: PlaylistsCtx,
+	playlists_ctx: PlaylistsCtx,
+	strategy: ImportStrategy,
 	event: Event,
-) -> Result<(), RustyTubeError> {
+) -> Result<ImportReport, RustyTubeError> {
 	let input = event
 		.target()
 		.expect("playlist button should exist")
@@ -531,17 +2055,9 @@ async fn get_playlists_from_file(
 		.expect("playlist button should be an input element");
 	let filelist = input.files().ok_or(RustyTubeError::NoFileSelected)?;
 	let file = filelist.get(0).ok_or(RustyTubeError::NoFileSelected)?;
-	let blob: Blob = file.into();
-	let mut new_playlists = LocalPlaylist::read_playlists(blob).await?;
-	playlists_resource.playlists.update(|playlists| {
-		playlists.append(&mut new_playlists);
-		playlists.sort_by(|a, b| {
-			a.title.to_lowercase().cmp(&b.title.to_lowercase())
-		});
-		playlists.dedup_by(|a, b| a.title.eq_ignore_ascii_case(&b.title));
-		save_playlists(playlists);
-	});
-	Ok(())
+	input.set_value("");
+	let imported = LocalPlaylist::read_playlists(File::from(file)).await?;
+	Ok(playlists_ctx.import(imported, strategy))
 }
 
 #[component]
@@ -1,11 +1,15 @@
 use gloo::file::Blob;
-use invidious::{ChannelVideos, CommonVideo, Subscriptions};
+use invidious::{CommonVideo, FeedFailure, Subscriptions, SubscriptionsFeed};
 use leptos::{
-	component, create_action, create_rw_signal, expect_context, view,
-	wasm_bindgen, web_sys, CollectView, IntoView, Props, RwSignal, Show,
-	SignalGet, SignalUpdate, Suspense,
+	component, create_action, create_effect, create_rw_signal,
+	event_target_value, expect_context, view, wasm_bindgen, web_sys,
+	CollectView, For, IntoView, Props, RwSignal, Show, SignalGet,
+	SignalGetUntracked, SignalSet, SignalUpdate, SignalWith, Suspense,
 };
+use leptos_router::use_query_map;
+use phosphor_leptos::{ArrowClockwise, IconWeight, PencilSimple, Trash};
 use rustytube_error::RustyTubeError;
+use urlencoding::encode;
 use wasm_bindgen::JsCast;
 use web_sys::{Event, HtmlInputElement};
 
@@ -14,29 +18,67 @@ use crate::{
 		CardGrid, FerrisError, GridContainer, PlaceholderCardArray,
 		VideoPreviewCard,
 	},
+	contexts::{toast, NetworkConfigCtx, Toast, ToastType},
 	icons::FerrisWaveIcon,
 	resources::{
-		save_subs, SubscriptionsCtx, SubscriptionsThumbnailsResource,
+		SubscriptionsCtx, SubscriptionsThumbnailsResource,
 		SubscriptionsVideosResource,
 	},
-	utils::i18n,
+	utils::{go_to, i18n},
 };
 
 #[component]
 pub fn SubscriptionsSection() -> impl IntoView {
 	let subs = expect_context::<SubscriptionsCtx>();
+	let group = expect_context::<SubscriptionsVideosResource>().group;
+	let query = use_query_map();
+	create_effect(move |_| {
+		group.set(query.with(|query| query.get("group").cloned()));
+	});
+
+	let group_is_empty = move || {
+		group.with(|group| {
+			group.as_ref().is_some_and(|name| {
+				subs.0.with(|subs| {
+					!subs
+						.group(name)
+						.is_some_and(|group| !group.channels.is_empty())
+				})
+			})
+		})
+	};
 
 	view! {
 		<GridContainer>
-			<h1 class="text-2xl font-semibold">
-				{i18n("sidebar.subscriptions")}
-			</h1>
+			<div class="flex flex-row justify-between items-center">
+				<Show
+					when=move || group.with(Option::is_some)
+					fallback=move || {
+						view! {
+							<h1 class="text-2xl font-semibold">
+								{i18n("sidebar.subscriptions")}
+							</h1>
+						}
+					}
+				>
+					<GroupHeader group=group/>
+				</Show>
+				<RefreshFeedButton/>
+			</div>
+			<GroupTabs group=group/>
 			<Suspense fallback=PlaceholderCardArray>
 				<Show
 					when=move || !subs.0.get().channels.is_empty()
 					fallback=ImportSubscriptions
 				>
-					<SubscriptionsVideos/>
+					<Show
+						when=move || !group_is_empty()
+						fallback=move || {
+							view! { <p>{i18n("subscriptions.empty_group")}</p> }
+						}
+					>
+						<SubscriptionsVideos/>
+					</Show>
 				</Show>
 			</Suspense>
 
@@ -44,6 +86,146 @@ pub fn SubscriptionsSection() -> impl IntoView {
 	}
 }
 
+#[component]
+pub fn GroupTabs(group: RwSignal<Option<String>>) -> impl IntoView {
+	let subs = expect_context::<SubscriptionsCtx>();
+	let groups = move || {
+		subs.0.with(|subs| {
+			subs.groups
+				.iter()
+				.map(|group| group.name.clone())
+				.collect::<Vec<_>>()
+		})
+	};
+
+	let tab_class = move |name: Option<String>| {
+		move || {
+			if group.with(|group| *group == name) {
+				"btn btn-sm btn-primary"
+			} else {
+				"btn btn-sm btn-outline"
+			}
+		}
+	};
+
+	view! {
+		<Show when=move || subs.0.with(|subs| !subs.groups.is_empty())>
+			<div class="flex flex-row flex-wrap gap-2">
+				<button
+					on:click=move |_| go_to("/subscriptions")
+					class=tab_class(None)
+				>
+					{i18n("subscriptions.all")}
+				</button>
+				<For each=groups key=|name| name.clone() let:name>
+					<button
+						on:click={
+							let name = name.clone();
+							move |_| go_to(group_path(&name))
+						}
+						class=tab_class(Some(name.clone()))
+					>
+						{name}
+					</button>
+				</For>
+			</div>
+		</Show>
+	}
+}
+
+#[component]
+pub fn GroupHeader(group: RwSignal<Option<String>>) -> impl IntoView {
+	let subs = expect_context::<SubscriptionsCtx>();
+	let editing = create_rw_signal(false);
+	let new_name = create_rw_signal(String::new());
+	let name = move || group.get().unwrap_or_default();
+
+	let start_editing = move |_| {
+		new_name.set(group.get_untracked().unwrap_or_default());
+		editing.set(true);
+	};
+
+	let rename = move || {
+		let renamed = new_name.get_untracked().trim().to_string();
+		let name = group.get_untracked().unwrap_or_default();
+		match subs.rename_group(&name, &renamed) {
+			Ok(()) => {
+				editing.set(false);
+				go_to(group_path(&renamed));
+			}
+			Err(err) => {
+				toast(Toast::new(err.to_string(), None, Some(ToastType::Error)))
+			}
+		}
+	};
+
+	let delete = move |_| {
+		subs.delete_group(&group.get_untracked().unwrap_or_default());
+		go_to("/subscriptions");
+	};
+
+	view! {
+		<div class="flex flex-row gap-x-2 items-center">
+			<Show
+				when=move || editing.get()
+				fallback=move || {
+					view! { <h1 class="text-2xl font-semibold">{name}</h1> }
+				}
+			>
+				<input
+					type="text"
+					class="input input-sm input-bordered"
+					prop:value=new_name
+					on:input=move |ev| new_name.set(event_target_value(&ev))
+					on:keydown=move |ev| {
+						if ev.key() == "Enter" {
+							rename();
+						}
+					}
+				/>
+			</Show>
+			<div class="tooltip" data-tip=i18n("subscriptions.rename_group")>
+				<button on:click=start_editing class="btn btn-ghost btn-xs">
+					<PencilSimple
+						weight=IconWeight::Regular
+						class="w-4 h-4 base-content"
+					/>
+				</button>
+			</div>
+			<div class="tooltip" data-tip=i18n("subscriptions.delete_group")>
+				<button on:click=delete class="btn btn-ghost btn-xs">
+					<Trash weight=IconWeight::Regular class="w-4 h-4 base-content"/>
+				</button>
+			</div>
+		</div>
+	}
+}
+
+/// The feed of a subscription group.
+pub fn group_path(name: &str) -> String {
+	format!("/subscriptions?group={}", encode(name))
+}
+
+#[component]
+pub fn RefreshFeedButton() -> impl IntoView {
+	let subs_videos = expect_context::<SubscriptionsVideosResource>();
+	let loading = subs_videos.resource.loading();
+
+	view! {
+		<button
+			on:click=move |_| subs_videos.refresh()
+			disabled=loading
+			class="btn btn-sm btn-ghost"
+		>
+			<ArrowClockwise
+				weight=IconWeight::Regular
+				class="w-4 h-4 base-content"
+			/>
+			{i18n("subscriptions.refresh")}
+		</button>
+	}
+}
+
 #[component]
 pub fn SubscriptionsVideos() -> impl IntoView {
 	let subs_videos_resource =
@@ -56,16 +238,7 @@ pub fn SubscriptionsVideos() -> impl IntoView {
 			{move || {
 				subs_videos_resource
 					.get()
-					.map(|subs_videos_res| {
-						match subs_videos_res {
-							Ok(subs_videos) => {
-								view! {
-									<SubscriptionsVideosInner subs_videos=subs_videos/>
-								}
-							}
-							Err(err) => view! { <FerrisError error=err/> },
-						}
-					})
+					.map(|feed| view! { <SubscriptionsVideosInner feed=feed/> })
 			}}
 
 		</Suspense>
@@ -73,28 +246,11 @@ pub fn SubscriptionsVideos() -> impl IntoView {
 }
 
 #[component]
-pub fn SubscriptionsVideosInner(
-	subs_videos: Vec<Result<ChannelVideos, RustyTubeError>>,
-) -> impl IntoView {
-	let mut videos: Vec<Vec<CommonVideo>> = Vec::new();
-	let mut fails: Vec<RustyTubeError> = Vec::new();
-
-	subs_videos.into_iter().for_each(|sub| match sub {
-		Ok(sub_videos) => videos.push(sub_videos.videos),
-		Err(error) => fails.push(error),
-	});
-
-	let mut total_videos: Vec<CommonVideo> =
-		videos.into_iter().flatten().collect();
-	total_videos.sort_by(|a, b| b.published.cmp(&a.published));
-
+pub fn SubscriptionsVideosInner(feed: SubscriptionsFeed) -> impl IntoView {
+	let SubscriptionsFeed { videos: total_videos, failures, .. } = feed;
 	let total_videos_len = total_videos.len();
 
-	let initial_len = if total_videos_len > 100 {
-		100
-	} else {
-		total_videos_len
-	};
+	let initial_len = total_videos_len.min(100);
 	let initial_videos = Vec::from(&total_videos[0..initial_len]);
 	let visible_videos = create_rw_signal(initial_videos);
 
@@ -124,12 +280,54 @@ pub fn SubscriptionsVideosInner(
 		.into_view()
 	};
 
+	let empty_view = (total_videos_len == 0 && failures.is_empty())
+		.then(|| view! { <p>{i18n("subscriptions.feed_empty")}</p> });
+
 	view! {
+		<FeedFailures failures=failures/>
+		{empty_view}
 		<CardGrid>{videos_view}</CardGrid>
 		{view_more_btn}
 	}
 }
 
+#[component]
+pub fn FeedFailures(failures: Vec<FeedFailure>) -> impl IntoView {
+	let count = failures.len();
+	let failures_view = failures
+		.into_iter()
+		.map(|failure| {
+			let name = if failure.name.is_empty() {
+				failure.id
+			} else {
+				failure.name
+			};
+			view! {
+				<li>
+					<span class="font-semibold">{name}</span>
+					{" - "}
+					{failure.error.to_string()}
+				</li>
+			}
+		})
+		.collect_view();
+
+	(count > 0).then(|| {
+		view! {
+			<div class="rounded-lg collapse collapse-arrow bg-base-200">
+				<input type="checkbox"/>
+				<div class="font-medium collapse-title text-warning">
+					{i18n("subscriptions.refresh_failed")}
+					{format!(" ({count})")}
+				</div>
+				<div class="collapse-content">
+					<ul class="space-y-1 text-sm">{failures_view}</ul>
+				</div>
+			</div>
+		}
+	})
+}
+
 #[component]
 pub fn ImportSubscriptions() -> impl IntoView {
 	view! {
@@ -163,8 +361,8 @@ fn load_more_videos(
 	total_videos: &[CommonVideo],
 ) {
 	visible_videos.update(|visible| {
-		let next_slice = &total_videos[(visible.len())..(visible.len() + 100)];
-		visible.extend_from_slice(next_slice);
+		let end = total_videos.len().min(visible.len() + 100);
+		visible.extend_from_slice(&total_videos[visible.len()..end]);
 	});
 }
 
@@ -213,14 +411,9 @@ async fn get_subs_from_file(
 	let filelist = input.files().ok_or(RustyTubeError::NoFileSelected)?;
 	let file = filelist.get(0).ok_or(RustyTubeError::NoFileSelected)?;
 	let blob: Blob = file.into();
-	let mut subscriptions = Subscriptions::read_subs(blob).await?;
-	subs_resource.0.update(|subs| {
-		subs.channels.append(&mut subscriptions.channels);
-		subs.channels
-			.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-		subs.channels.dedup_by(|a, b| a.name.eq_ignore_ascii_case(&b.name));
-		save_subs(subs);
-	});
+	let subscriptions = Subscriptions::read_subs(blob).await?;
+	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
+	subs_resource.import(subscriptions, server.get_untracked());
 	expect_context::<SubscriptionsVideosResource>().resource.refetch();
 	expect_context::<SubscriptionsThumbnailsResource>().resource.refetch();
 	Ok(())
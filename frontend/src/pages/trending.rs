@@ -1,7 +1,7 @@
 use invidious::{Trending, TrendingCategory};
 use leptos::{
 	component, view, For, IntoView, Props, RwSignal, SignalGet, SignalSet,
-	Suspense,
+	SignalWith, Suspense,
 };
 
 use crate::{
@@ -9,7 +9,7 @@ use crate::{
 		CardGrid, FerrisError, GridContainer, PlaceholderCardArray,
 		VideoPreviewCard,
 	},
-	resources::TrendingResource,
+	resources::{FeedFilter, TrendingResource},
 	utils::i18n,
 };
 
@@ -17,6 +17,7 @@ use crate::{
 pub fn TrendingSection() -> impl IntoView {
 	let category = RwSignal::new(TrendingCategory::Default);
 	let trending = TrendingResource::initialise(category);
+	let filter = FeedFilter::memo();
 
 	view! {
 		<GridContainer>
@@ -34,7 +35,11 @@ pub fn TrendingSection() -> impl IntoView {
 								view! {
 									<CardGrid>
 										<For
-											each=move || trending.videos.clone()
+											each=move || {
+												let mut videos = trending.videos.clone();
+												filter.with(|filter| filter.apply(&mut videos));
+												videos
+											}
 											key=|video| video.id.clone()
 											let:video
 										>
@@ -1,16 +1,17 @@
-use invidious::{Dislikes, Formats, Video};
+use invidious::{Dislikes, Formats, LocalPlaylist, Video};
 use leptos::*;
 use leptos_router::create_query_signal;
 use num_format::ToFormattedString;
 use phosphor_leptos::{
-	CalendarBlank, DownloadSimple, Eye, IconWeight, ShareNetwork, ThumbsDown,
-	ThumbsUp,
+	CalendarBlank, Check, DownloadSimple, Eye, IconWeight, ListPlus, Plus,
+	ShareNetwork, ThumbsDown, ThumbsUp,
 };
 
 use crate::{
 	components::{ChannelRoll, FerrisError},
-	contexts::{PlayerState, RegionConfigCtx},
-	resources::{SubscriptionsCtx, VideoResource},
+	contexts::{toast, PlayerState, RegionConfigCtx, Toast, ToastType},
+	resources::{PlaylistsCtx, SubscriptionsCtx, VideoResource},
+	utils::i18n,
 };
 
 #[component]
@@ -45,6 +46,7 @@ pub fn VideoInfoContent(video: Video) -> impl IntoView {
 		move || video.likes.to_formatted_string(&locale.get().to_num_fmt());
 	let author = video.author;
 	let author_id = video.author_id.clone();
+	let video_id = video.id.clone();
 	let sub_count_text = video.sub_count_text.clone();
 	let author_thumb_url =
 		video.author_thumbnails.first().cloned().map(|thumb| thumb.url);
@@ -142,6 +144,7 @@ pub fn VideoInfoContent(video: Video) -> impl IntoView {
 						image_url=author_thumb_url.unwrap_or_default()
 					/>
 					<div class="flex flex-row gap-x-2 justify-center items-end">
+						<SaveToPlaylistDropdown id=video_id/>
 						<DownloadsDropdown formats=formats title=title/>
 						<ShareDropdown/>
 					</div>
@@ -274,6 +277,108 @@ pub fn DownloadsDropdownList(formats: Formats, title: String) -> impl IntoView {
 	}
 }
 
+#[component]
+pub fn SaveToPlaylistDropdown(id: String) -> impl IntoView {
+	let playlists = expect_context::<PlaylistsCtx>();
+	let id = StoredValue::new(id);
+	let new_title = create_rw_signal(String::new());
+
+	let create_playlist = move |_| {
+		let title = new_title.get_untracked().trim().to_string();
+		match playlists.create(&title) {
+			Ok(playlist_id) => {
+				playlists.add_video(&playlist_id, &id.get_value());
+				new_title.set(String::new());
+			}
+			Err(err) => {
+				toast(Toast::new(err.to_string(), None, Some(ToastType::Error)))
+			}
+		}
+	};
+
+	view! {
+		<div class="z-20 dropdown dropdown-bottom sm:dropdown-end">
+			<div
+				tabindex="0"
+				role="button"
+				class="btn btn-circle btn-accent btn-outline"
+			>
+				<ListPlus weight=IconWeight::Regular class="w-6 h-6 base-content"/>
+			</div>
+			<div
+				tabindex="0"
+				class="p-4 mt-2 space-y-4 w-max rounded-lg dropdown-content h-max bg-base-200 shadow-dropdown"
+			>
+				<h1 class="font-semibold">{i18n("video.info.save_to_playlist")}</h1>
+				<ul class="flex overflow-y-scroll flex-col max-h-64">
+					<For
+						each=move || playlists.playlists.get()
+						key=|playlist| playlist.id.clone()
+						let:playlist
+					>
+						<SaveToPlaylistItem playlist=playlist id=id/>
+					</For>
+				</ul>
+				<div class="flex flex-row gap-x-2 items-center">
+					<input
+						type="text"
+						placeholder=i18n("video.info.new_playlist")
+						class="input input-sm input-bordered"
+						prop:value=new_title
+						on:input=move |ev| new_title.set(event_target_value(&ev))
+					/>
+					<button
+						on:click=create_playlist
+						class="btn btn-sm btn-circle btn-ghost"
+					>
+						<Plus weight=IconWeight::Regular class="w-4 h-4 base-content"/>
+					</button>
+				</div>
+			</div>
+		</div>
+	}
+}
+
+#[component]
+pub fn SaveToPlaylistItem(
+	playlist: LocalPlaylist,
+	id: StoredValue<String>,
+) -> impl IntoView {
+	let playlists = expect_context::<PlaylistsCtx>();
+	let playlist_id = StoredValue::new(playlist.id);
+	let title = move || {
+		playlists.get(&playlist_id.get_value()).map(|playlist| playlist.title)
+	};
+
+	let saved = move || {
+		playlists
+			.get(&playlist_id.get_value())
+			.is_some_and(|playlist| playlist.contains(&id.get_value()))
+	};
+
+	let toggle = move |_| {
+		if saved() {
+			playlists.remove_video(&playlist_id.get_value(), &id.get_value());
+		} else {
+			playlists.add_video(&playlist_id.get_value(), &id.get_value());
+		}
+	};
+
+	view! {
+		<li>
+			<button
+				on:click=toggle
+				class="flex flex-row justify-between items-center btn btn-sm btn-ghost"
+			>
+				<span>{title}</span>
+				<Show when=saved>
+					<Check weight=IconWeight::Bold class="w-4 h-4 base-content"/>
+				</Show>
+			</button>
+		</li>
+	}
+}
+
 #[derive(Clone, Copy)]
 pub enum LinkType {
 	RustyTube,
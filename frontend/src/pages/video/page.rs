@@ -1,6 +1,6 @@
 use leptos::{
-	component, expect_context, provide_context, view, IntoView, Props, Signal,
-	SignalGet,
+	component, create_effect, create_memo, expect_context, provide_context,
+	view, IntoView, Props, SignalGet, SignalSet, SignalWith,
 };
 use leptos_router::create_query_signal;
 
@@ -8,8 +8,14 @@ use super::{
 	comments::CommentsSection, info::VideoInfo, video_player::VideoContainer,
 };
 use crate::{
-	components::RecommendedSectionCollapsible,
-	resources::{SponsorBlockResource, VideoResource},
+	components::{
+		LocalPlaylistSectionCollapsible, QueueSection,
+		RecommendedSectionCollapsible,
+	},
+	resources::{
+		PlaylistVideosResource, PlaylistsCtx, SponsorBlockResource,
+		VideoResource,
+	},
 };
 
 #[component]
@@ -19,15 +25,36 @@ pub fn VideoPage() -> impl IntoView {
 	expect_context::<SponsorBlockResource>()
 		.set_video(create_query_signal::<String>("id").0);
 
-	let playlist_query_signal = create_query_signal::<String>("videos").0;
-	let playlist_videos = Signal::derive(move || {
-		playlist_query_signal.get().map(|videos| {
-			videos
-				.split(',')
-				.map(std::string::ToString::to_string)
-				.collect::<Vec<String>>()
+	let playlist_videos = PlaylistVideosResource::initialise();
+	provide_context(playlist_videos);
+
+	let (id, set_id) = create_query_signal::<String>("id");
+	create_effect(move |_| {
+		if id.get().is_none() {
+			if let Some(Ok(videos)) = playlist_videos.resource.get() {
+				if let Some(first) = videos.first() {
+					set_id.set(Some(first.clone()));
+				}
+			}
+		}
+	});
+
+	let playlists = expect_context::<PlaylistsCtx>();
+	let playlist_id = create_query_signal::<String>("playlist").0;
+	let is_local_playlist = create_memo(move |_| {
+		playlist_id.with(|id| {
+			id.as_ref().is_some_and(|id| playlists.get(id).is_some())
 		})
 	});
+	let local_playlist_view = move || {
+		playlist_id.get().filter(|_| is_local_playlist.get()).map(|id| {
+			view! {
+				<div class="mb-4">
+					<LocalPlaylistSectionCollapsible id=id/>
+				</div>
+			}
+		})
+	};
 
 	view! {
 		<div class="flex flex-row gap-x-4 mb-48 md:px-4">
@@ -37,10 +64,8 @@ pub fn VideoPage() -> impl IntoView {
 					<VideoInfo/>
 				</div>
 				<div class="mt-5 lg:hidden">
-					// {move || match playlist_query_signal.get().is_some() {
-					// true => ().into_view(),
-					// false => view! { <RecommendedSectionCollapsible/> },
-					// }}
+					{local_playlist_view}
+					<QueueSection/>
 					<RecommendedSectionCollapsible/>
 				</div>
 				<div class="mt-5 lg:mt-10">
@@ -48,6 +73,8 @@ pub fn VideoPage() -> impl IntoView {
 				</div>
 			</div>
 			<div class="hidden flex-col basis-2/6 lg:!flex">
+				{local_playlist_view}
+				<QueueSection/>
 				<RecommendedSectionCollapsible/>
 			</div>
 		</div>
@@ -1,20 +1,16 @@
 use invidious::{Caption, Captions};
 use leptos::{
-	component, document, expect_context, view, wasm_bindgen, web_sys,
-	CollectView, IntoView, Props, SignalGet, Suspense,
+	component, expect_context, view, CollectView, IntoView, Props, SignalGet,
+	Suspense,
 };
 use phosphor_leptos::{IconWeight, Subtitles};
-use wasm_bindgen::JsCast;
-use web_sys::{HtmlVideoElement, MouseEvent, TextTrackMode};
 
-use crate::{
-	components::FerrisError, contexts::VIDEO_PLAYER_ID,
-	resources::CaptionsResource,
-};
+use crate::{resources::CaptionsCtx, utils::i18n};
 
+/// Hidden for videos without captions.
 #[component]
 pub fn CaptionsDropdown() -> impl IntoView {
-	let captions = expect_context::<CaptionsResource>();
+	let captions = expect_context::<CaptionsCtx>();
 
 	view! {
 		<Suspense fallback=move || {
@@ -23,19 +19,18 @@ pub fn CaptionsDropdown() -> impl IntoView {
 
 			{move || {
 				captions
+					.captions
 					.resource
 					.get()
-					.map(|captions| match captions {
-						Ok(captions) => {
-							view! {
-								<div class="z-20 dropdown dropdown-top dropdown-end">
-									<CaptionsDropdownBtn/>
-									<CaptionsDropdownContent captions=captions/>
-								</div>
-							}
-								.into_view()
+					.and_then(Result::ok)
+					.filter(|captions| !captions.captions.is_empty())
+					.map(|captions| {
+						view! {
+							<div class="z-20 dropdown dropdown-top dropdown-end">
+								<CaptionsDropdownBtn/>
+								<CaptionsDropdownContent captions=captions/>
+							</div>
 						}
-						Err(err) => view! { <FerrisError error=err/> },
 					})
 			}}
 
@@ -46,8 +41,11 @@ pub fn CaptionsDropdown() -> impl IntoView {
 #[component]
 pub fn CaptionsDropdownBtn() -> impl IntoView {
 	view! {
-		<label tabindex="0" class="btn btn-ghost btn-xs">
-			<Subtitles weight=IconWeight::Regular class="w-4 h-4 base-content"/>
+		<label tabindex="0" class="btn btn-ghost btn-xs lg:btn-sm">
+			<Subtitles
+				weight=IconWeight::Regular
+				class="w-4 h-4 lg:w-5 lg:h-5 base-content"
+			/>
 		</label>
 	}
 }
@@ -57,43 +55,83 @@ pub fn CaptionsDropdownContent(captions: Captions) -> impl IntoView {
 	view! {
 		<ul
 			tabindex="0"
-			class="py-3 mr-2 mb-4 w-max h-auto max-h-48 rounded-xl shadow menu dropdown-content bg-base-300"
+			class="py-3 px-1.5 mr-2 mb-4 w-max rounded-xl shadow menu dropdown-content bg-base-300 h-max"
 		>
-			<div class="flex overflow-y-scroll flex-col px-3 space-y-2 h-full">
+			<div class="flex flex-row gap-x-4 p-2 w-max rounded-lg h-max">
+				<CaptionsList captions=captions.captions.clone() secondary=false/>
+				<CaptionsList captions=captions.captions secondary=true/>
+			</div>
+		</ul>
+	}
+}
+
+/// The captions that can be shown in the primary or the secondary slot.
+#[component]
+pub fn CaptionsList(captions: Vec<Caption>, secondary: bool) -> impl IntoView {
+	let title = if secondary {
+		i18n("video.controls.secondary_captions")
+	} else {
+		i18n("video.controls.captions")
+	};
+
+	view! {
+		<div class="flex flex-col items-center">
+			<h1>{title}</h1>
+			<div class="flex overflow-y-scroll flex-col my-4 max-h-48 lg:max-h-64">
+				<CaptionsDropdownItem caption=None secondary=secondary/>
 				{captions
-					.captions
 					.into_iter()
 					.map(|caption| {
-						view! { <CaptionsDropdownItem caption=caption/> }
+						view! {
+							<CaptionsDropdownItem
+								caption=Some(caption)
+								secondary=secondary
+							/>
+						}
 					})
 					.collect_view()}
 
 			</div>
-		</ul>
+		</div>
 	}
 }
 
+/// `None` turns the slot off.
 #[component]
-pub fn CaptionsDropdownItem(caption: Caption) -> impl IntoView {
-	// let set_captions = move |_: MouseEvent| {
-	// 	let video: HtmlVideoElement = document()
-	// 		.get_element_by_id(VIDEO_PLAYER_ID)
-	// 		.unwrap()
-	// 		.dyn_into()
-	// 		.unwrap();
-	// 	video
-	// 		.text_tracks()
-	// 		.unwrap()
-	// 		.get_track_by_id(&caption.language)
-	// 		.unwrap()
-	// 		.set_mode(TextTrackMode::Showing);
-	// };
+pub fn CaptionsDropdownItem(
+	caption: Option<Caption>,
+	secondary: bool,
+) -> impl IntoView {
+	let captions = expect_context::<CaptionsCtx>();
+	let slot = if secondary {
+		captions.secondary
+	} else {
+		captions.primary
+	};
+
+	let off_label = i18n("video.controls.captions_off");
+	let label = caption.as_ref().map(|caption| caption.label.clone());
+	let label = move || label.clone().unwrap_or_else(&off_label);
 
-	// view! {
-	// 	<div class="p-3 rounded-lg bg-base-100 cursor-pointer">
-	// 		<a on:click=set_captions class="text-base-content font-sans">
-	// 			{caption.label}
-	// 		</a>
-	// 	</div>
-	// }
+	let active = {
+		let caption = caption.clone();
+		move || slot.selected.get() == caption
+	};
+	let select = move |_| {
+		if secondary {
+			captions.select_secondary(caption.clone());
+		} else {
+			captions.select(caption.clone());
+		}
+	};
+
+	view! {
+		<button
+			on:click=select
+			class="justify-start btn btn-xs btn-ghost md:btn-sm"
+			class:btn-active=active
+		>
+			{label}
+		</button>
+	}
 }
@@ -0,0 +1,34 @@
+use leptos::{component, expect_context, view, IntoView};
+use phosphor_leptos::{IconWeight, Star};
+
+use crate::{
+	contexts::PlayerState, resources::SponsorBlockResource, utils::i18n,
+};
+
+/// Jumps to the SponsorBlock highlight. Hidden for videos without one.
+#[component]
+pub fn HighlightBtn() -> impl IntoView {
+	let state = expect_context::<PlayerState>();
+	let sponsorblock = expect_context::<SponsorBlockResource>();
+
+	move || {
+		sponsorblock.get_highlight().map(|highlight| {
+			let jump = move |_| {
+				let _ = state.seek(highlight.timeframe.0);
+			};
+
+			view! {
+				<button
+					on:click=jump
+					title=i18n("video.controls.highlight")
+					class="btn btn-ghost btn-xs lg:btn-sm"
+				>
+					<Star
+						weight=IconWeight::Regular
+						class="w-4 h-4 lg:w-5 lg:h-5 base-content"
+					/>
+				</button>
+			}
+		})
+	}
+}
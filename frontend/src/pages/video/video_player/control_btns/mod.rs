@@ -1,6 +1,7 @@
 mod captions;
 mod format;
 mod fullscreen;
+mod highlight;
 mod pause;
 mod progress_bar;
 mod theatre;
@@ -10,6 +11,7 @@ mod volume;
 pub use captions::CaptionsDropdown;
 pub use format::FormatDropdown;
 pub use fullscreen::FullScreenBtn;
+pub use highlight::HighlightBtn;
 pub use pause::PauseBtn;
 pub use progress_bar::ProgressBar;
 pub use theatre::FullWindowBtn;
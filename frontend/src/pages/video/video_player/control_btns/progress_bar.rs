@@ -1,11 +1,25 @@
+use config::SegmentAction;
+use invidious::{Storyboard, StoryboardTile};
 use leptos::{
-	component, expect_context, view, wasm_bindgen, web_sys, IntoView, Props,
-	RwSignal, SignalSet,
+	component, create_memo, expect_context, view, wasm_bindgen, web_sys,
+	CollectView, IntoView, Props, RwSignal, SignalGet, SignalGetUntracked,
+	SignalSet, SignalWithUntracked,
 };
 use wasm_bindgen::JsCast;
 use web_sys::{DragEvent, HtmlProgressElement, MouseEvent};
 
-use crate::contexts::{PlayerState, PlayerStyle};
+use crate::{
+	contexts::{
+		NetworkConfigCtx, PlayerState, PlayerStyle, SponsorBlockConfigCtx,
+	},
+	resources::{
+		chapter_at, segment_action, segment_colour, SponsorBlockResource,
+	},
+};
+
+/// Storyboard thumbnails are at most this fraction of the progress bar's
+/// width.
+const PREVIEW_WIDTH_DIVISOR: i32 = 4;
 
 #[component]
 pub fn ProgressBar() -> impl IntoView {
@@ -22,14 +36,39 @@ pub fn ProgressBar() -> impl IntoView {
 	let tip_styles = RwSignal::new("bottom: 48px; left: 0px;".to_string());
 	let tip_classes = RwSignal::new("hidden".to_string());
 	let tip_time = RwSignal::new("0:00".to_string());
+	let tip_tile = RwSignal::new(None::<StoryboardTile>);
+	let tip_chapter = RwSignal::new(None::<String>);
+	let sponsorblock = expect_context::<SponsorBlockResource>();
+	let storyboards = expect_context::<RwSignal<Vec<Storyboard>>>();
+	let server = expect_context::<NetworkConfigCtx>().server_slice.0;
 
 	let open_tip = move |mouse_event: MouseEvent| {
-		tip_time.set(get_seek_pos_as_time_str(mouse_event.clone()));
-		let styles =
-			format!("bottom: 48px; left: {}px;", mouse_event.offset_x());
+		let time = seek_pos(mouse_event.clone());
+		let tile = storyboards.with_untracked(|storyboards| {
+			Storyboard::for_width(storyboards, preview_width(&mouse_event))?
+				.tile_at(
+					&server.get_untracked(),
+					time,
+					state.duration.get_untracked(),
+				)
+		});
+		let half_width = tile.as_ref().map_or(0, |tile| {
+			i32::try_from(tile.width / 2).unwrap_or_default()
+		});
+
+		tip_time.set(utils::unix_to_hours_secs_mins(time));
+		tip_tile.set(tile);
+		tip_chapter.set(
+			chapter_at(&sponsorblock.get_chapters(), time)
+				.map(|chapter| chapter.description.clone()),
+		);
+		let styles = format!(
+			"bottom: 48px; left: {}px;",
+			(mouse_event.offset_x() - half_width).max(0)
+		);
 		tip_styles.set(styles);
 		tip_classes.set(
-			"absolute p-2 rounded-lg z-100 bg-primary text-primary-content h-fit w-fit".to_string(),
+			"absolute flex flex-col items-center gap-1 p-2 rounded-lg z-100 bg-primary text-primary-content h-fit w-fit".to_string(),
 		);
 	};
 	let close_tip = move |_| {
@@ -38,28 +77,130 @@ pub fn ProgressBar() -> impl IntoView {
 
 	view! {
 		<div style=tip_styles class=tip_classes>
+			{move || {
+				tip_tile
+					.get()
+					.map(|tile| view! { <div class="rounded" style=tile_style(&tile)></div> })
+			}}
+			{move || tip_chapter.get().map(|title| view! { <span class="font-semibold">{title}</span> })}
 			{tip_time}
 		</div>
-		<progress
-			on:mouseover=open_tip
-			on:mousemove=open_tip
-			on:mouseout=close_tip
-			on:click=on_click
-			on:dragend=on_drag
-			data-controlsvisible=style.controls_visible
-			max=state.duration.read_only()
-			value=state.current_time.read_only()
-			class=PROGRESS_BAR
-		></progress>
+		<div class="flex relative w-full">
+			<progress
+				on:mouseover=open_tip
+				on:mousemove=open_tip
+				on:mouseout=close_tip
+				on:click=on_click
+				on:dragend=on_drag
+				data-controlsvisible=style.controls_visible
+				max=state.duration.read_only()
+				value=state.current_time.read_only()
+				class=PROGRESS_BAR
+			></progress>
+			<SegmentMarkers/>
+			<ChapterMarkers/>
+		</div>
 	}
 }
 
-fn get_seek_pos_as_time_str<E>(event: E) -> String
-where
-	E: AsRef<MouseEvent>,
-{
-	let seek_pos = seek_pos(event);
-	utils::unix_to_hours_secs_mins(seek_pos)
+/// Marks the SponsorBlock segments of the video in their category's colour.
+#[component]
+fn SegmentMarkers() -> impl IntoView {
+	let state = expect_context::<PlayerState>();
+	let sponsorblock = expect_context::<SponsorBlockResource>();
+	let config = expect_context::<SponsorBlockConfigCtx>().sponsorblock_slice.0;
+	let duration = create_memo(move |_| state.duration.get());
+
+	let markers = move || {
+		let duration = duration.get();
+		if !duration.is_finite() || duration <= 0f64 {
+			return ().into_view();
+		}
+		let config = config.get();
+		sponsorblock
+			.get_segments()
+			.unwrap_or_default()
+			.into_iter()
+			.filter(|segment| {
+				segment_action(&config, segment) != SegmentAction::Disabled
+			})
+			.map(|segment| {
+				let (start, end) = segment.timeframe;
+				let style = format!(
+					"left: {}%; width: {}%; background-color: {};",
+					start / duration * 100f64,
+					(end - start) / duration * 100f64,
+					segment_colour(&segment.category)
+				);
+				view! { <div class="absolute h-full opacity-80" style=style></div> }
+			})
+			.collect_view()
+	};
+
+	view! { <div class="absolute inset-0 pointer-events-none">{markers}</div> }
+}
+
+/// Separates the chapters of the video and marks its highlight.
+#[component]
+fn ChapterMarkers() -> impl IntoView {
+	let state = expect_context::<PlayerState>();
+	let sponsorblock = expect_context::<SponsorBlockResource>();
+	let duration = create_memo(move |_| state.duration.get());
+
+	let percent = move |time: f64| time / duration.get() * 100f64;
+	let valid_duration = move || {
+		let duration = duration.get();
+		duration.is_finite() && duration > 0f64
+	};
+
+	let boundaries = move || {
+		if !valid_duration() {
+			return ().into_view();
+		}
+		sponsorblock
+			.get_chapters()
+			.into_iter()
+			.filter(|chapter| chapter.timeframe.0 > 0f64)
+			.map(|chapter| {
+				let style = format!("left: {}%;", percent(chapter.timeframe.0));
+				view! { <div class="absolute w-0.5 h-full bg-base-300" style=style></div> }
+			})
+			.collect_view()
+	};
+
+	let highlight = move || {
+		let highlight =
+			sponsorblock.get_highlight().filter(|_| valid_duration())?;
+		let style = format!(
+			"left: {}%; background-color: {};",
+			percent(highlight.timeframe.0),
+			segment_colour(&highlight.category)
+		);
+		Some(view! { <div class="absolute w-1 h-full" style=style></div> })
+	};
+
+	view! {
+		<div class="absolute inset-0 pointer-events-none">{boundaries} {highlight}</div>
+	}
+}
+
+fn preview_width(event: &MouseEvent) -> u32 {
+	event
+		.target()
+		.and_then(|target| target.dyn_into::<HtmlProgressElement>().ok())
+		.and_then(|progress_bar| {
+			u32::try_from(progress_bar.offset_width() / PREVIEW_WIDTH_DIVISOR)
+				.ok()
+		})
+		.unwrap_or_default()
+}
+
+fn tile_style(tile: &StoryboardTile) -> String {
+	format!(
+		"width: {}px; height: {}px; background-image: url('{}'); \
+		 background-position: -{}px -{}px;",
+		tile.width, tile.height, tile.url, tile.x, tile.y
+	)
 }
 
 fn seek_pos<E>(event: E) -> f64
@@ -24,6 +24,13 @@ pub fn VolumeKnob() -> impl IntoView {
 	let state = expect_context::<PlayerState>();
 	let volume_readable =
 		Signal::derive(move || state.volume.get().mul(100f64) as u8);
+	let audible_volume = Signal::derive(move || {
+		if state.muted.get() {
+			u8::MIN
+		} else {
+			volume_readable.get()
+		}
+	});
 
 	let knob_visible = RwSignal::new(false);
 	let toggle_knob = move |_| knob_visible.set(!knob_visible.get());
@@ -52,25 +59,25 @@ pub fn VolumeKnob() -> impl IntoView {
 				class="btn btn-ghost btn-xs peer lg:btn-sm"
 				id="vol_btn"
 			>
-				<Show when=move || volume_readable.get() == u8::MIN>
+				<Show when=move || audible_volume.get() == u8::MIN>
 					<SpeakerSimpleX
 						weight=IconWeight::Regular
 						class="w-4 h-4 lg:w-5 lg:h-5 base-content"
 					/>
 				</Show>
-				<Show when=move || (1..=20).contains(&volume_readable.get())>
+				<Show when=move || (1..=20).contains(&audible_volume.get())>
 					<SpeakerSimpleNone
 						weight=IconWeight::Regular
 						class="w-4 h-4 lg:w-5 lg:h-5 base-content"
 					/>
 				</Show>
-				<Show when=move || (21..=50).contains(&volume_readable.get())>
+				<Show when=move || (21..=50).contains(&audible_volume.get())>
 					<SpeakerSimpleLow
 						weight=IconWeight::Regular
 						class="w-4 h-4 lg:w-5 lg:h-5 base-content"
 					/>
 				</Show>
-				<Show when=move || (51..=100).contains(&volume_readable.get())>
+				<Show when=move || (51..=100).contains(&audible_volume.get())>
 					<SpeakerSimpleHigh
 						weight=IconWeight::Regular
 						class="w-4 h-4 lg:w-5 lg:h-5 base-content"
@@ -5,8 +5,8 @@ use leptos::{
 };
 
 use super::control_btns::{
-	FormatDropdown, FullScreenBtn, FullWindowBtn, PauseBtn, ProgressBar,
-	TimeInfo, VolumeKnob,
+	CaptionsDropdown, FormatDropdown, FullScreenBtn, FullWindowBtn,
+	HighlightBtn, PauseBtn, ProgressBar, TimeInfo, VolumeKnob,
 };
 use crate::contexts::{PlayerStyle, VIDEO_CONTROLS_ID};
 
@@ -35,8 +35,9 @@ pub fn VideoPlayerControls() -> impl IntoView {
 						<TimeInfo/>
 					</div>
 					<div class="flex flex-row">
+						<HighlightBtn/>
 						<FormatDropdown/>
-						// <CaptionsDropdown/>
+						<CaptionsDropdown/>
 						<FullWindowBtn/>
 						<FullScreenBtn/>
 					</div>
@@ -2,9 +2,7 @@ use invidious::Format;
 use leptos::*;
 
 use crate::{
-	components::FerrisError,
 	contexts::{PlayerState, AUDIO_PLAYER_ID},
-	resources::CaptionsResource,
 	utils::is_webkit,
 };
 
@@ -16,9 +14,6 @@ pub fn AudioStream() -> impl IntoView {
 		expect_context::<RwSignal<Option<Format>>>();
 	let source = move || format.get().and_then(|format| format.audio_url());
 
-	let captions = CaptionsResource::initialise();
-	provide_context(captions);
-
 	view! {
 		<audio
 			on:waiting=move |_| {
@@ -35,37 +30,21 @@ pub fn AudioStream() -> impl IntoView {
 				let _ = state.set_audio_ready(true);
 			}
 
+			on:ended=move |_| {
+				if format
+					.get_untracked()
+					.map_or(false, |format| format.is_audio_only())
+				{
+					state.ended();
+				}
+			}
+
 			id=AUDIO_PLAYER_ID
 			preload="auto"
 			controls=false
 			autoplay=false
 			playsinline=true
 			src=source
-		>
-			{move || {
-				captions
-					.resource
-					.get()
-					.map(|captions| match captions {
-						Ok(captions) => {
-							view! {
-								<For
-									each=move || captions.captions.clone()
-									key=|caption| caption.url.clone()
-									let:caption
-								>
-									<track
-										kind="captions"
-										srclang=caption.language
-										src=caption.url
-									/>
-								</For>
-							}
-						}
-						Err(err) => view! { <FerrisError error=err/> },
-					})
-			}}
-
-		</audio>
+		></audio>
 	}
 }
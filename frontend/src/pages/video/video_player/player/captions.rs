@@ -0,0 +1,92 @@
+use config::CaptionsPosition;
+use leptos::{
+	component, create_effect, create_memo, expect_context, view, IntoView,
+	Props, SignalGet,
+};
+
+use crate::{
+	contexts::{
+		toast, CaptionsConfigCtx, PlayerState, PlayerStyle, Toast, ToastType,
+	},
+	resources::{CaptionSlot, CaptionsCtx},
+};
+
+/// Draws the selected captions over the video. Unlike a native `<track>`,
+/// this is part of the player container, so it stays visible and scales in
+/// full window and fullscreen.
+#[component]
+pub fn CaptionsOverlay() -> impl IntoView {
+	let style = expect_context::<PlayerStyle>();
+	let captions = expect_context::<CaptionsCtx>();
+	let captions_slice = expect_context::<CaptionsConfigCtx>().captions_slice;
+
+	let position = move || match captions_slice.0.get().position {
+		CaptionsPosition::Top => "top",
+		CaptionsPosition::Bottom => "bottom",
+	};
+
+	view! {
+		<div
+			data-controls=move || style.controls_visible.get().to_string()
+			data-fullwindow=move || style.full_window.get().to_string()
+			data-position=position
+			class=CAPTIONS_CLASSES
+		>
+			<CaptionsText captions=captions.primary/>
+			<CaptionsText captions=captions.secondary/>
+		</div>
+	}
+}
+
+#[component]
+fn CaptionsText(captions: CaptionSlot) -> impl IntoView {
+	let state = expect_context::<PlayerState>();
+	let captions_slice = expect_context::<CaptionsConfigCtx>().captions_slice;
+
+	create_effect(move |_| {
+		if let Some(Err(err)) = captions.track.get() {
+			toast(Toast::new(err.to_string(), None, Some(ToastType::Error)));
+		}
+	});
+
+	let text = create_memo(move |_| {
+		let time = state.current_time.get();
+		captions
+			.track
+			.with(|track| {
+				track.as_ref().map_or_else(
+					|_| String::new(),
+					|track| {
+						track
+							.cues_at(time)
+							.map(|cue| cue.text.as_str())
+							.collect::<Vec<_>>()
+							.join("\n")
+					},
+				)
+			})
+			.unwrap_or_default()
+	});
+
+	view! {
+		<span
+			class="py-0.5 px-2 text-center whitespace-pre-line rounded"
+			class:hidden=move || text.get().is_empty()
+			style:font-size=move || format!("{}%", captions_slice.0.get().font_size)
+			style:color=move || captions_slice.0.get().colour
+			style:background-color=move || captions_slice.0.get().background()
+		>
+			{text}
+		</span>
+	}
+}
+
+pub const CAPTIONS_CLASSES: &str = "\
+flex absolute inset-0 z-10 flex-col gap-1 items-center justify-end \
+px-4 pt-4 pb-4 pointer-events-none transition-all duration-300 \
+text-base lg:text-2xl \
+\
+data-[controls=true]:pb-16 \
+data-[fullwindow=true]:text-2xl \
+data-[position=top]:justify-start \
+";
@@ -0,0 +1,78 @@
+use leptos::{
+	component, create_memo, expect_context, view, CollectView, IntoView, Memo,
+	Props, SignalGet,
+};
+use sponsorblock_rs::Segment;
+
+use crate::{
+	contexts::PlayerState,
+	resources::{chapter_at, SponsorBlockResource},
+	utils::i18n,
+};
+
+/// Lists the SponsorBlock chapters of the video. Hidden for videos without
+/// chapters.
+#[component]
+pub fn ChapterList() -> impl IntoView {
+	let state = expect_context::<PlayerState>();
+	let sponsorblock = expect_context::<SponsorBlockResource>();
+
+	move || {
+		let chapters = sponsorblock.get_chapters();
+		if chapters.is_empty() {
+			return None;
+		}
+
+		let current = {
+			let chapters = chapters.clone();
+			create_memo(move |_| {
+				chapter_at(&chapters, state.current_time.get())
+					.map(|chapter| chapter.uuid.clone())
+			})
+		};
+		let items = chapters
+			.into_iter()
+			.map(
+				|chapter| view! { <ChapterItem chapter=chapter current=current/> },
+			)
+			.collect_view();
+
+		Some(view! {
+			<div class="mt-5 rounded-lg collapse collapse-arrow bg-base-200">
+				<input type="checkbox"/>
+				<div class="text-xl font-medium collapse-title">
+					<span>{i18n("video.info.chapters")}</span>
+				</div>
+				<div class="collapse-content">
+					<div class="flex overflow-y-scroll flex-col max-h-64">{items}</div>
+				</div>
+			</div>
+		})
+	}
+}
+
+#[component]
+fn ChapterItem(
+	chapter: Segment,
+	current: Memo<Option<String>>,
+) -> impl IntoView {
+	let state = expect_context::<PlayerState>();
+
+	let start = chapter.timeframe.0;
+	let uuid = chapter.uuid;
+	let active = move || current.get().as_ref() == Some(&uuid);
+	let jump = move |_| {
+		let _ = state.seek(start);
+	};
+
+	view! {
+		<button
+			on:click=jump
+			class="gap-x-4 justify-start btn btn-ghost btn-sm"
+			class:btn-active=active
+		>
+			<span class="font-mono">{utils::unix_to_hours_secs_mins(start)}</span>
+			<span class="font-normal text-left">{chapter.description}</span>
+		</button>
+	}
+}
@@ -1,5 +1,9 @@
 mod audio;
+mod captions;
+mod chapters;
 mod player;
+mod shortcuts;
+mod sponsorblock;
 mod video;
 
 pub use player::VideoContainer;
@@ -16,15 +16,23 @@ use crate::{
 	pages::video::{
 		utils::get_format,
 		video_player::{
-			player::{audio::AudioStream, video::VideoStream},
+			player::{
+				audio::AudioStream,
+				captions::CaptionsOverlay,
+				chapters::ChapterList,
+				shortcuts::{use_keyboard_shortcuts, ShortcutsOverlay},
+				sponsorblock::SkipSegmentBtn,
+				video::VideoStream,
+			},
 			VideoPlayerControls,
 		},
 	},
-	resources::VideoResource,
+	resources::{CaptionsCtx, VideoResource},
 };
 
 #[component]
 pub fn VideoContainer() -> impl IntoView {
+	provide_context(CaptionsCtx::initialise());
 	provide_context(PlayerState::init());
 	provide_context(PlayerStyle::init());
 
@@ -43,6 +51,7 @@ pub fn VideoContainer() -> impl IntoView {
 			}}
 
 		</Suspense>
+		<ChapterList/>
 	}
 }
 
@@ -50,6 +59,10 @@ pub fn VideoContainer() -> impl IntoView {
 pub fn VideoPlayer(video: Video) -> impl IntoView {
 	let state = expect_context::<PlayerState>();
 	let style = expect_context::<PlayerStyle>();
+	state.load_position(&video);
+
+	let shortcuts_visible = create_rw_signal(false);
+	use_keyboard_shortcuts(state, style, shortcuts_visible);
 
 	let formats = Formats::from((
 		video.adaptive_formats.clone(),
@@ -58,6 +71,7 @@ pub fn VideoPlayer(video: Video) -> impl IntoView {
 	let format = get_format(&formats).ok();
 	provide_context(create_rw_signal(formats));
 	provide_context::<RwSignal<Option<Format>>>(create_rw_signal(format));
+	provide_context(create_rw_signal(video.storyboards.clone()));
 
 	// let format = get_video_format_ctx(&formats).ok();
 	// provide_context(create_rw_signal(format));
@@ -112,8 +126,11 @@ pub fn VideoPlayer(video: Video) -> impl IntoView {
 		>
 			<VideoStream video=video/>
 			<AudioStream/>
+			<CaptionsOverlay/>
+			<SkipSegmentBtn/>
 			<VideoPlayerControls/>
 			<LoadingCircle/>
+			<ShortcutsOverlay shortcuts_visible=shortcuts_visible/>
 		</div>
 	}
 }
@@ -174,7 +191,7 @@ fn controls_hovered() -> bool {
 	})
 }
 
-fn toggle_fullscreen() {
+pub fn toggle_fullscreen() {
 	if document().fullscreen() {
 		document().exit_fullscreen();
 	} else if let Ok(element) =
@@ -0,0 +1,182 @@
+use config::KeybindingsConfig;
+use leptos::{
+	component, ev, expect_context, on_cleanup, view, wasm_bindgen, web_sys,
+	window_event_listener, CollectView, IntoView, Props, RwSignal, Show,
+	SignalGet, SignalGetUntracked, SignalSet,
+};
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlElement, KeyboardEvent};
+
+use super::player::toggle_fullscreen;
+use crate::{
+	contexts::{PlayerConfigCtx, PlayerState, PlayerStyle},
+	utils::i18n,
+};
+
+/// Volume step applied by the volume up/down bindings.
+const VOLUME_STEP: f64 = 0.05;
+
+/// Listens for player shortcuts on the window for as long as the calling
+/// component is mounted. Keys typed into text fields are ignored.
+pub fn use_keyboard_shortcuts(
+	state: PlayerState,
+	style: PlayerStyle,
+	shortcuts_visible: RwSignal<bool>,
+) {
+	let player_config = expect_context::<PlayerConfigCtx>();
+	let keybindings = player_config.keybindings_slice.0;
+	let interval = player_config.fast_forward_interval_slice.0;
+
+	let handle = window_event_listener(ev::keydown, move |event| {
+		if event.ctrl_key()
+			|| event.alt_key()
+			|| event.meta_key()
+			|| is_typing(&event)
+		{
+			return;
+		}
+
+		let key = key_name(&event);
+		let bindings = keybindings.get_untracked();
+		let interval = f64::from(interval.get_untracked());
+		let digit = key.parse::<u8>().ok().filter(|digit| *digit < 10);
+		let matches = |keys: &[String]| {
+			keys.iter().any(|bound| bound.eq_ignore_ascii_case(&key))
+		};
+
+		let handled = if matches(&bindings.toggle_playback) {
+			let _ = state.toggle_playback();
+			true
+		} else if matches(&bindings.seek_backward) {
+			let _ = state.seek_by(-interval);
+			true
+		} else if matches(&bindings.seek_forward) {
+			let _ = state.seek_by(interval);
+			true
+		} else if matches(&bindings.volume_up) {
+			let _ = state.change_volume_by(VOLUME_STEP);
+			true
+		} else if matches(&bindings.volume_down) {
+			let _ = state.change_volume_by(-VOLUME_STEP);
+			true
+		} else if matches(&bindings.fullscreen) {
+			toggle_fullscreen();
+			true
+		} else if matches(&bindings.full_window) {
+			style.full_window.set(!style.full_window.get_untracked());
+			true
+		} else if matches(&bindings.mute) {
+			let _ = state.toggle_mute();
+			true
+		} else if matches(&bindings.cycle_captions) {
+			state.cycle_captions();
+			true
+		} else if matches(&bindings.show_shortcuts) {
+			shortcuts_visible.set(!shortcuts_visible.get_untracked());
+			true
+		} else if let Some(digit) = digit {
+			let _ = state.seek_to_fraction(f64::from(digit) / 10f64);
+			true
+		} else if key.eq("Escape") && shortcuts_visible.get_untracked() {
+			shortcuts_visible.set(false);
+			true
+		} else {
+			false
+		};
+
+		if handled {
+			event.prevent_default();
+		}
+	});
+
+	on_cleanup(move || handle.remove());
+}
+
+/// Uses `Space` for the space bar so it can be written in the config.
+fn key_name(event: &KeyboardEvent) -> String {
+	let key = event.key();
+	if key.eq(" ") {
+		String::from("Space")
+	} else {
+		key
+	}
+}
+
+fn is_typing(event: &KeyboardEvent) -> bool {
+	event
+		.target()
+		.and_then(|target| target.dyn_into::<HtmlElement>().ok())
+		.map_or(false, |element| {
+			element.is_content_editable()
+				|| matches!(
+					element.tag_name().to_lowercase().as_str(),
+					"input" | "textarea" | "select"
+				)
+		})
+}
+
+#[component]
+pub fn ShortcutsOverlay(shortcuts_visible: RwSignal<bool>) -> impl IntoView {
+	let keybindings = expect_context::<PlayerConfigCtx>().keybindings_slice.0;
+
+	let rows = move || {
+		shortcut_rows(&keybindings.get())
+			.into_iter()
+			.map(|(label, keys)| {
+				view! {
+					<tr>
+						<td>{i18n(label)}</td>
+						<td>
+							{keys
+								.into_iter()
+								.map(|key| {
+									view! { <kbd class="mx-0.5 kbd kbd-sm">{key}</kbd> }
+								})
+								.collect_view()}
+						</td>
+					</tr>
+				}
+			})
+			.collect_view()
+	};
+
+	view! {
+		<Show when=move || shortcuts_visible.get()>
+			<div
+				on:click=move |ev| {
+					ev.stop_propagation();
+					shortcuts_visible.set(false);
+				}
+
+				class="flex overflow-y-auto absolute inset-0 z-30 justify-center items-center bg-opacity-80 bg-base-300"
+			>
+				<div class="p-4 rounded-xl bg-base-200">
+					<h1 class="mb-2 text-xl font-semibold">
+						{i18n("video.shortcuts.title")}
+					</h1>
+					<table class="table table-xs md:table-sm">
+						<tbody>{rows}</tbody>
+					</table>
+				</div>
+			</div>
+		</Show>
+	}
+}
+
+fn shortcut_rows(
+	bindings: &KeybindingsConfig,
+) -> Vec<(&'static str, Vec<String>)> {
+	vec![
+		("video.shortcuts.toggle_playback", bindings.toggle_playback.clone()),
+		("video.shortcuts.seek_backward", bindings.seek_backward.clone()),
+		("video.shortcuts.seek_forward", bindings.seek_forward.clone()),
+		("video.shortcuts.volume_up", bindings.volume_up.clone()),
+		("video.shortcuts.volume_down", bindings.volume_down.clone()),
+		("video.shortcuts.fullscreen", bindings.fullscreen.clone()),
+		("video.shortcuts.full_window", bindings.full_window.clone()),
+		("video.shortcuts.mute", bindings.mute.clone()),
+		("video.shortcuts.cycle_captions", bindings.cycle_captions.clone()),
+		("video.shortcuts.jump", vec![String::from("0-9")]),
+		("video.shortcuts.show_shortcuts", bindings.show_shortcuts.clone()),
+	]
+}
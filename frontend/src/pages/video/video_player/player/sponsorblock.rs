@@ -0,0 +1,27 @@
+use leptos::{component, expect_context, view, IntoView, SignalGet};
+
+use crate::{contexts::PlayerState, utils::i18n};
+
+/// Offers to skip the playing segment when its category is skipped manually.
+#[component]
+pub fn SkipSegmentBtn() -> impl IntoView {
+	let state = expect_context::<PlayerState>();
+
+	move || {
+		state.skip_prompt.get().map(|segment| {
+			let skip = move |ev: leptos::ev::MouseEvent| {
+				ev.stop_propagation();
+				state.skip_segment(&segment);
+			};
+
+			view! {
+				<button
+					on:click=skip
+					class="absolute right-4 bottom-20 z-20 btn btn-sm btn-primary"
+				>
+					{i18n("sponsorblock.skip")}
+				</button>
+			}
+		})
+	}
+}
@@ -20,6 +20,7 @@ pub fn VideoStream(video: Video) -> impl IntoView {
 			}
 
 			on:loadedmetadata=move |_| {
+				let _ = state.restore_position();
 				if is_webkit() {
 					let _ = state.set_video_ready(true);
 				}
@@ -35,6 +36,8 @@ pub fn VideoStream(video: Video) -> impl IntoView {
 				let _ = state.update_time();
 			}
 
+			on:ended=move |_| state.ended()
+
 			poster=video.thumbnails.first().map(|thumb| thumb.url.clone())
 			preload="auto"
 			controls=false
@@ -0,0 +1,305 @@
+use config::Config;
+use invidious::{
+	ImportReport, ImportStrategy, LocalPlaylist, ProfileImport, Subscriptions,
+	WatchHistory,
+};
+use leptos::{
+	expect_context, SignalGetUntracked, SignalSet, SignalWithUntracked,
+};
+use rustytube_error::RustyTubeError;
+use serde::{Deserialize, Serialize};
+use utils::get_unix_time;
+
+use super::{
+	save_subs, HistoryCtx, PlaylistsCtx, PositionsCtx, SubscriptionsCtx,
+	WatchPositions,
+};
+use crate::contexts::ConfigCtx;
+
+pub const BACKUP_FORMAT: &str = "RustyTube";
+/// Bumped whenever the layout of [`RustyTubeBackup`] changes.
+pub const BACKUP_VERSION: u32 = 1;
+
+/// A complete profile, or the parts of one chosen when exporting.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RustyTubeBackup {
+	pub format: String,
+	pub version: u32,
+	/// Unix timestamp in seconds.
+	pub created: u64,
+	/// The config as TOML, the same way it is stored.
+	pub config: Option<String>,
+	pub subscriptions: Option<Subscriptions>,
+	pub playlists: Option<Vec<LocalPlaylist>>,
+	pub history: Option<WatchHistory>,
+	pub positions: Option<WatchPositions>,
+}
+
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BackupSections {
+	pub config: bool,
+	pub subscriptions: bool,
+	pub playlists: bool,
+	pub history: bool,
+	pub positions: bool,
+}
+
+impl Default for BackupSections {
+	fn default() -> Self {
+		Self {
+			config: true,
+			subscriptions: true,
+			playlists: true,
+			history: true,
+			positions: true,
+		}
+	}
+}
+
+/// A backup that has been checked, waiting for the user to confirm it.
+#[derive(Clone, Debug)]
+pub struct PendingBackup {
+	config: Option<Config>,
+	subscriptions: Option<Subscriptions>,
+	playlists: Option<Vec<LocalPlaylist>>,
+	history: Option<WatchHistory>,
+	positions: Option<WatchPositions>,
+}
+
+/// The number of entries in each section of a backup, or `None` for the
+/// sections it leaves out.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BackupPreview {
+	pub config: bool,
+	pub subscriptions: Option<usize>,
+	pub playlists: Option<usize>,
+	pub history: Option<usize>,
+	pub positions: Option<usize>,
+}
+
+impl PendingBackup {
+	/// # Errors
+	///
+	/// - Not a backup, or a backup from a newer version.
+	/// - Json or Toml deserialisation error.
+	pub fn read(json: &str) -> Result<Self, RustyTubeError> {
+		let backup = serde_json::from_str::<RustyTubeBackup>(json)
+			.map_err(|_| RustyTubeError::BackupFormat)?;
+		if backup.format != BACKUP_FORMAT {
+			return Err(RustyTubeError::BackupFormat);
+		}
+		if backup.version > BACKUP_VERSION {
+			return Err(RustyTubeError::BackupVersion(backup.version));
+		}
+		let config = backup
+			.config
+			.map(|config| toml::from_str::<Config>(&config))
+			.transpose()?;
+
+		Ok(Self {
+			config,
+			subscriptions: backup.subscriptions,
+			playlists: backup.playlists,
+			history: backup.history,
+			positions: backup.positions,
+		})
+	}
+
+	pub fn preview(&self) -> BackupPreview {
+		BackupPreview {
+			config: self.config.is_some(),
+			subscriptions: self
+				.subscriptions
+				.as_ref()
+				.map(|subs| subs.channels.len()),
+			playlists: self.playlists.as_ref().map(Vec::len),
+			history: self.history.as_ref().map(|history| history.entries.len()),
+			positions: self
+				.positions
+				.as_ref()
+				.map(|positions| positions.positions.len()),
+		}
+	}
+}
+
+/// What was added from another app's export.
+#[derive(Clone, Debug, Default)]
+pub struct ProfileImportReport {
+	pub subscriptions: usize,
+	pub playlists: ImportReport,
+	pub history: usize,
+}
+
+/// How much of an import is new, shown before it is applied.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImportPreview {
+	pub new_subscriptions: usize,
+	pub known_subscriptions: usize,
+	pub playlists: Vec<PlaylistPreview>,
+	pub new_history: usize,
+	pub known_history: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlaylistPreview {
+	pub title: String,
+	pub videos: usize,
+	/// A playlist with the same title already exists.
+	pub exists: bool,
+}
+
+/// The contexts a backup is taken from and restored into.
+#[derive(Clone, Copy)]
+pub struct UserData {
+	config: ConfigCtx,
+	subscriptions: SubscriptionsCtx,
+	playlists: PlaylistsCtx,
+	history: HistoryCtx,
+	positions: PositionsCtx,
+}
+
+impl UserData {
+	pub fn from_context() -> Self {
+		Self {
+			config: expect_context::<ConfigCtx>(),
+			subscriptions: expect_context::<SubscriptionsCtx>(),
+			playlists: expect_context::<PlaylistsCtx>(),
+			history: expect_context::<HistoryCtx>(),
+			positions: expect_context::<PositionsCtx>(),
+		}
+	}
+
+	/// Serialises the selected sections as a JSON backup.
+	///
+	/// # Errors
+	///
+	/// - Toml or Json serialisation error.
+	pub fn export_backup(
+		&self,
+		sections: BackupSections,
+	) -> Result<String, RustyTubeError> {
+		let config = sections
+			.config
+			.then(|| toml::to_string(&self.config.0.get_untracked()))
+			.transpose()?;
+		let backup = RustyTubeBackup {
+			format: BACKUP_FORMAT.to_string(),
+			version: BACKUP_VERSION,
+			created: get_unix_time(),
+			config,
+			subscriptions: sections
+				.subscriptions
+				.then(|| self.subscriptions.0.get_untracked()),
+			playlists: sections
+				.playlists
+				.then(|| self.playlists.playlists.get_untracked()),
+			history: sections.history.then(|| self.history.0.get_untracked()),
+			positions: sections
+				.positions
+				.then(|| self.positions.0.get_untracked()),
+		};
+		Ok(serde_json::to_string_pretty(&backup)?)
+	}
+
+	/// Restores the selected sections that the backup contains, replacing
+	/// the current data. The homepage is reset if its playlist is gone.
+	///
+	/// Returns the sections that were restored.
+	pub fn restore_backup(
+		&self,
+		backup: PendingBackup,
+		sections: BackupSections,
+	) -> BackupSections {
+		let restored = BackupSections {
+			config: sections.config && backup.config.is_some(),
+			subscriptions: sections.subscriptions
+				&& backup.subscriptions.is_some(),
+			playlists: sections.playlists && backup.playlists.is_some(),
+			history: sections.history && backup.history.is_some(),
+			positions: sections.positions && backup.positions.is_some(),
+		};
+
+		if let Some(config) = backup.config.filter(|_| restored.config) {
+			self.config.0.set(config);
+		}
+		if let Some(subs) =
+			backup.subscriptions.filter(|_| restored.subscriptions)
+		{
+			save_subs(&subs);
+			self.subscriptions.0.set(subs);
+		}
+		if let Some(playlists) = backup.playlists.filter(|_| restored.playlists)
+		{
+			self.playlists.replace(playlists);
+		}
+		if let Some(history) = backup.history.filter(|_| restored.history) {
+			self.history.replace(history);
+		}
+		if let Some(positions) = backup.positions.filter(|_| restored.positions)
+		{
+			self.positions.replace(positions);
+		}
+		self.playlists.check_homepage();
+		restored
+	}
+
+	/// Counts what in `profile` is already in the current data.
+	pub fn preview_import(&self, profile: &ProfileImport) -> ImportPreview {
+		let channels = &profile.subscriptions.channels;
+		let new_subscriptions = self.subscriptions.0.with_untracked(|subs| {
+			channels
+				.iter()
+				.filter(|channel| {
+					!subs.channels.iter().any(|sub| sub.id == channel.id)
+				})
+				.count()
+		});
+		let new_history = self.history.0.with_untracked(|history| {
+			profile
+				.history
+				.iter()
+				.filter(|entry| !history.contains(&entry.id))
+				.count()
+		});
+		let playlists = self.playlists.playlists.with_untracked(|existing| {
+			profile
+				.playlists
+				.iter()
+				.map(|playlist| PlaylistPreview {
+					title: playlist.title.clone(),
+					videos: playlist.videos.len(),
+					exists: existing
+						.iter()
+						.any(|existing| existing.has_title(&playlist.title)),
+				})
+				.collect()
+		});
+
+		ImportPreview {
+			new_subscriptions,
+			known_subscriptions: channels.len() - new_subscriptions,
+			playlists,
+			new_history,
+			known_history: profile.history.len() - new_history,
+		}
+	}
+
+	/// Merges an export from another app into the current data, resolving
+	/// playlist conflicts with `strategy`. Channels and videos imported
+	/// without details are looked up on `server` afterwards.
+	pub fn import_profile(
+		&self,
+		profile: ProfileImport,
+		strategy: ImportStrategy,
+		server: &str,
+	) -> ProfileImportReport {
+		ProfileImportReport {
+			subscriptions: self
+				.subscriptions
+				.import(profile.subscriptions, server.to_string()),
+			playlists: self.playlists.import(profile.playlists, strategy),
+			history: self.history.import(profile.history, server.to_string()),
+		}
+	}
+}
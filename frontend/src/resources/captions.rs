@@ -1,9 +1,13 @@
-use invidious::Captions;
-use leptos::{expect_context, Resource, SignalGet};
+use config::CaptionsConfig;
+use invidious::{Caption, CaptionTrack, Captions};
+use leptos::{
+	create_effect, create_rw_signal, expect_context, Resource, RwSignal,
+	SignalGet, SignalGetUntracked, SignalSet, SignalWithUntracked,
+};
 use leptos_router::create_query_signal;
 use rustytube_error::RustyTubeError;
 
-use crate::contexts::NetworkConfigCtx;
+use crate::contexts::{CaptionsConfigCtx, NetworkConfigCtx};
 
 static POPULAR_KEY: &str = "popular_videos";
 
@@ -44,3 +48,141 @@ async fn fetch_captions(
 ) -> Result<Captions, RustyTubeError> {
 	Captions::fetch_captions(&args.server, &args.id).await
 }
+
+#[derive(Clone, PartialEq)]
+pub struct CaptionTrackArgs {
+	server: String,
+	caption: Option<Caption>,
+}
+
+/// Captions shown in one of the two slots, or `None` when the slot is off.
+#[derive(Clone, Copy)]
+pub struct CaptionSlot {
+	pub selected: RwSignal<Option<Caption>>,
+	pub track: Resource<CaptionTrackArgs, Result<CaptionTrack, RustyTubeError>>,
+}
+
+impl CaptionSlot {
+	fn new() -> Self {
+		let selected = create_rw_signal(None);
+		let track = Resource::local(
+			move || CaptionTrackArgs {
+				server: expect_context::<NetworkConfigCtx>()
+					.server_slice
+					.0
+					.get(),
+				caption: selected.get(),
+			},
+			fetch_caption_track,
+		);
+		Self { selected, track }
+	}
+
+	/// Shows `caption` and remembers its language in the config field.
+	fn select(
+		&self,
+		caption: Option<Caption>,
+		language: fn(&mut CaptionsConfig) -> &mut Option<String>,
+	) {
+		let captions_slice =
+			expect_context::<CaptionsConfigCtx>().captions_slice;
+		let mut config = captions_slice.0.get_untracked();
+		*language(&mut config) =
+			caption.as_ref().map(|caption| caption.language.clone());
+		captions_slice.1.set(config);
+		self.selected.set(caption);
+	}
+}
+
+/// The captions of the current video and the ones being shown.
+#[derive(Clone, Copy)]
+pub struct CaptionsCtx {
+	pub captions: CaptionsResource,
+	pub primary: CaptionSlot,
+	/// Shown alongside the primary captions, e.g. a translation.
+	pub secondary: CaptionSlot,
+}
+
+impl CaptionsCtx {
+	/// Shows the captions in the remembered languages whenever a video's
+	/// captions load, if it has them.
+	pub fn initialise() -> Self {
+		let captions = CaptionsResource::initialise();
+		let primary = CaptionSlot::new();
+		let secondary = CaptionSlot::new();
+
+		create_effect(move |_| {
+			let config = expect_context::<CaptionsConfigCtx>()
+				.captions_slice
+				.0
+				.get_untracked();
+			let available = captions
+				.resource
+				.get()
+				.and_then(Result::ok)
+				.map(|captions| captions.captions)
+				.unwrap_or_default();
+			let find = |language: Option<String>| {
+				find_language(&available, &language?)
+			};
+
+			let first = find(config.language);
+			let second = find(config.secondary_language)
+				.filter(|second| first.as_ref() != Some(second));
+			primary.selected.set(first);
+			secondary.selected.set(second);
+		});
+
+		Self { captions, primary, secondary }
+	}
+
+	/// Shows `caption` as the primary captions, or turns them off, and
+	/// remembers the choice for the next videos.
+	pub fn select(&self, caption: Option<Caption>) {
+		self.primary.select(caption, |config| &mut config.language);
+	}
+
+	pub fn select_secondary(&self, caption: Option<Caption>) {
+		self.secondary.select(caption, |config| &mut config.secondary_language);
+	}
+
+	/// Shows the next primary captions, turning them off after the last ones.
+	pub fn cycle(&self) {
+		let Some(Ok(captions)) = self.captions.resource.get_untracked() else {
+			return;
+		};
+		let next = self.primary.selected.with_untracked(|selected| {
+			let index = selected.as_ref().and_then(|selected| {
+				captions.captions.iter().position(|caption| caption == selected)
+			});
+			captions.captions.get(index.map_or(0, |index| index + 1)).cloned()
+		});
+		self.select(next);
+	}
+}
+
+/// Prefers an exact match, then captions in another variant of the language,
+/// e.g. `en-GB` for `en`.
+fn find_language(captions: &[Caption], language: &str) -> Option<Caption> {
+	let primary = |code: &str| {
+		code.split(['-', '_']).next().unwrap_or(code).to_lowercase()
+	};
+	captions
+		.iter()
+		.find(|caption| caption.language.eq_ignore_ascii_case(language))
+		.or_else(|| {
+			captions
+				.iter()
+				.find(|caption| primary(&caption.language) == primary(language))
+		})
+		.cloned()
+}
+
+async fn fetch_caption_track(
+	args: CaptionTrackArgs,
+) -> Result<CaptionTrack, RustyTubeError> {
+	match args.caption {
+		Some(caption) => CaptionTrack::fetch(&args.server, &caption).await,
+		None => Ok(CaptionTrack::default()),
+	}
+}
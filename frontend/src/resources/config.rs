@@ -28,5 +28,5 @@ async fn fetch_config() -> Result<Config, RustyTubeError> {
 	// 	}
 	// 	false => Config::load(),
 	// }
-	Config::load()
+	Config::load().await
 }
@@ -0,0 +1,95 @@
+use std::collections::HashSet;
+
+use config::{BlocklistConfig, FilterConfig, Filterable, VideoFilter};
+use invidious::{SearchResult, SubscriptionsFeed};
+use leptos::{
+	expect_context, Memo, SignalGet, SignalGetUntracked, SignalSet,
+	SignalWithUntracked,
+};
+
+use super::HistoryCtx;
+use crate::contexts::FilterConfigCtx;
+
+/// The feed filters and blocklist, and the watched videos when those are
+/// hidden. Listings that are cheap to reload take it as a resource argument,
+/// the rest apply a [`FeedFilter::memo`] as they render.
+#[derive(Clone, PartialEq, Eq)]
+pub struct FeedFilter {
+	config: FilterConfig,
+	blocklist: BlocklistConfig,
+	watched: HashSet<String>,
+}
+
+impl FeedFilter {
+	pub fn from_context() -> Self {
+		let ctx = expect_context::<FilterConfigCtx>();
+		Self::new(ctx.filters_slice.0.get(), ctx.blocklist_slice.0.get())
+	}
+
+	/// Follows the filters, so fetched listings can be filtered again when
+	/// they change without refetching.
+	pub fn memo() -> Memo<Self> {
+		let ctx = expect_context::<FilterConfigCtx>();
+		Memo::new(move |_| {
+			Self::new(ctx.filters_slice.0.get(), ctx.blocklist_slice.0.get())
+		})
+	}
+
+	fn new(config: FilterConfig, blocklist: BlocklistConfig) -> Self {
+		let watched = if config.hide_watched {
+			expect_context::<HistoryCtx>().0.with_untracked(|history| {
+				history.entries.iter().map(|entry| entry.id.clone()).collect()
+			})
+		} else {
+			HashSet::new()
+		};
+		Self { config, blocklist, watched }
+	}
+
+	pub fn apply<T: Filterable>(&self, videos: &mut Vec<T>) {
+		let filter = VideoFilter::new(&self.config, &self.blocklist);
+		videos.retain(|video| self.allows(&filter, video));
+	}
+
+	/// Also hides the shorts the feed knows by id, as RSS feeds do not give
+	/// their length.
+	pub fn apply_feed(&self, feed: &mut SubscriptionsFeed) {
+		let filter = VideoFilter::new(&self.config, &self.blocklist);
+		let shorts = &feed.shorts;
+		feed.videos.retain(|video| {
+			self.allows(&filter, video)
+				&& !(self.config.hide_shorts && shorts.contains(&video.id))
+		});
+	}
+
+	/// Filters the videos, and drops the channels and playlists of blocked
+	/// channels.
+	pub fn apply_search(&self, results: &mut Vec<SearchResult>) {
+		let filter = VideoFilter::new(&self.config, &self.blocklist);
+		results.retain(|result| match result {
+			SearchResult::Video(video) => self.allows(&filter, video),
+			SearchResult::Playlist(playlist) => {
+				!self.blocklist.blocks_channel(&playlist.author_id)
+			}
+			SearchResult::Channel(channel) => {
+				!self.blocklist.blocks_channel(&channel.id)
+			}
+		});
+	}
+
+	pub const fn hides_shorts(&self) -> bool {
+		self.config.hide_shorts
+	}
+
+	fn allows(&self, filter: &VideoFilter, video: &impl Filterable) -> bool {
+		filter.allows(video) && !self.watched.contains(video.video_id())
+	}
+}
+
+/// Hides the channel from every listing.
+pub fn block_channel(id: &str, name: &str) {
+	let blocklist_slice = expect_context::<FilterConfigCtx>().blocklist_slice;
+	let mut blocklist = blocklist_slice.0.get_untracked();
+	blocklist.block_channel(id, name);
+	blocklist_slice.1.set(blocklist);
+}
@@ -0,0 +1,110 @@
+use invidious::{HistoryEntry, LocalPlaylist, Video, WatchHistory};
+use leptos::{
+	spawn_local, RwSignal, SignalSet, SignalUpdate, SignalWithUntracked,
+};
+
+/// How many videos are looked up at once when filling in imported entries.
+const DETAILS_CHUNK_SIZE: usize = 10;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct HistoryCtx(pub RwSignal<WatchHistory>);
+
+impl HistoryCtx {
+	pub fn initialise(history: WatchHistory) -> Self {
+		Self(RwSignal::new(history))
+	}
+
+	pub fn add_video(&self, video: &Video) {
+		let mut dropped = vec![];
+		self.0.update(|history| {
+			dropped = history.add(HistoryEntry::new(video));
+		});
+		self.save_entry(&video.id);
+		spawn_local(async move {
+			for entry in dropped {
+				let _ = HistoryEntry::delete(&entry.id).await;
+			}
+		});
+	}
+
+	pub fn set_progress(&self, id: &str, progress: f64) {
+		self.0.update(|history| history.set_progress(id, progress));
+		self.save_entry(id);
+	}
+
+	pub fn remove(&self, id: &str) {
+		self.0.update(|history| history.remove(id));
+		let id = id.to_owned();
+		spawn_local(async move {
+			let _ = HistoryEntry::delete(&id).await;
+		});
+	}
+
+	pub fn clear(&self) {
+		self.0.update(WatchHistory::clear);
+		spawn_local(async {
+			let _ = WatchHistory::delete_all().await;
+		});
+	}
+
+	/// Swaps the whole history, rewriting the stored entries.
+	pub fn replace(&self, history: WatchHistory) {
+		let entries = history.entries.clone();
+		self.0.set(history);
+		spawn_local(async move {
+			let _ = WatchHistory::delete_all().await;
+			for entry in entries {
+				let _ = entry.save().await;
+			}
+		});
+	}
+
+	/// Adds the entries that are not in the history yet, then fetches the
+	/// titles and thumbnails of those imported without them.
+	///
+	/// Returns the number of entries added.
+	pub fn import(&self, entries: Vec<HistoryEntry>, server: String) -> usize {
+		let mut merged = (vec![], vec![]);
+		self.0.update(|history| merged = history.merge(entries));
+		let (added, dropped) = merged;
+		let count = added.len();
+
+		let ctx = *self;
+		spawn_local(async move {
+			for entry in dropped {
+				let _ = HistoryEntry::delete(&entry.id).await;
+			}
+			for entry in &added {
+				let _ = entry.save().await;
+			}
+
+			let missing: Vec<String> = added
+				.into_iter()
+				.filter(|entry| !entry.has_details())
+				.map(|entry| entry.id)
+				.collect();
+			for ids in missing.chunks(DETAILS_CHUNK_SIZE) {
+				let videos = LocalPlaylist::fetch_videos(&server, ids).await;
+				for video in videos.into_iter().flatten() {
+					ctx.0.update(|history| {
+						if let Some(entry) = history.get_mut(&video.id) {
+							entry.fill_details(&video);
+						}
+					});
+					ctx.save_entry(&video.id);
+				}
+			}
+		});
+		count
+	}
+
+	fn save_entry(&self, id: &str) {
+		if let Some(entry) =
+			self.0.with_untracked(|history| history.get(id).cloned())
+		{
+			spawn_local(async move {
+				let _ = entry.save().await;
+			});
+		}
+	}
+}
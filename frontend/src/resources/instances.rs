@@ -1,8 +1,9 @@
-use invidious::{fetch_instance_info, Instances};
-use leptos::Resource;
+use invidious::{fetch_instance_info, Instances, ServerPool};
+use leptos::{create_effect, expect_context, Resource, SignalGet};
 use rustytube_error::RustyTubeError;
 
 use super::save_resource;
+use crate::contexts::NetworkConfigCtx;
 
 static INSTANCES_KEY: &str = "instances";
 
@@ -23,3 +24,25 @@ async fn fetch_instances() -> Result<Instances, RustyTubeError> {
 	save_resource(INSTANCES_KEY, &instances).await?;
 	Ok(instances)
 }
+
+/// Rebuilds the failover `ServerPool` whenever the configured servers or the
+/// public instance list change.
+pub fn sync_server_pool() {
+	let network = expect_context::<NetworkConfigCtx>();
+	let instances = expect_context::<InstancesResource>().resource;
+
+	create_effect(move |_| {
+		let instances = instances
+			.get()
+			.and_then(|instances| instances.ok())
+			.unwrap_or_default();
+		let custom_servers =
+			network.custom_servers_slice.0.get().unwrap_or_default();
+		ServerPool::new(
+			&network.server_slice.0.get(),
+			&custom_servers,
+			&instances,
+		)
+		.install();
+	});
+}
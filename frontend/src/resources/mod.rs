@@ -1,29 +1,41 @@
+mod backup;
 mod captions;
 mod channel;
 mod comments;
 mod config;
+mod filters;
+mod history;
 mod instances;
 mod playlists;
 mod popular;
+mod positions;
 mod replies;
 mod search;
 mod sponsorblock;
+mod storage;
 mod r#struct;
 mod subscriptions;
+mod time_saved;
 mod trending;
 mod video;
 
+pub use backup::*;
 pub use captions::*;
 pub use channel::*;
 pub use comments::*;
 pub use config::*;
+pub use filters::*;
+pub use history::*;
 pub use instances::*;
 pub use playlists::*;
 pub use popular::*;
+pub use positions::*;
 pub use r#struct::*;
 pub use replies::*;
 pub use search::*;
 pub use sponsorblock::*;
+pub use storage::*;
 pub use subscriptions::*;
+pub use time_saved::*;
 pub use trending::*;
 pub use video::*;
@@ -1,24 +1,58 @@
-use gloo::storage::{LocalStorage, Storage};
-use invidious::LocalPlaylist;
-use leptos::{RwSignal, SignalGet, SignalUpdate};
+use config::HomepageCategory;
+use invidious::{
+	resolve_import, ImportReport, ImportStrategy, LocalPlaylist, Playlist,
+};
+use leptos::{
+	expect_context, spawn_local, Resource, RwSignal, Signal, SignalGet,
+	SignalGetUntracked, SignalSet, SignalSetter, SignalUpdate, SignalWith,
+	SignalWithUntracked,
+};
+use leptos_router::create_query_signal;
 use locales::RustyTubeLocale;
 use rustytube_error::RustyTubeError;
+use utils::{idb_clear, PLAYLISTS_STORE};
 
-use crate::contexts::{NetworkConfigCtx, RegionConfigCtx};
-
-static PLAYLISTS_KEY: &str = "playlists";
+use crate::contexts::{NetworkConfigCtx, RegionConfigCtx, UiConfigCtx};
 
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub struct PlaylistsCtx {
 	pub playlists: RwSignal<Vec<LocalPlaylist>>,
+	homepage: (Signal<HomepageCategory>, SignalSetter<HomepageCategory>),
 }
 
 impl PlaylistsCtx {
-	pub fn initialise() -> Self {
-		Self {
-			playlists: RwSignal::new(
-				get_playlists(PLAYLISTS_KEY).unwrap_or_default(),
-			),
+	pub fn initialise(playlists: Vec<LocalPlaylist>) -> Self {
+		let ctx = Self {
+			playlists: RwSignal::new(playlists),
+			homepage: expect_context::<UiConfigCtx>().homepage_slice,
+		};
+		ctx.check_homepage();
+		ctx
+	}
+
+	/// Points the homepage back at subscriptions if its playlist no longer
+	/// exists. Older configs point it at a playlist title, which is swapped
+	/// for the playlist's id.
+	pub fn check_homepage(&self) {
+		let HomepageCategory::Playlist(key) = self.homepage.0.get_untracked()
+		else {
+			return;
+		};
+		let homepage = self.playlists.with_untracked(|playlists| {
+			if playlists.iter().any(|playlist| playlist.id == key) {
+				return None;
+			}
+			Some(
+				playlists
+					.iter()
+					.find(|playlist| playlist.has_title(&key))
+					.map_or(HomepageCategory::Subscriptions, |playlist| {
+						HomepageCategory::Playlist(playlist.id.clone())
+					}),
+			)
+		});
+		if let Some(homepage) = homepage {
+			self.homepage.1.set(homepage);
 		}
 	}
 
@@ -32,16 +66,203 @@ impl PlaylistsCtx {
 		});
 		Ok(())
 	}
+
+	pub fn get(&self, id: &str) -> Option<LocalPlaylist> {
+		self.playlists.with(|playlists| {
+			playlists.iter().find(|playlist| playlist.id == id).cloned()
+		})
+	}
+
+	/// Whether a playlist other than `except` already uses the title.
+	fn title_taken(&self, title: &str, except: Option<&str>) -> bool {
+		self.playlists.with_untracked(|playlists| {
+			playlists.iter().any(|playlist| {
+				playlist.has_title(title)
+					&& Some(playlist.id.as_str()) != except
+			})
+		})
+	}
+
+	/// Creates an empty playlist, rejecting blank or already used titles.
+	///
+	/// Returns the id of the new playlist.
+	pub fn create(&self, title: &str) -> Result<String, RustyTubeError> {
+		let title = title.trim();
+		if title.is_empty() {
+			return Err(RustyTubeError::PlaylistParse);
+		}
+		if self.title_taken(title, None) {
+			return Err(RustyTubeError::PlaylistExists);
+		}
+		let playlist = LocalPlaylist::create(title)?;
+		let id = playlist.id.clone();
+		save_playlist(playlist.clone());
+		self.playlists.update(|playlists| playlists.push(playlist));
+		Ok(id)
+	}
+
+	pub fn add_video(&self, playlist_id: &str, id: &str) {
+		self.modify(playlist_id, |playlist| {
+			playlist.add_video(id);
+		});
+	}
+
+	pub fn remove_video(&self, playlist_id: &str, id: &str) {
+		self.modify(playlist_id, |playlist| playlist.remove_video(id));
+	}
+
+	pub fn move_video(&self, playlist_id: &str, from: usize, to: usize) {
+		self.modify(playlist_id, |playlist| playlist.move_video(from, to));
+	}
+
+	pub fn rename(
+		&self,
+		id: &str,
+		new_title: &str,
+	) -> Result<(), RustyTubeError> {
+		let new_title = new_title.trim();
+		if new_title.is_empty() {
+			return Err(RustyTubeError::PlaylistParse);
+		}
+		if self.title_taken(new_title, Some(id)) {
+			return Err(RustyTubeError::PlaylistExists);
+		}
+		self.modify(id, |playlist| playlist.rename(new_title));
+		Ok(())
+	}
+
+	pub fn delete(&self, id: &str) {
+		self.playlists.update(|playlists| {
+			playlists.retain(|existing| existing.id != id);
+		});
+		self.check_homepage();
+		let id = id.to_string();
+		spawn_local(async move {
+			let _ = LocalPlaylist::delete(&id).await;
+		});
+	}
+
+	/// Merges imported playlists into the existing ones and saves the
+	/// playlists that changed.
+	pub fn import(
+		&self,
+		imported: Vec<LocalPlaylist>,
+		strategy: ImportStrategy,
+	) -> ImportReport {
+		let import =
+			resolve_import(&self.playlists.get_untracked(), imported, strategy);
+		save_playlists(&import.changed);
+		self.playlists.set(import.playlists);
+		import.report
+	}
+
+	/// Swaps every playlist, rewriting the playlists store.
+	pub fn replace(&self, playlists: Vec<LocalPlaylist>) {
+		let saved = playlists.clone();
+		self.playlists.set(playlists);
+		spawn_local(async move {
+			let _ = idb_clear(PLAYLISTS_STORE).await;
+			let _ = LocalPlaylist::save_playlists(&saved).await;
+		});
+	}
+
+	fn modify(&self, id: &str, modify: impl FnOnce(&mut LocalPlaylist)) {
+		self.playlists.update(|playlists| {
+			if let Some(playlist) =
+				playlists.iter_mut().find(|playlist| playlist.id == id)
+			{
+				modify(playlist);
+				save_playlist(playlist.clone());
+			}
+		});
+	}
+}
+
+pub fn save_playlist(playlist: LocalPlaylist) {
+	spawn_local(async move {
+		let _ = playlist.save().await;
+	});
+}
+
+pub fn save_playlists(playlists: &[LocalPlaylist]) {
+	let playlists = playlists.to_vec();
+	spawn_local(async move {
+		let _ = LocalPlaylist::save_playlists(&playlists).await;
+	});
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct PlaylistVideosResourceArgs {
+	server: String,
+	list: Option<String>,
+	videos: Option<String>,
+	/// Videos of the local playlist in the `playlist` query, tracked so
+	/// edits are picked up.
+	local_videos: Option<Vec<String>>,
+}
+
+impl PlaylistVideosResourceArgs {
+	fn new() -> Self {
+		let local_videos = create_query_signal::<String>("playlist")
+			.0
+			.get()
+			.and_then(|id| expect_context::<PlaylistsCtx>().get(&id))
+			.map(|playlist| {
+				playlist.videos.into_iter().map(|video| video.id).collect()
+			});
+
+		Self {
+			server: expect_context::<NetworkConfigCtx>().server_slice.0.get(),
+			list: create_query_signal::<String>("list").0.get(),
+			videos: create_query_signal::<String>("videos").0.get(),
+			local_videos,
+		}
+	}
+}
+
+/// Video ids of the playlist active on the `/playlist` route: the local
+/// playlist in `playlist`, the ids listed in `videos` or the remote playlist
+/// in `list`.
+#[derive(Clone, Copy)]
+pub struct PlaylistVideosResource {
+	pub resource: Resource<
+		PlaylistVideosResourceArgs,
+		Result<Vec<String>, RustyTubeError>,
+	>,
 }
 
-fn get_playlists(
-	key: &'static str,
-) -> Result<Vec<LocalPlaylist>, RustyTubeError> {
-	Ok(LocalStorage::get::<Vec<LocalPlaylist>>(key)?)
+impl PlaylistVideosResource {
+	pub fn initialise() -> Self {
+		Self {
+			resource: Resource::local(
+				PlaylistVideosResourceArgs::new,
+				fetch_playlist_video_ids,
+			),
+		}
+	}
 }
 
-pub fn save_playlists(playlists: &mut Vec<LocalPlaylist>) {
-	for playlist in playlists {
-		playlist.save();
+async fn fetch_playlist_video_ids(
+	args: PlaylistVideosResourceArgs,
+) -> Result<Vec<String>, RustyTubeError> {
+	if let Some(videos) = args.local_videos {
+		Ok(videos)
+	} else if let Some(videos) = args.videos {
+		Ok(parse_playlist_videos(&videos))
+	} else if let Some(list) = args.list {
+		let playlist = Playlist::fetch_playlist(&args.server, &list).await?;
+		Ok(playlist.videos.into_iter().map(|video| video.id).collect())
+	} else {
+		Ok(vec![])
 	}
 }
+
+pub fn parse_playlist_videos(videos: &str) -> Vec<String> {
+	videos
+		.trim_matches(|c| matches!(c, '[' | ']' | '{' | '}'))
+		.split(',')
+		.map(str::trim)
+		.filter(|id| !id.is_empty())
+		.map(ToString::to_string)
+		.collect()
+}
@@ -0,0 +1,90 @@
+use leptos::{
+	spawn_local, RwSignal, SignalSet, SignalUpdate, SignalWithUntracked,
+};
+use rustytube_error::RustyTubeError;
+use serde::{Deserialize, Serialize};
+use utils::{idb_get, idb_put, POSITIONS_STORE};
+
+static POSITIONS_KEY: &str = "watch_positions";
+
+/// Oldest positions are dropped once this many videos have been stored.
+pub const MAX_WATCH_POSITIONS: usize = 500;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct WatchPositions {
+	/// Ordered from least to most recently updated.
+	pub positions: Vec<WatchPosition>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WatchPosition {
+	pub id: String,
+	pub time: f64,
+}
+
+impl WatchPositions {
+	pub async fn load() -> Result<Self, RustyTubeError> {
+		Ok(idb_get::<Self>(POSITIONS_STORE, POSITIONS_KEY)
+			.await?
+			.unwrap_or_default())
+	}
+
+	pub async fn save(&self) -> Result<(), RustyTubeError> {
+		idb_put(POSITIONS_STORE, POSITIONS_KEY, self).await
+	}
+
+	pub fn get(&self, id: &str) -> Option<f64> {
+		self.positions
+			.iter()
+			.find(|position| position.id.eq(id))
+			.map(|position| position.time)
+	}
+
+	pub fn set(&mut self, id: &str, time: f64) {
+		self.remove(id);
+		self.positions.push(WatchPosition { id: id.to_string(), time });
+		if self.positions.len() > MAX_WATCH_POSITIONS {
+			let overflow = self.positions.len() - MAX_WATCH_POSITIONS;
+			self.positions.drain(..overflow);
+		}
+	}
+
+	pub fn remove(&mut self, id: &str) {
+		self.positions.retain(|position| !position.id.eq(id));
+	}
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct PositionsCtx(pub RwSignal<WatchPositions>);
+
+impl PositionsCtx {
+	pub fn initialise(positions: WatchPositions) -> Self {
+		Self(RwSignal::new(positions))
+	}
+
+	pub fn get(&self, id: &str) -> Option<f64> {
+		self.0.with_untracked(|positions| positions.get(id))
+	}
+
+	pub fn set(&self, id: &str, time: f64) {
+		self.0.update(|positions| positions.set(id, time));
+		self.save();
+	}
+
+	pub fn remove(&self, id: &str) {
+		self.0.update(|positions| positions.remove(id));
+		self.save();
+	}
+
+	pub fn replace(&self, positions: WatchPositions) {
+		self.0.set(positions);
+		self.save();
+	}
+
+	fn save(&self) {
+		let positions = self.0.with_untracked(Clone::clone);
+		spawn_local(async move {
+			let _ = positions.save().await;
+		});
+	}
+}
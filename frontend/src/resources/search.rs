@@ -50,14 +50,15 @@ impl SearchResource {
 async fn fetch_search_results(
 	args: SearchResourceArgs,
 ) -> Result<Vec<SearchResult>, RustyTubeError> {
-	Ok(SearchResults::fetch_search_results(
+	let results = SearchResults::fetch_search_results(
 		&args.server,
 		&args.search_args,
 		1,
 		args.locale.to_invidious_lang(),
 	)
 	.await?
-	.items)
+	.items;
+	Ok(results)
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -1,22 +1,63 @@
+use config::{SegmentAction, SponsorBlockConfig};
 use leptos::{expect_context, Memo, Resource, RwSignal, SignalGet, SignalSet};
 use rustytube_error::RustyTubeError;
-use sponsorblock_rs::{Category, Query, Response, Segment};
+use sponsorblock_rs::{Action, Category, Query, Response, Segment};
 
 use crate::contexts::SponsorBlockConfigCtx;
 
+#[derive(Clone, PartialEq, Eq)]
+pub struct SponsorBlockResourceArgs {
+	id: String,
+	/// Empty when SponsorBlock is disabled. Highlights and chapters are
+	/// always fetched otherwise.
+	categories: Vec<Category>,
+}
+
+impl SponsorBlockResourceArgs {
+	fn new(id: String) -> Self {
+		let config = expect_context::<SponsorBlockConfigCtx>()
+			.sponsorblock_slice
+			.0
+			.get();
+		let categories = Category::ALL
+			.into_iter()
+			.filter(|category| {
+				config.enabled
+					&& match category {
+						Category::Highlight | Category::Chapter => true,
+						category => {
+							config.action(category.name())
+								!= SegmentAction::Disabled
+						}
+					}
+			})
+			.collect();
+		Self { id, categories }
+	}
+}
+
 #[allow(clippy::type_complexity)]
 #[derive(Clone, Copy)]
 pub struct SponsorBlockResource {
 	pub resource: RwSignal<
-		Option<Resource<String, Result<Option<Response>, RustyTubeError>>>,
+		Option<
+			Resource<
+				SponsorBlockResourceArgs,
+				Result<Option<Response>, RustyTubeError>,
+			>,
+		>,
 	>,
 }
 
 impl SponsorBlockResource {
 	pub fn set_video(&self, video_id: Memo<Option<String>>) {
 		self.resource.set(Some(Resource::local(
-			move || video_id.get().unwrap_or_default(),
-			move |id: String| fetch_sponsorblock_segments(id),
+			move || {
+				SponsorBlockResourceArgs::new(
+					video_id.get().unwrap_or_default(),
+				)
+			},
+			fetch_sponsorblock_segments,
 		)));
 	}
 
@@ -27,31 +68,87 @@ impl SponsorBlockResource {
 	pub fn get_segments(&self) -> Option<Vec<Segment>> {
 		Some(self.resource.get()?.get()?.ok()??.segments)
 	}
+
+	/// The chapters of the video, in order.
+	pub fn get_chapters(&self) -> Vec<Segment> {
+		let mut chapters: Vec<Segment> = self
+			.get_segments()
+			.unwrap_or_default()
+			.into_iter()
+			.filter(|segment| segment.action == Action::Chapter.name())
+			.collect();
+		chapters.sort_by(|a, b| a.timeframe.0.total_cmp(&b.timeframe.0));
+		chapters
+	}
+
+	pub fn get_highlight(&self) -> Option<Segment> {
+		self.get_segments()?
+			.into_iter()
+			.find(|segment| segment.action == Action::PointOfInterest.name())
+	}
+}
+
+/// The innermost chapter playing at `time`. Chapters may be nested, in which
+/// case the one that started last is playing.
+pub fn chapter_at(chapters: &[Segment], time: f64) -> Option<&Segment> {
+	chapters.iter().rev().find(|chapter| {
+		let (start, end) = chapter.timeframe;
+		start <= time && time < end
+	})
+}
+
+/// The action taken for `segment`. Segments submitted as mute segments are
+/// muted rather than skipped.
+pub fn segment_action(
+	config: &SponsorBlockConfig,
+	segment: &Segment,
+) -> SegmentAction {
+	match config.action(&segment.category) {
+		SegmentAction::AutoSkip | SegmentAction::ManualSkip
+			if segment.action == Action::Mute.name() =>
+		{
+			SegmentAction::Mute
+		}
+		action => action,
+	}
+}
+
+/// The colours the SponsorBlock extension uses for each category.
+pub fn segment_colour(category: &str) -> &'static str {
+	match category {
+		"sponsor" => "#00d400",
+		"selfpromo" => "#ffff00",
+		"interaction" => "#cc00ff",
+		"intro" => "#00ffff",
+		"outro" => "#0202ed",
+		"preview" => "#008fd6",
+		"music_offtopic" => "#ff9900",
+		"filler" => "#7300ff",
+		"poi_highlight" => "#ff1684",
+		_ => "#ffffff",
+	}
 }
 
 async fn fetch_sponsorblock_segments(
-	id: String,
+	args: SponsorBlockResourceArgs,
 ) -> Result<Option<Response>, RustyTubeError> {
-	let categories = move || {
-		let ctx = expect_context::<SponsorBlockConfigCtx>();
-
-		let mut vec = vec![];
-		ctx.skip_sponsors.0.get().then(|| vec.push(Category::Sponsor));
-		ctx.skip_selfpromos.0.get().then(|| vec.push(Category::SelfPromotion));
-		ctx.skip_intros.0.get().then(|| vec.push(Category::Intro));
-		ctx.skip_outros.0.get().then(|| vec.push(Category::Outro));
-		ctx.skip_interactions.0.get().then(|| vec.push(Category::Interaction));
-		ctx.skip_previews.0.get().then(|| vec.push(Category::Preview));
-		ctx.skip_irrelevant_music
-			.0
-			.get()
-			.then(|| vec.push(Category::OffTopicMusic));
-		ctx.skip_filler.0.get().then(|| vec.push(Category::Filler));
-		Some(vec)
-	};
-
-	Ok(Query::create(id, None, categories(), None, None)
-		.send_query()
-		.await
-		.expect("Error fetching sponsorblock data."))
+	if args.id.is_empty() || args.categories.is_empty() {
+		return Ok(None);
+	}
+
+	Query::create(
+		args.id,
+		None,
+		Some(args.categories),
+		Some(vec![
+			Action::Skip,
+			Action::Mute,
+			Action::PointOfInterest,
+			Action::Chapter,
+		]),
+		None,
+	)
+	.send_query()
+	.await
+	.map_err(|err| RustyTubeError::Network(err.to_string()))
 }
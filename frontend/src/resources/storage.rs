@@ -0,0 +1,43 @@
+use config::Config;
+use invidious::{LocalPlaylist, Subscriptions, WatchHistory};
+use rustytube_error::RustyTubeError;
+
+use super::{load_subs, TimeSavedCtx, WatchPositions};
+
+/// Everything persisted in `IndexedDB`, loaded once before the app mounts.
+pub struct StoredData {
+	pub config: Config,
+	pub subscriptions: Subscriptions,
+	pub playlists: Vec<LocalPlaylist>,
+	pub history: WatchHistory,
+	pub positions: WatchPositions,
+	pub time_saved: f64,
+}
+
+impl StoredData {
+	/// The app is not mounted when this fails, e.g. while another tab blocks
+	/// a database upgrade, as it would save its defaults over the stored
+	/// data.
+	///
+	/// # Errors
+	///
+	/// - `IndexedDB` errors.
+	/// - Serde or Toml deserialisation errors.
+	pub async fn load() -> Result<Self, RustyTubeError> {
+		let config = Config::load().await?;
+		let subscriptions = load_subs().await?;
+		let playlists = LocalPlaylist::load_local_playlists().await?;
+		let history = WatchHistory::load().await?;
+		let positions = WatchPositions::load().await?;
+		let time_saved = TimeSavedCtx::load().await?;
+
+		Ok(Self {
+			config,
+			subscriptions,
+			playlists,
+			history,
+			positions,
+			time_saved,
+		})
+	}
+}
@@ -1,9 +1,9 @@
 use std::path::Path;
 
-use gloo::storage::{LocalStorage, Storage};
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 use tauri_sys::fs::{read_text_file, write_text_file, BaseDirectory};
+use utils::{idb_get, idb_put, RESOURCES_STORE};
 
 pub async fn load_resource<T>(key: impl AsRef<str>) -> Result<T, RustyTubeError>
 where
@@ -17,7 +17,9 @@ where
 		.await?;
 		ron::from_str(&text_data)?
 	} else {
-		LocalStorage::get::<T>(key)?
+		idb_get::<T>(RESOURCES_STORE, key.as_ref())
+			.await?
+			.ok_or(RustyTubeError::NotFound)?
 	};
 	Ok(data)
 }
@@ -29,7 +31,7 @@ pub async fn save_resource<T>(
 where
 	T: Serialize,
 {
-	gloo::storage::LocalStorage::set(key.as_ref(), &data)?;
+	idb_put(RESOURCES_STORE, key.as_ref(), &data).await?;
 	if cfg!(feature = "tauri") {
 		write_text_file(
 			Path::new(&format!("RustyTube/{}.ron", key.as_ref())),
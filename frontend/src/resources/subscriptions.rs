@@ -1,23 +1,25 @@
-use gloo::storage::{LocalStorage, Storage};
 use invidious::{
-	SubsThumbsResult, SubsVideosResult, Subscription, Subscriptions,
+	CachedChannelFeed, SubsThumbsResult, Subscription, Subscriptions,
+	SubscriptionsFeed, FEED_MAX_AGE, SUBS_KEY,
 };
 use leptos::{
-	expect_context, Resource, RwSignal, SignalGet, SignalSet, SignalUpdate,
+	expect_context, spawn_local, Resource, RwSignal, SignalGet,
+	SignalGetUntracked, SignalSet, SignalUpdate, SignalWith,
+	SignalWithUntracked,
 };
 use locales::RustyTubeLocale;
 use rustytube_error::RustyTubeError;
+use utils::{get_unix_time, idb_get, idb_put, is_tauri, SUBSCRIPTIONS_STORE};
 
+use super::FeedFilter;
 use crate::contexts::{NetworkConfigCtx, RegionConfigCtx};
 
-static SUBSCRIPTIONS_KEY: &str = "subscriptions";
-
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub struct SubscriptionsCtx(pub RwSignal<Subscriptions>);
 
 impl SubscriptionsCtx {
-	pub fn initialise() -> Self {
-		Self(RwSignal::new(get_subs(SUBSCRIPTIONS_KEY).unwrap_or_default()))
+	pub fn initialise(subscriptions: Subscriptions) -> Self {
+		Self(RwSignal::new(subscriptions))
 	}
 
 	pub async fn add_subscription(
@@ -30,11 +32,7 @@ impl SubscriptionsCtx {
 			if !subs.channels.iter().any(|sub| sub.id.eq_ignore_ascii_case(id))
 			{
 				subs.channels.push(sub);
-				subs.channels.sort_by(|a, b| {
-					a.name.to_lowercase().cmp(&b.name.to_lowercase())
-				});
-				subs.channels
-					.dedup_by(|a, b| a.name.eq_ignore_ascii_case(&b.name));
+				sort_subs(subs);
 				save_subs(subs);
 			}
 		});
@@ -46,66 +44,227 @@ impl SubscriptionsCtx {
 		id: &str,
 	) -> Result<(), RustyTubeError> {
 		self.0.update(|subs| {
-			subs.channels
-				.retain(|channel| !channel.id.eq_ignore_ascii_case(id));
-			subs.channels.sort_by(|a, b| {
-				a.name.to_lowercase().cmp(&b.name.to_lowercase())
-			});
-			subs.channels.dedup_by(|a, b| a.name.eq_ignore_ascii_case(&b.name));
+			subs.remove_channel(id);
 			save_subs(subs);
 		});
+		let id = id.to_owned();
+		spawn_local(async move {
+			let _ = CachedChannelFeed::delete(&id).await;
+		});
 		Ok(())
 	}
+
+	/// # Errors
+	///
+	/// - Empty name, or a group with the same name exists.
+	pub fn add_group(&self, name: &str) -> Result<(), RustyTubeError> {
+		self.modify(|subs| subs.add_group(name))
+	}
+
+	/// # Errors
+	///
+	/// - Empty name, or a group with the new name exists.
+	pub fn rename_group(
+		&self,
+		name: &str,
+		new_name: &str,
+	) -> Result<(), RustyTubeError> {
+		self.modify(|subs| subs.rename_group(name, new_name))
+	}
+
+	pub fn delete_group(&self, name: &str) {
+		self.0.update(|subs| {
+			subs.remove_group(name);
+			save_subs(subs);
+		});
+	}
+
+	pub fn toggle_group_channel(&self, name: &str, id: &str) {
+		self.0.update(|subs| {
+			subs.toggle_group_channel(name, id);
+			save_subs(subs);
+		});
+	}
+
+	/// Applies a change that can fail, saving only if it succeeds.
+	fn modify(
+		&self,
+		change: impl FnOnce(&mut Subscriptions) -> Result<(), RustyTubeError>,
+	) -> Result<(), RustyTubeError> {
+		let mut subs = self.0.get_untracked();
+		change(&mut subs)?;
+		save_subs(&subs);
+		self.0.set(subs);
+		Ok(())
+	}
+
+	/// Adds the channels that are not subscribed to yet and merges groups
+	/// by name, then looks up the names of channels imported by id only.
+	///
+	/// Returns the number of channels added.
+	pub fn import(&self, imported: Subscriptions, server: String) -> usize {
+		let mut added = 0;
+		self.0.update(|subs| {
+			for channel in imported.channels {
+				if !subs.channels.iter().any(|sub| sub.id == channel.id) {
+					subs.channels.push(channel);
+					added += 1;
+				}
+			}
+			for group in imported.groups {
+				let _ = subs.add_group(&group.name);
+				for id in group.channels {
+					let is_member = subs
+						.group(&group.name)
+						.is_some_and(|existing| existing.contains(&id));
+					if !is_member {
+						subs.toggle_group_channel(&group.name, &id);
+					}
+				}
+			}
+			sort_subs(subs);
+			save_subs(subs);
+		});
+
+		let ctx = *self;
+		if ctx.0.with_untracked(|subs| {
+			subs.channels.iter().any(|channel| channel.name.is_empty())
+		}) {
+			spawn_local(async move {
+				let mut named = ctx.0.get_untracked();
+				named.fill_names(&server).await;
+				ctx.0.update(|subs| {
+					for channel in subs
+						.channels
+						.iter_mut()
+						.filter(|channel| channel.name.is_empty())
+					{
+						if let Some(found) = named
+							.channels
+							.iter()
+							.find(|named| named.id == channel.id)
+						{
+							channel.name.clone_from(&found.name);
+						}
+					}
+					sort_subs(subs);
+					save_subs(subs);
+				});
+			});
+		}
+		added
+	}
+}
+
+fn sort_subs(subs: &mut Subscriptions) {
+	subs.channels
+		.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
 }
 
-pub fn get_subs(key: &'static str) -> Result<Subscriptions, RustyTubeError> {
-	Ok(LocalStorage::get::<Subscriptions>(key)?)
+pub async fn load_subs() -> Result<Subscriptions, RustyTubeError> {
+	Ok(idb_get::<Subscriptions>(SUBSCRIPTIONS_STORE, SUBS_KEY)
+		.await?
+		.unwrap_or_default())
 }
 
-pub fn save_subs(subs: &mut Subscriptions) -> Result<(), RustyTubeError> {
-	LocalStorage::set(SUBSCRIPTIONS_KEY, subs)?;
-	Ok(())
+pub fn save_subs(subs: &Subscriptions) {
+	let subs = subs.clone();
+	spawn_local(async move {
+		let _ = idb_put(SUBSCRIPTIONS_STORE, SUBS_KEY, &subs).await;
+	});
 }
 
 #[derive(Clone, PartialEq, Eq)]
 pub struct SubscriptionsVideosResourceArgs {
 	server: String,
+	rss: bool,
+	auto_fetch: bool,
+	refreshed: u64,
 	locale: RustyTubeLocale,
 	subscriptions: Subscriptions,
+	filter: FeedFilter,
 }
 
 impl SubscriptionsVideosResourceArgs {
-	pub fn new(subscriptions: SubscriptionsCtx) -> Self {
+	pub fn new(
+		subscriptions: SubscriptionsCtx,
+		group: RwSignal<Option<String>>,
+		refreshed: RwSignal<u64>,
+	) -> Self {
+		let network = expect_context::<NetworkConfigCtx>();
+		let group = group.get();
 		Self {
-			server: expect_context::<NetworkConfigCtx>().server_slice.0.get(),
+			server: network.server_slice.0.get(),
+			rss: network.fetch_rss_slice.0.get() && is_tauri(),
+			auto_fetch: network.auto_fetch_subs_slice.0.get_untracked(),
+			refreshed: refreshed.get(),
 			locale: expect_context::<RegionConfigCtx>().locale_slice.0.get(),
-			subscriptions: subscriptions.0.get(),
+			subscriptions: subscriptions
+				.0
+				.with(|subs| subs.in_group(group.as_deref())),
+			filter: FeedFilter::from_context(),
 		}
 	}
 }
 
 #[derive(Copy, Clone)]
 pub struct SubscriptionsVideosResource {
-	pub resource: Resource<SubscriptionsVideosResourceArgs, SubsVideosResult>,
+	pub resource: Resource<SubscriptionsVideosResourceArgs, SubscriptionsFeed>,
+	/// The group whose feed is shown, or `None` for every subscription.
+	pub group: RwSignal<Option<String>>,
+	/// Unix timestamp of the last refresh requested by the user.
+	pub refreshed: RwSignal<u64>,
 }
 
 impl SubscriptionsVideosResource {
 	pub fn initialise(subscriptions: SubscriptionsCtx) -> Self {
+		let group = RwSignal::new(None);
+		let refreshed = RwSignal::new(0);
 		Self {
 			resource: Resource::local(
-				move || SubscriptionsVideosResourceArgs::new(subscriptions),
+				move || {
+					SubscriptionsVideosResourceArgs::new(
+						subscriptions,
+						group,
+						refreshed,
+					)
+				},
 				fetch_subs_videos,
 			),
+			group,
+			refreshed,
 		}
 	}
+
+	/// Fetches every channel not fetched since now.
+	pub fn refresh(&self) {
+		self.refreshed.set(get_unix_time());
+	}
 }
 
+/// Only the cached feed is shown until the user refreshes, unless feeds are
+/// fetched automatically. Channels fetched since the last refresh, or within
+/// `FEED_MAX_AGE`, are served from the cache.
 async fn fetch_subs_videos(
 	args: SubscriptionsVideosResourceArgs,
-) -> SubsVideosResult {
-	args.subscriptions
-		.fetch_videos(&args.server, false, args.locale.to_invidious_lang())
-		.await
+) -> SubscriptionsFeed {
+	let mut feed = if !args.auto_fetch && args.refreshed == 0 {
+		args.subscriptions.cached_feed().await
+	} else {
+		let stale_before =
+			get_unix_time().saturating_sub(FEED_MAX_AGE).max(args.refreshed);
+		args.subscriptions
+			.refresh_feed(
+				&args.server,
+				args.rss,
+				args.rss && args.filter.hides_shorts(),
+				args.locale.to_invidious_lang(),
+				stale_before,
+			)
+			.await
+	};
+	args.filter.apply_feed(&mut feed);
+	feed
 }
 
 static SUBSCRIPTIONS_THUMBNAILS_KEY: &str = "subscriptions_thumbs";
@@ -120,7 +279,7 @@ impl SubscriptionsThumbnailsResourceArgs {
 	pub fn new(subscriptions: SubscriptionsCtx) -> Self {
 		Self {
 			server: expect_context::<NetworkConfigCtx>().server_slice.0.get(),
-			subscriptions: subscriptions.0.get(),
+			subscriptions: subscriptions.0.with(|subs| subs.in_group(None)),
 		}
 	}
 }
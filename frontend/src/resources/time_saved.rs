@@ -0,0 +1,31 @@
+use leptos::{spawn_local, RwSignal, SignalGetUntracked, SignalUpdate};
+use rustytube_error::RustyTubeError;
+use utils::{idb_get, idb_put, STATS_STORE};
+
+static TIME_SAVED_KEY: &str = "time_saved";
+
+/// Seconds of video skipped by SponsorBlock, over all videos. Kept out of the
+/// config, as it changes with every skip.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct TimeSavedCtx(pub RwSignal<f64>);
+
+impl TimeSavedCtx {
+	pub fn initialise(seconds: f64) -> Self {
+		Self(RwSignal::new(seconds))
+	}
+
+	pub async fn load() -> Result<f64, RustyTubeError> {
+		Ok(idb_get::<f64>(STATS_STORE, TIME_SAVED_KEY)
+			.await?
+			.unwrap_or_default())
+	}
+
+	/// Negative `seconds` take back the time of an undone skip.
+	pub fn add(&self, seconds: f64) {
+		self.0.update(|saved| *saved = (*saved + seconds).max(0f64));
+		let saved = self.0.get_untracked();
+		spawn_local(async move {
+			let _ = idb_put(STATS_STORE, TIME_SAVED_KEY, &saved).await;
+		});
+	}
+}
@@ -1,10 +1,13 @@
 use invidious::Video;
-use leptos::{expect_context, Resource, SignalGet};
+use leptos::{expect_context, Resource, SignalGet, SignalGetUntracked};
 use leptos_router::create_query_signal;
 use locales::RustyTubeLocale;
 use rustytube_error::RustyTubeError;
 
-use crate::contexts::{NetworkConfigCtx, RegionConfigCtx};
+use crate::{
+	contexts::{NetworkConfigCtx, PrivacyConfigCtx, RegionConfigCtx},
+	resources::HistoryCtx,
+};
 
 static POPULAR_KEY: &str = "popular_videos";
 
@@ -13,6 +16,7 @@ pub struct VideoResourceArgs {
 	server: String,
 	locale: RustyTubeLocale,
 	id: String,
+	history: Option<HistoryCtx>,
 }
 
 impl VideoResourceArgs {
@@ -21,6 +25,11 @@ impl VideoResourceArgs {
 			server: expect_context::<NetworkConfigCtx>().server_slice.0.get(),
 			locale: expect_context::<RegionConfigCtx>().locale_slice.0.get(),
 			id: create_query_signal::<String>("id").0.get().unwrap_or_default(),
+			history: expect_context::<PrivacyConfigCtx>()
+				.keep_history_slice
+				.0
+				.get_untracked()
+				.then(expect_context::<HistoryCtx>),
 		}
 	}
 }
@@ -37,6 +46,14 @@ impl VideoResource {
 }
 
 async fn fetch_video(args: VideoResourceArgs) -> Result<Video, RustyTubeError> {
-	Video::fetch_video(&args.server, &args.id, args.locale.to_invidious_lang())
-		.await
+	let video = Video::fetch_video(
+		&args.server,
+		&args.id,
+		args.locale.to_invidious_lang(),
+	)
+	.await?;
+	if let Some(history) = args.history {
+		history.add_video(&video);
+	}
+	Ok(video)
 }
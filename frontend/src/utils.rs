@@ -1,6 +1,20 @@
-use gloo::console::debug;
-use leptos::{expect_context, request_animation_frame, window, SignalGet};
+use std::path::Path;
+
+use gloo::{
+	console::debug,
+	file::{Blob, ObjectUrl},
+	timers::callback::Timeout,
+};
+use leptos::{
+	expect_context, html, request_animation_frame, window, SignalGet,
+};
 use leptos_router::{create_query_signal, NavigateOptions};
+use rustytube_error::RustyTubeError;
+use tauri_sys::{
+	dialog::FileDialogBuilder,
+	fs::{write_text_file, BaseDirectory},
+};
+use utils::is_tauri;
 
 use crate::contexts::RegionConfigCtx;
 
@@ -29,3 +43,28 @@ pub fn go_to(page: impl AsRef<str>) {
 		navigate(&page, NavigateOptions::default());
 	});
 }
+
+/// Saves `contents` as a file the user picks. The desktop app asks for a
+/// location with the native save dialog; browsers download the file instead.
+pub async fn save_file(
+	name: &str,
+	mime: &str,
+	contents: &str,
+) -> Result<(), RustyTubeError> {
+	if is_tauri() {
+		let path = FileDialogBuilder::new()
+			.set_default_path(Path::new(name))
+			.save()
+			.await?;
+		if let Some(path) = path {
+			write_text_file(&path, contents, BaseDirectory::Download).await?;
+		}
+	} else {
+		let url = ObjectUrl::from(Blob::new_with_options(contents, Some(mime)));
+		let anchor =
+			html::a().attr("href", url.to_string()).attr("download", name);
+		anchor.click();
+		Timeout::new(1000, move || drop(url)).forget();
+	}
+	Ok(())
+}
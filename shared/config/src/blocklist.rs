@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Filterable;
+
+/// Channels and title keywords hidden from every listing.
+#[derive(Clone, Default, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[serde(default)]
+pub struct BlocklistConfig {
+	pub channels: Vec<BlockedChannel>,
+	/// Matched case-insensitively against video titles.
+	pub keywords: Vec<String>,
+}
+
+#[derive(Clone, Default, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct BlockedChannel {
+	pub id: String,
+	/// Only used for display, as channels are matched by id.
+	pub name: String,
+}
+
+impl BlocklistConfig {
+	pub fn blocks_channel(&self, id: &str) -> bool {
+		self.channels.iter().any(|channel| channel.id.eq_ignore_ascii_case(id))
+	}
+
+	pub fn blocks(&self, video: &impl Filterable) -> bool {
+		if self.blocks_channel(video.author_id()) {
+			return true;
+		}
+		let title = video.title().to_lowercase();
+		self.keywords
+			.iter()
+			.any(|keyword| title.contains(&keyword.to_lowercase()))
+	}
+
+	pub fn block_channel(&mut self, id: &str, name: &str) {
+		if !id.is_empty() && !self.blocks_channel(id) {
+			self.channels.push(BlockedChannel {
+				id: id.to_owned(),
+				name: name.to_owned(),
+			});
+		}
+	}
+
+	pub fn unblock_channel(&mut self, id: &str) {
+		self.channels.retain(|channel| !channel.id.eq_ignore_ascii_case(id));
+	}
+
+	/// Returns false if the keyword is empty or already blocked.
+	pub fn add_keyword(&mut self, keyword: &str) -> bool {
+		let keyword = keyword.trim();
+		if keyword.is_empty()
+			|| self
+				.keywords
+				.iter()
+				.any(|existing| existing.eq_ignore_ascii_case(keyword))
+		{
+			return false;
+		}
+		self.keywords.push(keyword.to_owned());
+		true
+	}
+
+	pub fn remove_keyword(&mut self, keyword: &str) {
+		self.keywords.retain(|existing| existing != keyword);
+	}
+}
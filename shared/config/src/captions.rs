@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+/// How captions look, and which languages are shown.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[serde(default)]
+pub struct CaptionsConfig {
+	/// Language code of the last chosen captions, or `None` when captions
+	/// were turned off.
+	pub language: Option<String>,
+	/// Language of the captions shown alongside the first ones, e.g. a
+	/// translation.
+	pub secondary_language: Option<String>,
+	/// Percent of the default size.
+	pub font_size: u16,
+	/// CSS hex colour of the text.
+	pub colour: String,
+	/// Percent opacity of the black box behind the text.
+	pub background_opacity: u8,
+	pub position: CaptionsPosition,
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptionsPosition {
+	Top,
+	Bottom,
+}
+
+impl Default for CaptionsConfig {
+	fn default() -> Self {
+		Self {
+			language: None,
+			secondary_language: None,
+			font_size: 100,
+			colour: String::from("#ffffff"),
+			background_opacity: 75,
+			position: CaptionsPosition::Bottom,
+		}
+	}
+}
+
+impl CaptionsConfig {
+	/// The background as a CSS colour.
+	pub fn background(&self) -> String {
+		let alpha = f32::from(self.background_opacity.min(100)) / 100f32;
+		format!("rgba(0, 0, 0, {alpha})")
+	}
+}
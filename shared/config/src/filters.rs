@@ -0,0 +1,249 @@
+use invidious::{CommonVideo, PopularItem, VideoShort};
+use regex::{Regex, RegexBuilder};
+use rustytube_error::RustyTubeError;
+use serde::{Deserialize, Serialize};
+
+use crate::BlocklistConfig;
+
+/// Which videos are hidden from the subscriptions feed, trending, popular
+/// and search results.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[serde(default)]
+pub struct FilterConfig {
+	pub hide_live: bool,
+	pub hide_upcoming: bool,
+	pub hide_premium: bool,
+	/// The subscriptions feed hides shorts by id, as RSS feeds do not give
+	/// video lengths. Elsewhere they are recognised by length.
+	pub hide_shorts: bool,
+	/// Seconds. Shorts can be up to three minutes long. Videos of unknown
+	/// length are never counted as shorts.
+	pub shorts_max_length: u32,
+	pub hide_watched: bool,
+	pub rules: Vec<FilterRule>,
+}
+
+/// Hides videos whose title or channel name matches the pattern.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct FilterRule {
+	pub field: FilterField,
+	pub kind: FilterKind,
+	pub pattern: String,
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterField {
+	Title,
+	Author,
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterKind {
+	/// Case-insensitive substring match.
+	Keyword,
+	Regex,
+}
+
+/// The parts of a video the filters look at.
+pub trait Filterable {
+	fn video_id(&self) -> &str;
+	fn title(&self) -> &str;
+	fn author(&self) -> &str;
+	fn author_id(&self) -> &str;
+	/// Seconds, or 0 when unknown.
+	fn length(&self) -> u32;
+	fn is_live(&self) -> bool;
+	fn is_upcoming(&self) -> bool;
+	fn is_premium(&self) -> bool;
+}
+
+/// A `FilterConfig` with its rules compiled, and the blocklist.
+pub struct VideoFilter<'a> {
+	config: &'a FilterConfig,
+	blocklist: &'a BlocklistConfig,
+	rules: Vec<(FilterField, Regex)>,
+}
+
+impl Default for FilterConfig {
+	fn default() -> Self {
+		Self {
+			hide_live: false,
+			hide_upcoming: false,
+			hide_premium: false,
+			hide_shorts: false,
+			shorts_max_length: 180,
+			hide_watched: false,
+			rules: Vec::new(),
+		}
+	}
+}
+
+impl FilterRule {
+	/// # Errors
+	///
+	/// - Empty pattern or invalid regex.
+	pub fn new(
+		field: FilterField,
+		kind: FilterKind,
+		pattern: &str,
+	) -> Result<Self, RustyTubeError> {
+		let rule = Self { field, kind, pattern: pattern.trim().to_owned() };
+		if rule.pattern.is_empty() {
+			return Err(RustyTubeError::FilterRule(String::from(
+				"Empty pattern.",
+			)));
+		}
+		rule.regex()?;
+		Ok(rule)
+	}
+
+	fn regex(&self) -> Result<Regex, RustyTubeError> {
+		let pattern = match self.kind {
+			FilterKind::Keyword => regex::escape(&self.pattern),
+			FilterKind::Regex => self.pattern.clone(),
+		};
+		RegexBuilder::new(&pattern)
+			.case_insensitive(self.kind == FilterKind::Keyword)
+			.build()
+			.map_err(|err| RustyTubeError::FilterRule(err.to_string()))
+	}
+}
+
+impl<'a> VideoFilter<'a> {
+	/// Compiles the rules. Invalid ones are skipped, as they are checked
+	/// when added.
+	pub fn new(
+		config: &'a FilterConfig,
+		blocklist: &'a BlocklistConfig,
+	) -> Self {
+		let rules = config
+			.rules
+			.iter()
+			.filter_map(|rule| Some((rule.field, rule.regex().ok()?)))
+			.collect();
+		Self { config, blocklist, rules }
+	}
+
+	pub fn allows(&self, video: &impl Filterable) -> bool {
+		let config = self.config;
+		let is_short =
+			video.length() > 0 && video.length() <= config.shorts_max_length;
+
+		!(self.blocklist.blocks(video)
+			|| config.hide_live && video.is_live()
+			|| config.hide_upcoming && video.is_upcoming()
+			|| config.hide_premium && video.is_premium()
+			|| config.hide_shorts && is_short
+			|| self.rules.iter().any(|(field, regex)| match field {
+				FilterField::Title => regex.is_match(video.title()),
+				FilterField::Author => regex.is_match(video.author()),
+			}))
+	}
+}
+
+impl Filterable for CommonVideo {
+	fn video_id(&self) -> &str {
+		&self.id
+	}
+
+	fn title(&self) -> &str {
+		&self.title
+	}
+
+	fn author(&self) -> &str {
+		&self.author
+	}
+
+	fn author_id(&self) -> &str {
+		&self.author_id
+	}
+
+	fn length(&self) -> u32 {
+		self.length
+	}
+
+	fn is_live(&self) -> bool {
+		self.live
+	}
+
+	fn is_upcoming(&self) -> bool {
+		self.upcoming
+	}
+
+	fn is_premium(&self) -> bool {
+		self.premium
+	}
+}
+
+impl Filterable for PopularItem {
+	fn video_id(&self) -> &str {
+		&self.id
+	}
+
+	fn title(&self) -> &str {
+		&self.title
+	}
+
+	fn author(&self) -> &str {
+		&self.author
+	}
+
+	fn author_id(&self) -> &str {
+		&self.author_id
+	}
+
+	fn length(&self) -> u32 {
+		self.length
+	}
+
+	fn is_live(&self) -> bool {
+		false
+	}
+
+	fn is_upcoming(&self) -> bool {
+		false
+	}
+
+	fn is_premium(&self) -> bool {
+		false
+	}
+}
+
+impl Filterable for VideoShort {
+	fn video_id(&self) -> &str {
+		&self.id
+	}
+
+	fn title(&self) -> &str {
+		&self.title
+	}
+
+	fn author(&self) -> &str {
+		&self.author
+	}
+
+	fn author_id(&self) -> &str {
+		&self.author_id
+	}
+
+	fn length(&self) -> u32 {
+		self.length
+	}
+
+	fn is_live(&self) -> bool {
+		false
+	}
+
+	fn is_upcoming(&self) -> bool {
+		false
+	}
+
+	fn is_premium(&self) -> bool {
+		false
+	}
+}
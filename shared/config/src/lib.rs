@@ -1,11 +1,19 @@
+mod blocklist;
+mod captions;
+mod filters;
+mod sponsorblock;
 mod tests;
 
-use gloo::storage::{LocalStorage, Storage};
+pub use blocklist::*;
+pub use captions::*;
+pub use filters::*;
+pub use sponsorblock::*;
+
 use invidious::{AudioQuality, VideoQuality};
 use locales::RustyTubeLocale;
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
-use utils::save_to_browser_storage;
+use utils::{idb_get, idb_put, CONFIG_STORE};
 
 use crate::RememberPosition::VideosOnly;
 
@@ -34,6 +42,26 @@ pub struct PlayerConfig {
 	pub default_audio_quality: AudioQuality,
 	pub remember_position: RememberPosition,
 	pub volume: f64,
+	#[serde(default)]
+	pub keybindings: KeybindingsConfig,
+}
+
+/// Keys are matched against `KeyboardEvent.key`, with `Space` standing in for
+/// the space bar. Digits always jump to the matching tenth of the video.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[serde(default)]
+pub struct KeybindingsConfig {
+	pub toggle_playback: Vec<String>,
+	pub seek_backward: Vec<String>,
+	pub seek_forward: Vec<String>,
+	pub volume_up: Vec<String>,
+	pub volume_down: Vec<String>,
+	pub fullscreen: Vec<String>,
+	pub full_window: Vec<String>,
+	pub mute: Vec<String>,
+	pub cycle_captions: Vec<String>,
+	pub show_shortcuts: Vec<String>,
 }
 
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
@@ -49,21 +77,6 @@ pub struct RegionConfig {
 	pub trending_region: isocountry::CountryCode,
 }
 
-#[allow(clippy::struct_excessive_bools)]
-#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
-pub struct SponsorBlockConfig {
-	pub enabled: bool,
-	pub skip_sponsors: bool,
-	pub skip_selfpromos: bool,
-	pub skip_interactions: bool,
-	pub skip_intros: bool,
-	pub skip_outros: bool,
-	pub skip_previews: bool,
-	pub skip_irrelevant_music: bool,
-	pub skip_filler: bool,
-}
-
 #[derive(Clone, Default, Deserialize, Serialize, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct Config {
@@ -72,7 +85,14 @@ pub struct Config {
 	pub player: PlayerConfig,
 	pub privacy: PrivacyConfig,
 	pub region: RegionConfig,
+	#[serde(default)]
 	pub sponsorblock: SponsorBlockConfig,
+	#[serde(default)]
+	pub filters: FilterConfig,
+	#[serde(default)]
+	pub blocklist: BlocklistConfig,
+	#[serde(default)]
+	pub captions: CaptionsConfig,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -89,6 +109,9 @@ pub enum HomepageCategory {
 	Trending,
 	Popular,
 	Subscriptions,
+	History,
+	/// A local playlist, identified by its id.
+	Playlist(String),
 }
 
 impl Default for NetworkConfig {
@@ -119,6 +142,7 @@ impl Default for PlayerConfig {
 		let default_audio_quality = AudioQuality::Medium;
 		let remember_position = VideosOnly;
 		let volume = 0.5f64;
+		let keybindings = KeybindingsConfig::default();
 
 		Self {
 			auto_play,
@@ -127,6 +151,28 @@ impl Default for PlayerConfig {
 			default_audio_quality,
 			remember_position,
 			volume,
+			keybindings,
+		}
+	}
+}
+
+impl Default for KeybindingsConfig {
+	fn default() -> Self {
+		let keys = |keys: &[&str]| {
+			keys.iter().map(|key| (*key).to_string()).collect::<Vec<String>>()
+		};
+
+		Self {
+			toggle_playback: keys(&["Space", "k"]),
+			seek_backward: keys(&["j", "ArrowLeft"]),
+			seek_forward: keys(&["l", "ArrowRight"]),
+			volume_up: keys(&["ArrowUp"]),
+			volume_down: keys(&["ArrowDown"]),
+			fullscreen: keys(&["f"]),
+			full_window: keys(&["t"]),
+			mute: keys(&["m"]),
+			cycle_captions: keys(&["c"]),
+			show_shortcuts: keys(&["?"]),
 		}
 	}
 }
@@ -148,40 +194,29 @@ impl Default for RegionConfig {
 	}
 }
 
-impl Default for SponsorBlockConfig {
-	fn default() -> Self {
-		Self {
-			enabled: true,
-			skip_sponsors: true,
-			skip_selfpromos: false,
-			skip_interactions: false,
-			skip_intros: false,
-			skip_outros: false,
-			skip_previews: false,
-			skip_irrelevant_music: false,
-			skip_filler: false,
-		}
-	}
-}
-
 pub const CONFIG_KEY: &str = "RUSTYTUBE_CONFIG";
 
 impl Config {
 	/// # Errors
 	///
-	/// - `LocalStorage` errors.
+	/// - `IndexedDB` errors.
 	/// - Toml serialisation error.
-	pub fn save(&self) -> Result<(), RustyTubeError> {
-		save_to_browser_storage(CONFIG_KEY, &toml::to_string(&self)?)?;
-		Ok(())
+	pub async fn save(&self) -> Result<(), RustyTubeError> {
+		idb_put(CONFIG_STORE, CONFIG_KEY, &toml::to_string(&self)?).await
 	}
 
+	/// Returns the default config if none has been saved yet.
+	///
 	/// # Errors
 	///
-	/// - `LocalStorage` errors.
+	/// - `IndexedDB` errors.
 	/// - Toml deserialisation error.
-	pub fn load() -> Result<Self, RustyTubeError> {
-		let toml_str = LocalStorage::get::<String>(CONFIG_KEY)?;
+	pub async fn load() -> Result<Self, RustyTubeError> {
+		let Some(toml_str) =
+			idb_get::<String>(CONFIG_STORE, CONFIG_KEY).await?
+		else {
+			return Ok(Self::default());
+		};
 		let config = toml::from_str::<Self>(&toml_str)?;
 		Ok(config)
 	}
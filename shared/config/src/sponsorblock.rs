@@ -0,0 +1,96 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// What to do with the segments of each category. Fields are named after the
+/// categories of the SponsorBlock API.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[serde(default)]
+pub struct SponsorBlockConfig {
+	pub enabled: bool,
+	#[serde(alias = "skip_sponsors", deserialize_with = "segment_action")]
+	pub sponsor: SegmentAction,
+	#[serde(alias = "skip_selfpromos", deserialize_with = "segment_action")]
+	pub selfpromo: SegmentAction,
+	#[serde(alias = "skip_interactions", deserialize_with = "segment_action")]
+	pub interaction: SegmentAction,
+	#[serde(alias = "skip_intros", deserialize_with = "segment_action")]
+	pub intro: SegmentAction,
+	#[serde(alias = "skip_outros", deserialize_with = "segment_action")]
+	pub outro: SegmentAction,
+	#[serde(alias = "skip_previews", deserialize_with = "segment_action")]
+	pub preview: SegmentAction,
+	#[serde(
+		alias = "skip_irrelevant_music",
+		deserialize_with = "segment_action"
+	)]
+	pub music_offtopic: SegmentAction,
+	#[serde(alias = "skip_filler", deserialize_with = "segment_action")]
+	pub filler: SegmentAction,
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentAction {
+	/// Seeks past the segment when it starts.
+	AutoSkip,
+	/// Shows a button that seeks past the segment.
+	ManualSkip,
+	/// Only marks the segment on the progress bar.
+	Show,
+	/// Mutes the video for the length of the segment.
+	Mute,
+	/// Segments of the category are not fetched.
+	Disabled,
+}
+
+impl Default for SponsorBlockConfig {
+	fn default() -> Self {
+		Self {
+			enabled: true,
+			sponsor: SegmentAction::AutoSkip,
+			selfpromo: SegmentAction::Show,
+			interaction: SegmentAction::Show,
+			intro: SegmentAction::Show,
+			outro: SegmentAction::Show,
+			preview: SegmentAction::Show,
+			music_offtopic: SegmentAction::Show,
+			filler: SegmentAction::Show,
+		}
+	}
+}
+
+impl SponsorBlockConfig {
+	/// The action for a category, as named by the SponsorBlock API.
+	pub fn action(&self, category: &str) -> SegmentAction {
+		match category {
+			"sponsor" => self.sponsor,
+			"selfpromo" => self.selfpromo,
+			"interaction" => self.interaction,
+			"intro" => self.intro,
+			"outro" => self.outro,
+			"preview" => self.preview,
+			"music_offtopic" => self.music_offtopic,
+			"filler" => self.filler,
+			_ => SegmentAction::Disabled,
+		}
+	}
+}
+
+/// Older configs stored whether each category was skipped.
+fn segment_action<'de, D>(deserializer: D) -> Result<SegmentAction, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	#[derive(Deserialize)]
+	#[serde(untagged)]
+	enum StoredAction {
+		Skip(bool),
+		Action(SegmentAction),
+	}
+
+	Ok(match StoredAction::deserialize(deserializer)? {
+		StoredAction::Skip(true) => SegmentAction::AutoSkip,
+		StoredAction::Skip(false) => SegmentAction::Disabled,
+		StoredAction::Action(action) => action,
+	})
+}
@@ -3,18 +3,27 @@ use wasm_bindgen::JsValue;
 
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum RustyTubeError {
+	BackupFormat,
+	BackupVersion(u32),
 	BrowserStorage(String),
 	ChronoParse(#[from] chrono::ParseError),
 	Csv(String),
 	DateTime,
 	DynInto,
 	ElementNotFound,
+	EmptyPlaylist,
+	FilterRule(String),
 	GlooFileRead(String),
+	GroupExists,
+	GroupName,
+	ImportFormat,
+	InstanceError(String),
 	LangCode,
 	Network(String),
 	NoAdaptiveFormat,
 	NoAudioFormat,
 	NoFileSelected,
+	NoIndexedDb,
 	NoLegacyFormat,
 	NoThumbnails,
 	NoVideoFormat,
@@ -22,17 +31,24 @@ pub enum RustyTubeError {
 	NoVideoUrl,
 	NoPerformance,
 	NoWindow,
+	NotFound,
+	PlaylistExists,
 	PlaylistParse,
+	RateLimited,
+	RequestError(u16),
 	Ron(#[from] ron::error::Error),
 	RonSpanned(#[from] ron::error::SpannedError),
 	SearchArgs,
 	SerdeJson(String),
+	ServerError(u16),
 	TargetNotFound,
 	Tauri(#[from] tauri_sys::error::Error),
 	TomlSerialisation(#[from] toml::ser::Error),
 	TomlDeserialisation(#[from] toml::de::Error),
 	Websys(String),
+	WebVtt(String),
 	Xml(String),
+	Zip(String),
 }
 
 impl From<JsValue> for RustyTubeError {
@@ -44,6 +60,10 @@ impl From<JsValue> for RustyTubeError {
 impl std::fmt::Display for RustyTubeError {
 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
 		match self {
+			Self::BackupFormat => write!(f, "Error: Not a RustyTube backup."),
+			Self::BackupVersion(version) => {
+				write!(f, "Error: Unsupported backup version {version}.")
+			}
 			Self::BrowserStorage(error) => {
 				write!(f, "Browser Storage Error: {error}")
 			}
@@ -54,9 +74,19 @@ impl std::fmt::Display for RustyTubeError {
 			Self::DateTime => write!(f, "Date time error."),
 			Self::DynInto => write!(f, "Dynamic conversion error."),
 			Self::ElementNotFound => write!(f, "Element not found error."),
+			Self::EmptyPlaylist => write!(f, "Error: Playlist is empty."),
+			Self::FilterRule(error) => {
+				write!(f, "Error: Invalid filter rule. {error}")
+			}
 			Self::GlooFileRead(error) => {
 				write!(f, "File Read Error: {error}")
 			}
+			Self::GroupExists => {
+				write!(f, "Error: A group with this name already exists.")
+			}
+			Self::GroupName => write!(f, "Error: Group name cannot be empty."),
+			Self::ImportFormat => write!(f, "Error: Unrecognised export file."),
+			Self::InstanceError(error) => write!(f, "Instance Error: {error}"),
 			Self::LangCode => write!(f, "Unknown lang code."),
 			Self::Network(error) => write!(f, "Network Error: {error}"),
 			Self::NoAdaptiveFormat => {
@@ -66,6 +96,7 @@ impl std::fmt::Display for RustyTubeError {
 				write!(f, "Error: No audio formats available.")
 			}
 			Self::NoFileSelected => write!(f, "No file selected."),
+			Self::NoIndexedDb => write!(f, "Error: IndexedDB not available."),
 			Self::NoLegacyFormat => write!(f, "No legacy format available."),
 			Self::NoThumbnails => write!(f, "Error: No thumbnails available."),
 			Self::NoVideoFormat => {
@@ -79,13 +110,26 @@ impl std::fmt::Display for RustyTubeError {
 				write!(f, "Error: Perfomance not available.")
 			}
 			Self::NoWindow => write!(f, "Error: Window not available."),
+			Self::NotFound => write!(f, "Error: Not found."),
+			Self::PlaylistExists => {
+				write!(f, "Error: A playlist with this title already exists.")
+			}
 			Self::PlaylistParse => write!(f, "Error: Playlist parse failed."),
+			Self::RateLimited => {
+				write!(f, "Error: Rate limited, try again later.")
+			}
+			Self::RequestError(status) => {
+				write!(f, "Request Error: status code {status}.")
+			}
 			Self::Ron(error) => write!(f, "Ron Error: {error}"),
 			Self::RonSpanned(error) => write!(f, "Ron Error: {error}"),
 			Self::SearchArgs => write!(f, "Error: Search args invalid."),
 			Self::SerdeJson(error) => {
 				write!(f, "Serde Json Error: {error}")
 			}
+			Self::ServerError(status) => {
+				write!(f, "Server Error: status code {status}.")
+			}
 			Self::TargetNotFound => write!(f, "Error: target not found."),
 			Self::Tauri(error) => write!(f, "Tauri Error: {error}"),
 			Self::TomlSerialisation(error) => {
@@ -95,7 +139,9 @@ impl std::fmt::Display for RustyTubeError {
 				write!(f, "Toml Deserialisation Error: {error}")
 			}
 			Self::Websys(error) => write!(f, "Websys Error: {error}"),
+			Self::WebVtt(error) => write!(f, "WebVTT Error: {error}"),
 			Self::Xml(error) => write!(f, "Xml Error: {error}"),
+			Self::Zip(error) => write!(f, "Zip Error: {error}"),
 		}
 	}
 }
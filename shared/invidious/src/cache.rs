@@ -0,0 +1,122 @@
+use leptos::spawn_local;
+use rustytube_error::RustyTubeError;
+use serde::{Deserialize, Serialize};
+use utils::{
+	get_unix_time, idb_clear, idb_delete, idb_entries, idb_get, idb_put,
+	idb_size, API_CACHE_STORE,
+};
+
+use crate::{HttpClient, INSTANCES_API_LINK};
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = MINUTE * 60;
+const DAY: u64 = HOUR * 24;
+const WEEK: u64 = DAY * 7;
+
+/// Responses kept past their max age for offline use.
+const OFFLINE_GRACE: u64 = WEEK;
+/// Responses kept in the cache, past which the oldest are evicted.
+const MAX_ENTRIES: usize = 500;
+
+/// How long responses from an endpoint are cached for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachePolicy {
+	/// Seconds a response is served without being revalidated.
+	pub ttl: u64,
+	/// Seconds a stale response is still served while it is revalidated in
+	/// the background.
+	pub max_age: u64,
+}
+
+impl CachePolicy {
+	/// Returns `None` for endpoints that should not be cached.
+	pub fn for_url(url: &str) -> Option<Self> {
+		let (ttl, max_age) = if url.contains("/api/v1/videos/") {
+			// Stream urls expire after roughly six hours.
+			(MINUTE * 10, HOUR * 3)
+		} else if url.contains("/api/v1/channels/") {
+			(MINUTE * 30, WEEK)
+		} else if url.contains("/api/v1/trending")
+			|| url.contains("/api/v1/popular")
+		{
+			(MINUTE * 30, DAY)
+		} else if url.contains("/api/v1/playlists/") {
+			(HOUR, WEEK)
+		} else if url.eq(INSTANCES_API_LINK) {
+			(DAY, WEEK)
+		} else {
+			return None;
+		};
+		Some(Self { ttl, max_age })
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedResponse {
+	body: String,
+	/// Unix timestamp in seconds.
+	fetched: u64,
+}
+
+/// Serves the url from the response cache, revalidating stale entries in
+/// the background. Entries past their max age are only used when offline.
+pub async fn fetch_cached(
+	url: &str,
+	policy: CachePolicy,
+) -> Result<String, RustyTubeError> {
+	let Some(cached) =
+		idb_get::<CachedResponse>(API_CACHE_STORE, url).await.ok().flatten()
+	else {
+		return refresh(url).await;
+	};
+
+	let age = get_unix_time().saturating_sub(cached.fetched);
+	if age < policy.ttl {
+		Ok(cached.body)
+	} else if age < policy.max_age {
+		let url = url.to_owned();
+		spawn_local(async move {
+			let _ = refresh(&url).await;
+		});
+		Ok(cached.body)
+	} else {
+		match refresh(url).await {
+			Err(RustyTubeError::Network(_)) => Ok(cached.body),
+			result => result,
+		}
+	}
+}
+
+async fn refresh(url: &str) -> Result<String, RustyTubeError> {
+	let body = HttpClient::default().get(url).await?;
+	let cached = CachedResponse { body, fetched: get_unix_time() };
+	let _ = idb_put(API_CACHE_STORE, url, &cached).await;
+	Ok(cached.body)
+}
+
+/// Evicts responses kept past their max age and `OFFLINE_GRACE`, then the
+/// oldest responses past `MAX_ENTRIES`.
+pub async fn prune_cache() -> Result<(), RustyTubeError> {
+	let now = get_unix_time();
+	let mut entries = idb_entries::<CachedResponse>(API_CACHE_STORE).await?;
+	entries.sort_by(|a, b| b.1.fetched.cmp(&a.1.fetched));
+
+	for (index, (url, cached)) in entries.iter().enumerate() {
+		let expired = CachePolicy::for_url(url).map_or(true, |policy| {
+			now.saturating_sub(cached.fetched) >= policy.max_age + OFFLINE_GRACE
+		});
+		if expired || index >= MAX_ENTRIES {
+			idb_delete(API_CACHE_STORE, url).await?;
+		}
+	}
+	Ok(())
+}
+
+/// Size of the response cache in bytes.
+pub async fn cache_size() -> Result<usize, RustyTubeError> {
+	idb_size(API_CACHE_STORE).await
+}
+
+pub async fn clear_cache() -> Result<(), RustyTubeError> {
+	idb_clear(API_CACHE_STORE).await
+}
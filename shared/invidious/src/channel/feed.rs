@@ -1,21 +1,27 @@
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 
-use crate::{fetch, ChannelVideos, CommonThumbnail, CommonVideo};
+use crate::{
+	fetch, fetch_native_feed, ChannelVideos, CommonThumbnail, CommonVideo,
+};
 
-/// Fetching feeds via RSS is currently broken due to CORS restrictions in regular browsers.
+/// Regular browsers block RSS feeds due to CORS restrictions, so the desktop
+/// app fetches them natively through the Tauri backend.
 impl Feed {
 	/// # Errors
 	///
 	/// - Network errors.
+	/// - Tauri errors.
 	/// - Serde errors.
 	pub async fn fetch_videos_from_feed(
 		server: &str,
 		id: &str,
 	) -> Result<ChannelVideos, RustyTubeError> {
-		let playlist_id = id.replace("UC", "UULF");
-		let url = format!("{server}/feed/playlist/{playlist_id}/");
-		let response = fetch(&url).await?;
+		let response = if utils::is_tauri() {
+			fetch_native_feed(server, id).await?
+		} else {
+			fetch(&format!("{server}/feed/channel/{id}")).await?
+		};
 		let feed = serde_xml_rs::from_str::<Self>(&response)?;
 		Ok(feed.into())
 	}
@@ -25,6 +31,8 @@ impl Feed {
 #[serde(rename_all = "camelCase")]
 pub struct Feed {
 	pub id: String,
+	/// Only present in playlist feeds.
+	#[serde(default)]
 	pub playlist_id: String,
 	pub channel_id: String,
 	pub title: String,
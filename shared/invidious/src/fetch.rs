@@ -1,11 +1,190 @@
+use gloo::timers::{callback::Timeout, future::TimeoutFuture};
 use reqwasm::http::{Method, Request, RequestMode};
 use rustytube_error::RustyTubeError;
-use web_sys::RequestCache;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use web_sys::{AbortController, RequestCache};
 
+use crate::{fetch_cached, CachePolicy, ServerPool};
+
+/// Milliseconds before a request is aborted.
+pub const DEFAULT_TIMEOUT: u32 = 15_000;
+
+/// Extra attempts made after a request fails with a retryable error.
+pub const DEFAULT_RETRIES: u32 = 2;
+
+/// Milliseconds waited before the first retry, doubled for every retry after.
+pub const DEFAULT_BACKOFF: u32 = 500;
+
+/// Fetches the url with the default `HttpClient`, going through the response
+/// cache for cacheable endpoints.
 pub async fn fetch(url: &str) -> Result<String, RustyTubeError> {
-	let request = Request::new(url)
-		.mode(RequestMode::Cors)
-		.method(Method::GET)
-		.cache(RequestCache::Default);
-	Ok(request.send().await?.text().await?)
+	if let Some(policy) = CachePolicy::for_url(url) {
+		return fetch_cached(url, policy).await;
+	}
+	HttpClient::default().get(url).await
+}
+
+/// Error body returned by Invidious, e.g. `{"error": "Video unavailable"}`.
+#[derive(Deserialize)]
+struct InvidiousError {
+	error: String,
+}
+
+/// Performs GET requests with status handling, timeouts, exponential backoff
+/// retries and failover across the `ServerPool`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpClient {
+	/// Milliseconds before a request is aborted.
+	pub timeout: u32,
+	pub retries: u32,
+	/// Milliseconds waited before the first retry.
+	pub backoff: u32,
+}
+
+impl Default for HttpClient {
+	fn default() -> Self {
+		let timeout = DEFAULT_TIMEOUT;
+		let retries = DEFAULT_RETRIES;
+		let backoff = DEFAULT_BACKOFF;
+
+		Self { timeout, retries, backoff }
+	}
+}
+
+impl HttpClient {
+	#[must_use]
+	pub const fn timeout(mut self, timeout: u32) -> Self {
+		self.timeout = timeout;
+		self
+	}
+
+	#[must_use]
+	pub const fn retries(mut self, retries: u32) -> Self {
+		self.retries = retries;
+		self
+	}
+
+	#[must_use]
+	pub const fn backoff(mut self, backoff: u32) -> Self {
+		self.backoff = backoff;
+		self
+	}
+
+	/// # Errors
+	///
+	/// - Network errors, including timeouts.
+	/// - `NotFound` for 404 responses.
+	/// - `RateLimited` for 429 responses.
+	/// - `InstanceError` for other responses with an Invidious error message,
+	///   such as unavailable videos.
+	/// - `ServerError` for other 5xx responses, otherwise `RequestError`.
+	pub async fn get(&self, url: &str) -> Result<String, RustyTubeError> {
+		let mut attempt = 0;
+		loop {
+			match self.get_with_failover(url).await {
+				Err(error)
+					if is_retryable(&error) && attempt < self.retries =>
+				{
+					let delay = self
+						.backoff
+						.saturating_mul(2u32.saturating_pow(attempt));
+					TimeoutFuture::new(delay).await;
+					attempt += 1;
+				}
+				result => return result,
+			}
+		}
+	}
+
+	/// # Errors
+	///
+	/// - Any error returned by `HttpClient::get`.
+	/// - Serde errors.
+	pub async fn get_json<T>(&self, url: &str) -> Result<T, RustyTubeError>
+	where
+		T: DeserializeOwned,
+	{
+		Ok(serde_json::from_str::<T>(&self.get(url).await?)?)
+	}
+
+	/// Tries each healthy server in turn, marking those that fail as
+	/// degraded. Client errors are returned straight away, as every server
+	/// would reject the same request.
+	async fn get_with_failover(
+		&self,
+		url: &str,
+	) -> Result<String, RustyTubeError> {
+		let mut last_error = None;
+		for candidate in ServerPool::failover_urls(url) {
+			match self.send(&candidate).await {
+				Err(error) if is_server_failure(&error) => {
+					ServerPool::mark_degraded(&candidate);
+					last_error = Some(error);
+				}
+				result => return result,
+			}
+		}
+		Err(last_error.unwrap_or_else(|| {
+			RustyTubeError::Network(String::from("No servers available."))
+		}))
+	}
+
+	async fn send(&self, url: &str) -> Result<String, RustyTubeError> {
+		let controller = AbortController::new()?;
+		let signal = controller.signal();
+		let _timeout = Timeout::new(self.timeout, move || controller.abort());
+
+		let response = Request::new(url)
+			.mode(RequestMode::Cors)
+			.method(Method::GET)
+			.cache(RequestCache::Default)
+			.abort_signal(Some(&signal))
+			.send()
+			.await?;
+		let status = response.status();
+		let body = response.text().await?;
+
+		match status {
+			200..=299 => Ok(body),
+			404 => Err(RustyTubeError::NotFound),
+			429 => Err(RustyTubeError::RateLimited),
+			// Invidious reports content errors, such as unavailable or age
+			// restricted videos, with a 5xx status and an error body.
+			_ => Err(serde_json::from_str::<InvidiousError>(&body).map_or(
+				if (500..=599).contains(&status) {
+					RustyTubeError::ServerError(status)
+				} else {
+					RustyTubeError::RequestError(status)
+				},
+				|error| RustyTubeError::InstanceError(error.error),
+			)),
+		}
+	}
+}
+
+/// Errors caused by the server itself rather than the request.
+const fn is_server_failure(error: &RustyTubeError) -> bool {
+	matches!(error, RustyTubeError::Network(_) | RustyTubeError::ServerError(_))
+}
+
+const fn is_retryable(error: &RustyTubeError) -> bool {
+	is_server_failure(error) || matches!(error, RustyTubeError::RateLimited)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NativeFeedArgs<'a> {
+	server: &'a str,
+	channel_id: &'a str,
+}
+
+/// Fetches a channel's RSS feed from the Tauri backend, bypassing CORS.
+///
+/// Only available in the desktop app.
+pub async fn fetch_native_feed(
+	server: &str,
+	channel_id: &str,
+) -> Result<String, RustyTubeError> {
+	let args = NativeFeedArgs { server, channel_id };
+	Ok(tauri_sys::tauri::invoke("fetch_feed", &args).await?)
 }
@@ -17,3 +17,82 @@ pub struct Storyboard {
 	#[serde(default)]
 	pub storyboard_count: i16,
 }
+
+/// One thumbnail of a storyboard: a `width` by `height` area of the sprite
+/// sheet at `url`, starting `x` and `y` pixels from its top left corner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoryboardTile {
+	pub url: String,
+	pub x: u32,
+	pub y: u32,
+	pub width: u32,
+	pub height: u32,
+}
+
+impl Storyboard {
+	/// Picks the largest level whose thumbnails fit in `width` pixels, or the
+	/// smallest level if none do.
+	pub fn for_width(storyboards: &[Self], width: u32) -> Option<&Self> {
+		let usable = storyboards.iter().filter(|storyboard| {
+			storyboard.count > 0
+				&& storyboard.width > 0
+				&& storyboard.height > 0
+		});
+		usable
+			.clone()
+			.filter(|storyboard| storyboard.width <= width)
+			.max_by_key(|storyboard| storyboard.width)
+			.or_else(|| usable.min_by_key(|storyboard| storyboard.width))
+	}
+
+	/// The thumbnail shown at `time` seconds into a video `duration` seconds
+	/// long.
+	#[allow(clippy::cast_possible_truncation)]
+	#[allow(clippy::cast_sign_loss)]
+	pub fn tile_at(
+		&self,
+		server: &str,
+		time: f64,
+		duration: f64,
+	) -> Option<StoryboardTile> {
+		let count =
+			u32::try_from(self.count).ok().filter(|count| *count > 0)?;
+		let columns = u32::from(self.storyboard_width).max(1);
+		let rows = u32::from(self.storyboard_height).max(1);
+
+		// Milliseconds between thumbnails. The smallest level reports 0, as its
+		// thumbnails are spread evenly over the video.
+		let interval = if self.interval > 0 {
+			f64::from(self.interval)
+		} else {
+			duration * 1000f64 / f64::from(count)
+		};
+		if !interval.is_finite() || interval <= 0f64 {
+			return None;
+		}
+
+		let index =
+			((time.max(0f64) * 1000f64 / interval) as u32).min(count - 1);
+		let sheet = index / (columns * rows);
+		let position = index % (columns * rows);
+
+		Some(StoryboardTile {
+			url: self.sheet_url(server, sheet),
+			x: position % columns * self.width,
+			y: position / columns * self.height,
+			width: self.width,
+			height: self.height,
+		})
+	}
+
+	/// The template's `$M` is replaced by the sheet number. Proxied
+	/// storyboards use paths relative to the server.
+	fn sheet_url(&self, server: &str, sheet: u32) -> String {
+		let url = self.template_url.replace("$M", &sheet.to_string());
+		if url.starts_with('/') {
+			format!("{server}{url}")
+		} else {
+			url
+		}
+	}
+}
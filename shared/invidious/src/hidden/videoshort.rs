@@ -10,6 +10,9 @@ pub struct VideoShort {
 	#[serde(rename = "videoThumbnails")]
 	pub thumbnails: Vec<CommonThumbnail>,
 	pub author: String,
+	#[serde(rename = "authorId")]
+	#[serde(default)]
+	pub author_id: String,
 	#[serde(rename = "lengthSeconds")]
 	pub length: u32,
 	#[serde(rename = "viewCountText")]
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests;
 
+mod cache;
 mod channel;
 mod common;
 mod dislikes;
@@ -8,10 +9,12 @@ mod fetch;
 mod formats;
 mod hidden;
 mod instance;
+mod server_pool;
 mod subs;
 mod universal;
 mod video;
 
+pub use cache::*;
 pub use channel::*;
 pub use common::*;
 pub use dislikes::*;
@@ -19,6 +22,7 @@ pub use fetch::*;
 pub use formats::*;
 pub use hidden::*;
 pub use instance::*;
+pub use server_pool::*;
 pub use subs::*;
 pub use universal::*;
 pub use video::*;
@@ -0,0 +1,153 @@
+use std::cell::RefCell;
+
+use utils::get_unix_time;
+
+use crate::{Instance, InstanceInfo};
+
+/// Seconds a failing server is skipped for before it is tried again.
+pub const DEGRADED_DURATION: u64 = 300;
+
+/// Public instances below this 30 day uptime percentage are left out.
+pub const MIN_UPTIME_RATIO: f64 = 90.0;
+
+/// Maximum number of servers a single request is attempted against.
+pub const MAX_FAILOVER_ATTEMPTS: usize = 3;
+
+thread_local! {
+	static SERVER_POOL: RefCell<ServerPool> = RefCell::new(ServerPool::default());
+}
+
+/// Servers that requests can fail over to when the configured one is down.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ServerPool {
+	/// Ordered by preference, starting with the configured server.
+	pub servers: Vec<PoolServer>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolServer {
+	pub url: String,
+	/// Unix timestamp in seconds until which the server is skipped.
+	pub degraded_until: u64,
+}
+
+impl ServerPool {
+	/// Builds a pool from the configured server, the user's custom servers
+	/// and the public instances that allow API access with CORS, most
+	/// reliable first.
+	pub fn new(
+		server: &str,
+		custom_servers: &[String],
+		instances: &[Instance],
+	) -> Self {
+		let mut public = instances
+			.iter()
+			.map(|(_, info)| info)
+			.filter(|info| is_usable(info))
+			.collect::<Vec<&InstanceInfo>>();
+		public.sort_by(|a, b| {
+			uptime(b)
+				.unwrap_or_default()
+				.total_cmp(&uptime(a).unwrap_or_default())
+		});
+
+		let mut pool = Self::default();
+		std::iter::once(server)
+			.chain(custom_servers.iter().map(String::as_str))
+			.chain(public.into_iter().map(|info| info.uri.as_str()))
+			.for_each(|url| pool.push(url));
+		pool
+	}
+
+	fn push(&mut self, url: &str) {
+		let url = url.trim().trim_end_matches('/');
+		if !url.is_empty()
+			&& !self.servers.iter().any(|server| server.url.eq(url))
+		{
+			self.servers
+				.push(PoolServer { url: url.to_owned(), degraded_until: 0 });
+		}
+	}
+
+	/// Replaces the pool used by `fetch`, keeping the health of servers that
+	/// were already known.
+	pub fn install(mut self) {
+		SERVER_POOL.with(|pool| {
+			let mut current = pool.borrow_mut();
+			for server in &mut self.servers {
+				if let Some(known) = current
+					.servers
+					.iter()
+					.find(|known| known.url.eq(&server.url))
+				{
+					server.degraded_until = known.degraded_until;
+				}
+			}
+			*current = self;
+		});
+	}
+
+	/// The urls a request should be attempted against, in order.
+	///
+	/// Urls that do not belong to a pooled server are returned unchanged.
+	pub fn failover_urls(url: &str) -> Vec<String> {
+		SERVER_POOL.with(|pool| pool.borrow().candidates(url, get_unix_time()))
+	}
+
+	/// Skips the server the url belongs to for `DEGRADED_DURATION` seconds.
+	pub fn mark_degraded(url: &str) {
+		SERVER_POOL.with(|pool| {
+			if let Some(server) = pool
+				.borrow_mut()
+				.servers
+				.iter_mut()
+				.find(|server| server_path(url, &server.url).is_some())
+			{
+				server.degraded_until = get_unix_time() + DEGRADED_DURATION;
+			}
+		});
+	}
+
+	fn candidates(&self, url: &str, now: u64) -> Vec<String> {
+		let Some((origin, path)) = self.servers.iter().find_map(|server| {
+			server_path(url, &server.url).map(|path| (server, path))
+		}) else {
+			return vec![url.to_owned()];
+		};
+
+		let mut urls = self
+			.servers
+			.iter()
+			.filter(|server| server.degraded_until <= now)
+			.filter(|server| !server.url.eq(&origin.url))
+			.map(|server| format!("{}{path}", server.url))
+			.collect::<Vec<String>>();
+		if origin.degraded_until <= now || urls.is_empty() {
+			urls.insert(0, url.to_owned());
+		}
+		urls.truncate(MAX_FAILOVER_ATTEMPTS);
+		urls
+	}
+}
+
+/// The remainder of `url` after `server`, if the url points at that server.
+fn server_path<'a>(url: &'a str, server: &str) -> Option<&'a str> {
+	url.strip_prefix(server).filter(|path| {
+		path.is_empty() || path.starts_with('/') || path.starts_with('?')
+	})
+}
+
+fn is_usable(info: &InstanceInfo) -> bool {
+	info.api.unwrap_or_default()
+		&& info.cors.unwrap_or_default()
+		&& info.type_field.eq("https")
+		&& uptime(info).map_or(true, |ratio| ratio >= MIN_UPTIME_RATIO)
+}
+
+fn uptime(info: &InstanceInfo) -> Option<f64> {
+	info.monitor
+		.as_ref()
+		.and_then(|monitor| monitor.monthly_ratio.as_ref())
+		.and_then(|ratio| ratio.ratio.as_ref())
+		.and_then(|ratio| ratio.parse::<f64>().ok())
+}
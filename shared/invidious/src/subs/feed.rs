@@ -0,0 +1,258 @@
+use std::collections::{HashMap, HashSet};
+
+use futures::future::join_all;
+use gloo::timers::future::TimeoutFuture;
+use rustytube_error::RustyTubeError;
+use serde::{Deserialize, Serialize};
+use utils::{
+	get_unix_time, idb_clear, idb_delete, idb_get_all, idb_put, FEEDS_STORE,
+};
+
+use crate::{
+	ChannelShorts, ChannelVideos, CommonVideo, Feed, HttpClient, Subscription,
+	Subscriptions,
+};
+
+/// Channels fetched at the same time while refreshing the feed.
+pub const FEED_BATCH_SIZE: usize = 6;
+
+/// Milliseconds waited between batches.
+pub const FEED_BATCH_DELAY: u32 = 1_000;
+
+/// Seconds a channel's cached videos are used before it is fetched again.
+pub const FEED_MAX_AGE: u64 = 30 * 60;
+
+/// Videos kept per channel, so older uploads stay in the feed after they
+/// drop out of the channel's latest videos.
+const MAX_CHANNEL_VIDEOS: usize = 60;
+
+/// The last fetched videos of a subscribed channel.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CachedChannelFeed {
+	pub id: String,
+	pub videos: Vec<CommonVideo>,
+	/// Ids of the videos that are shorts. RSS feeds mix shorts in with other
+	/// uploads without giving their length, so they are looked up by id.
+	#[serde(default)]
+	pub shorts: Vec<String>,
+	/// Unix timestamp in seconds.
+	pub fetched: u64,
+}
+
+impl CachedChannelFeed {
+	/// Adds the latest videos to the cached ones, newest first.
+	fn merge(id: &str, latest: LatestVideos, cached: Option<Self>) -> Self {
+		let LatestVideos { mut videos, mut shorts } = latest;
+		let cached = cached.unwrap_or_else(|| Self {
+			id: id.to_owned(),
+			videos: Vec::new(),
+			shorts: Vec::new(),
+			fetched: 0,
+		});
+		for video in cached.videos {
+			if !videos.contains(&video) {
+				videos.push(video);
+			}
+		}
+		videos.sort_by(|a, b| b.published.cmp(&a.published));
+		videos.truncate(MAX_CHANNEL_VIDEOS);
+
+		for short in cached.shorts {
+			if !shorts.contains(&short) {
+				shorts.push(short);
+			}
+		}
+		shorts.retain(|short| videos.iter().any(|video| &video.id == short));
+		Self { id: id.to_owned(), videos, shorts, fetched: get_unix_time() }
+	}
+
+	pub async fn save(&self) -> Result<(), RustyTubeError> {
+		idb_put(FEEDS_STORE, &self.id, self).await
+	}
+
+	pub async fn delete(id: &str) -> Result<(), RustyTubeError> {
+		idb_delete(FEEDS_STORE, id).await
+	}
+
+	/// Deletes the cached videos of every channel.
+	pub async fn delete_all() -> Result<(), RustyTubeError> {
+		idb_clear(FEEDS_STORE).await
+	}
+
+	async fn load_all() -> HashMap<String, Self> {
+		idb_get_all::<Self>(FEEDS_STORE)
+			.await
+			.unwrap_or_default()
+			.into_iter()
+			.map(|feed| (feed.id.clone(), feed))
+			.collect()
+	}
+}
+
+/// A channel whose videos could not be refreshed.
+#[derive(Debug, Clone)]
+pub struct FeedFailure {
+	pub id: String,
+	pub name: String,
+	pub error: RustyTubeError,
+}
+
+impl FeedFailure {
+	fn new(channel: &Subscription, error: RustyTubeError) -> Self {
+		Self { id: channel.id.clone(), name: channel.name.clone(), error }
+	}
+}
+
+/// The videos of a set of subscriptions, newest first.
+#[derive(Debug, Clone, Default)]
+pub struct SubscriptionsFeed {
+	pub videos: Vec<CommonVideo>,
+	/// Ids of the videos known to be shorts.
+	pub shorts: HashSet<String>,
+	/// Channels that failed to refresh. Their cached videos, if any, are
+	/// still in the feed.
+	pub failures: Vec<FeedFailure>,
+}
+
+impl SubscriptionsFeed {
+	fn new(
+		feeds: impl IntoIterator<Item = CachedChannelFeed>,
+		failures: Vec<FeedFailure>,
+	) -> Self {
+		let mut videos = Vec::new();
+		let mut shorts = HashSet::new();
+		for feed in feeds {
+			videos.extend(feed.videos);
+			shorts.extend(feed.shorts);
+		}
+		videos.sort_by(|a, b| b.published.cmp(&a.published));
+		videos.dedup();
+		Self { videos, shorts, failures }
+	}
+}
+
+impl Subscriptions {
+	/// Builds the feed from the cached videos alone, without any requests.
+	pub async fn cached_feed(&self) -> SubscriptionsFeed {
+		let mut cached = CachedChannelFeed::load_all().await;
+		let feeds = self
+			.channels
+			.iter()
+			.filter_map(|channel| cached.remove(&channel.id));
+		SubscriptionsFeed::new(feeds, Vec::new())
+	}
+
+	/// Fetches the channels last fetched before `stale_before`, a unix
+	/// timestamp, `FEED_BATCH_SIZE` at a time, and merges their latest videos
+	/// into the cached ones. Once the server rate limits us the remaining
+	/// channels are not fetched, and are reported as failures.
+	///
+	/// With `find_shorts`, the shorts of channels fetched over RSS are looked
+	/// up as well.
+	pub async fn refresh_feed(
+		&self,
+		server: &str,
+		rss: bool,
+		find_shorts: bool,
+		lang: &str,
+		stale_before: u64,
+	) -> SubscriptionsFeed {
+		let mut cached = CachedChannelFeed::load_all().await;
+		let mut feeds = Vec::new();
+		let mut stale = Vec::new();
+		for channel in &self.channels {
+			match cached.remove(&channel.id) {
+				Some(feed) if feed.fetched >= stale_before => {
+					feeds.push(feed);
+				}
+				feed => stale.push((channel, feed)),
+			}
+		}
+
+		let mut failures = Vec::new();
+		let mut rate_limited = false;
+		let mut stale = stale.into_iter().peekable();
+		let mut first_batch = true;
+		while stale.peek().is_some() {
+			let batch =
+				stale.by_ref().take(FEED_BATCH_SIZE).collect::<Vec<_>>();
+			if rate_limited {
+				for (channel, feed) in batch {
+					feeds.extend(feed);
+					failures.push(FeedFailure::new(
+						channel,
+						RustyTubeError::RateLimited,
+					));
+				}
+				continue;
+			}
+			if !first_batch {
+				TimeoutFuture::new(FEED_BATCH_DELAY).await;
+			}
+			first_batch = false;
+
+			let results = join_all(batch.iter().map(|(channel, _)| {
+				fetch_latest(server, &channel.id, rss, find_shorts, lang)
+			}))
+			.await;
+			for ((channel, feed), result) in batch.into_iter().zip(results) {
+				match result {
+					Ok(latest) => {
+						let feed =
+							CachedChannelFeed::merge(&channel.id, latest, feed);
+						let _ = feed.save().await;
+						feeds.push(feed);
+					}
+					Err(error) => {
+						rate_limited |=
+							matches!(error, RustyTubeError::RateLimited);
+						feeds.extend(feed);
+						failures.push(FeedFailure::new(channel, error));
+					}
+				}
+			}
+		}
+		SubscriptionsFeed::new(feeds, failures)
+	}
+}
+
+/// A channel's latest videos, and the ids of those that are shorts.
+struct LatestVideos {
+	videos: Vec<CommonVideo>,
+	shorts: Vec<String>,
+}
+
+/// Falls back to the API if the RSS feed fails to load. The response cache is
+/// bypassed, as the feed keeps its own. The API leaves shorts out of the
+/// channel's videos, so they are only looked up for RSS feeds.
+async fn fetch_latest(
+	server: &str,
+	id: &str,
+	rss: bool,
+	find_shorts: bool,
+	lang: &str,
+) -> Result<LatestVideos, RustyTubeError> {
+	if rss {
+		if let Ok(latest) = Feed::fetch_videos_from_feed(server, id).await {
+			let shorts = if find_shorts {
+				fetch_shorts(server, id, lang).await
+			} else {
+				Vec::new()
+			};
+			return Ok(LatestVideos { videos: latest.videos, shorts });
+		}
+	}
+	let url = format!("{server}/api/v1/channels/{id}/videos?hl={lang}");
+	let latest = HttpClient::default().get_json::<ChannelVideos>(&url).await?;
+	Ok(LatestVideos { videos: latest.videos, shorts: Vec::new() })
+}
+
+/// The ids of the channel's latest shorts, or none if they fail to load.
+async fn fetch_shorts(server: &str, id: &str, lang: &str) -> Vec<String> {
+	let url = format!("{server}/api/v1/channels/{id}/shorts?hl={lang}");
+	HttpClient::default()
+		.get_json::<ChannelShorts>(&url)
+		.await
+		.map(|shorts| shorts.shorts.into_iter().map(|short| short.id).collect())
+		.unwrap_or_default()
+}
@@ -1,7 +1,9 @@
+mod feed;
 mod newpipe;
 mod subscriptions;
 mod youtube;
 
+pub use feed::*;
 pub use newpipe::*;
 pub use subscriptions::*;
 pub use youtube::*;
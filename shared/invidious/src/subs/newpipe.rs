@@ -1,7 +1,7 @@
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 
-use super::subscriptions::{Subscription, Subscriptions};
+use super::subscriptions::{Subscription, SubscriptionGroup, Subscriptions};
 
 impl From<NewpipeSubscription> for Subscription {
 	fn from(val: NewpipeSubscription) -> Self {
@@ -24,7 +24,7 @@ impl From<NewpipeSubscriptions> for Subscriptions {
 			channels.push(sub);
 		});
 
-		Self { channels }
+		Self { channels, groups: val.groups }
 	}
 }
 
@@ -40,6 +40,10 @@ pub struct NewpipeSubscriptions {
 	pub app_version: String,
 	pub app_version_int: u32,
 	pub subscriptions: Vec<NewpipeSubscription>,
+	/// Not part of NewPipe's format, which keeps feed groups in its database
+	/// backup only. NewPipe and LibreTube ignore the field when importing.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub groups: Vec<SubscriptionGroup>,
 }
 
 impl NewpipeSubscriptions {
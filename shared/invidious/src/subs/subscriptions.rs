@@ -1,19 +1,19 @@
 use futures::future::join_all;
-use gloo::file::{
-	futures::{read_as_bytes, read_as_text},
-	Blob,
-};
+use gloo::file::{futures::read_as_text, Blob};
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-	Channel, ChannelThumb, ChannelVideos, Feed, NewpipeSubscription,
-	NewpipeSubscriptions, YoutubeSubscription, YoutubeSubscriptions,
+	Channel, ChannelThumb, ImportFormat, NewpipeSubscription,
+	NewpipeSubscriptions, ProfileImport, YoutubeSubscription,
+	YoutubeSubscriptions,
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
 pub struct Subscriptions {
 	pub channels: Vec<Subscription>,
+	#[serde(default)]
+	pub groups: Vec<SubscriptionGroup>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
@@ -28,58 +28,158 @@ impl Subscription {
 	}
 }
 
+/// A named set of channels with its own feed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct SubscriptionGroup {
+	pub name: String,
+	/// Ids of the subscribed channels in the group.
+	pub channels: Vec<String>,
+}
+
+impl SubscriptionGroup {
+	pub fn new(name: &str) -> Self {
+		Self { name: name.to_owned(), channels: Vec::new() }
+	}
+
+	pub fn contains(&self, id: &str) -> bool {
+		self.channels.iter().any(|channel| channel.eq_ignore_ascii_case(id))
+	}
+}
+
 pub const SUBS_KEY: &str = "subscriptions";
 
-pub type SubsVideosResult =
-	Result<Vec<Result<ChannelVideos, RustyTubeError>>, RustyTubeError>;
 pub type SubsThumbsResult =
 	Result<Vec<Result<ChannelThumb, RustyTubeError>>, RustyTubeError>;
 pub type SubsChannelsResult =
 	Result<Vec<Result<Channel, RustyTubeError>>, RustyTubeError>;
 
 impl Subscriptions {
+	/// Reads subscriptions from any supported export, detected by content.
+	///
+	/// # Errors
+	///
+	/// - File read error.
+	/// - Unrecognised export, or one without subscriptions.
 	pub async fn read_subs(blob: Blob) -> Result<Self, RustyTubeError> {
-		if blob.raw_mime_type().eq("text/csv") {
-			match read_youtube(&blob).await {
-				Ok(subs) => Ok(subs),
-				Err(_) => match read_newpipe(&blob).await {
-					Ok(subs) => Ok(subs),
-					Err(err) => Err(err),
-				},
-			}
-		} else {
-			match read_newpipe(&blob).await {
-				Ok(subs) => Ok(subs),
-				Err(_) => match read_youtube(&blob).await {
-					Ok(subs) => Ok(subs),
-					Err(err) => Err(err),
-				},
-			}
+		let text = read_as_text(&blob).await?;
+		let import = ProfileImport::parse(&text, "").await?;
+		match import.format {
+			ImportFormat::Invidious
+			| ImportFormat::NewPipe
+			| ImportFormat::YoutubeCsv
+			| ImportFormat::FreetubeSubscriptions => Ok(import.subscriptions),
+			_ => Err(RustyTubeError::ImportFormat),
 		}
 	}
 
-	pub async fn fetch_videos(
-		&self,
-		server: &str,
-		rss: bool,
-		lang: &str,
-	) -> SubsVideosResult {
-		let mut futures = Vec::new();
+	pub fn group(&self, name: &str) -> Option<&SubscriptionGroup> {
+		self.groups.iter().find(|group| group.name == name)
+	}
 
-		for channel in self.channels.clone() {
-			let id = channel.id.clone();
-			let future = async move {
-				if rss {
-					Feed::fetch_videos_from_feed(server, &id).await
-				} else {
-					Channel::fetch_channel_videos(server, &id, None, lang).await
+	/// The channels in `group`, or every channel when there is no group.
+	/// Groups are left out, so editing them does not change the result.
+	pub fn in_group(&self, group: Option<&str>) -> Self {
+		let group = group.map(|name| self.group(name));
+		let channels = self
+			.channels
+			.iter()
+			.filter(|channel| match group {
+				None => true,
+				Some(group) => {
+					group.is_some_and(|group| group.contains(&channel.id))
 				}
-			};
-			futures.push(future);
+			})
+			.cloned()
+			.collect();
+		Self { channels, groups: Vec::new() }
+	}
+
+	/// # Errors
+	///
+	/// - Empty name, or a group with the same name exists.
+	pub fn add_group(&mut self, name: &str) -> Result<(), RustyTubeError> {
+		let name = self.check_group_name(name)?;
+		self.groups.push(SubscriptionGroup::new(&name));
+		Ok(())
+	}
+
+	/// # Errors
+	///
+	/// - Empty name, or a group with the new name exists.
+	pub fn rename_group(
+		&mut self,
+		name: &str,
+		new_name: &str,
+	) -> Result<(), RustyTubeError> {
+		if new_name.trim() == name {
+			return Ok(());
+		}
+		let new_name = self.check_group_name(new_name)?;
+		if let Some(group) =
+			self.groups.iter_mut().find(|group| group.name == name)
+		{
+			group.name = new_name;
+		}
+		Ok(())
+	}
+
+	pub fn remove_group(&mut self, name: &str) {
+		self.groups.retain(|group| group.name != name);
+	}
+
+	/// Adds the channel to the group, or takes it out if it is already in it.
+	pub fn toggle_group_channel(&mut self, name: &str, id: &str) {
+		let Some(group) =
+			self.groups.iter_mut().find(|group| group.name == name)
+		else {
+			return;
+		};
+		if group.contains(id) {
+			group.channels.retain(|channel| !channel.eq_ignore_ascii_case(id));
+		} else {
+			group.channels.push(id.to_owned());
+		}
+	}
+
+	/// Unsubscribes from the channel, taking it out of every group.
+	pub fn remove_channel(&mut self, id: &str) {
+		self.channels.retain(|channel| !channel.id.eq_ignore_ascii_case(id));
+		for group in &mut self.groups {
+			group.channels.retain(|channel| !channel.eq_ignore_ascii_case(id));
+		}
+	}
+
+	fn check_group_name(&self, name: &str) -> Result<String, RustyTubeError> {
+		let name = name.trim();
+		if name.is_empty() {
+			return Err(RustyTubeError::GroupName);
+		}
+		if self.group(name).is_some() {
+			return Err(RustyTubeError::GroupExists);
 		}
-		let subs_videos = join_all(futures).await;
+		Ok(name.to_owned())
+	}
+
+	/// Looks up the names of channels imported by id only.
+	pub async fn fill_names(&mut self, server: &str) {
+		let unnamed: Vec<String> = self
+			.channels
+			.iter()
+			.filter(|channel| channel.name.is_empty())
+			.map(|channel| channel.id.clone())
+			.collect();
+		let thumbs = join_all(
+			unnamed.iter().map(|id| Channel::fetch_channel_thumb(server, id)),
+		)
+		.await;
 
-		Ok(subs_videos)
+		for thumb in thumbs.into_iter().flatten() {
+			if let Some(channel) =
+				self.channels.iter_mut().find(|channel| channel.id == thumb.id)
+			{
+				channel.name = thumb.name;
+			}
+		}
 	}
 
 	pub async fn fetch_channels(
@@ -113,19 +213,6 @@ impl Subscriptions {
 	}
 }
 
-async fn read_youtube(file: &Blob) -> Result<Subscriptions, RustyTubeError> {
-	let bytes = read_as_bytes(file).await?;
-	let slice = bytes.as_slice();
-	let yt_subs = YoutubeSubscriptions::read_subs_from_csv(slice)?;
-	Ok(yt_subs.into())
-}
-
-async fn read_newpipe(file: &Blob) -> Result<Subscriptions, RustyTubeError> {
-	let json_str = read_as_text(file).await?;
-	let newpipe_subs = NewpipeSubscriptions::read_subs_from_file(&json_str)?;
-	Ok(newpipe_subs.into())
-}
-
 impl From<Subscriptions> for NewpipeSubscriptions {
 	fn from(val: Subscriptions) -> Self {
 		let subscriptions = val
@@ -138,6 +225,7 @@ impl From<Subscriptions> for NewpipeSubscriptions {
 			app_version: "0.0.0".to_string(),
 			app_version_int: 0,
 			subscriptions,
+			groups: val.groups,
 		}
 	}
 }
@@ -146,7 +234,7 @@ impl From<Subscription> for NewpipeSubscription {
 	fn from(val: Subscription) -> Self {
 		let service_id = 0;
 		let name = val.name;
-		let url = format!("https:://youtube.com/channel/{}", val.id);
+		let url = format!("https://www.youtube.com/channel/{}", val.id);
 
 		Self { name, service_id, url }
 	}
@@ -167,7 +255,7 @@ impl From<Subscriptions> for YoutubeSubscriptions {
 impl From<Subscription> for YoutubeSubscription {
 	fn from(val: Subscription) -> Self {
 		let channel_id = val.id.clone();
-		let channel_url = format!("https:://youtube.com/channel/{}", val.id);
+		let channel_url = format!("https://www.youtube.com/channel/{}", val.id);
 		let channel_title = val.name;
 
 		Self { channel_id, channel_url, channel_title }
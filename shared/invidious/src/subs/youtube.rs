@@ -59,6 +59,6 @@ impl From<YoutubeSubscriptions> for Subscriptions {
 			channels.push(sub);
 		});
 
-		Self { channels }
+		Self { channels, ..Default::default() }
 	}
 }
@@ -0,0 +1,148 @@
+use crate::{resolve_import, ImportReport, ImportStrategy, LocalPlaylist};
+
+fn playlist(id: &str, title: &str, videos: &[&str]) -> LocalPlaylist {
+	let videos = videos.iter().map(ToString::to_string).collect();
+	LocalPlaylist::new(Some(id.to_owned()), title, videos, 0)
+}
+
+fn video_ids(playlist: &LocalPlaylist) -> Vec<&str> {
+	playlist.videos.iter().map(|video| video.id.as_str()).collect()
+}
+
+fn titles(playlists: &[LocalPlaylist]) -> Vec<&str> {
+	playlists.iter().map(|playlist| playlist.title.as_str()).collect()
+}
+
+#[test]
+fn adds_new_playlists() {
+	let existing = [playlist("a", "Music", &["1"])];
+	let imported = vec![playlist("b", "Podcasts", &["2", "3"])];
+
+	for strategy in [
+		ImportStrategy::Merge,
+		ImportStrategy::Replace,
+		ImportStrategy::KeepBoth,
+	] {
+		let import = resolve_import(&existing, imported.clone(), strategy);
+
+		assert_eq!(titles(&import.playlists), ["Music", "Podcasts"]);
+		assert_eq!(titles(&import.changed), ["Podcasts"]);
+		assert_eq!(
+			import.report,
+			ImportReport {
+				added: 1,
+				videos_added: 2,
+				..ImportReport::default()
+			}
+		);
+	}
+}
+
+#[test]
+fn merges_new_videos_into_title_collisions() {
+	let existing = [playlist("a", "Music", &["1", "2"])];
+	let imported = vec![playlist("b", " music ", &["2", "3"])];
+
+	let import = resolve_import(&existing, imported, ImportStrategy::Merge);
+
+	assert_eq!(import.playlists.len(), 1);
+	assert_eq!(import.playlists[0].id, "a");
+	assert_eq!(import.playlists[0].title, "Music");
+	assert_eq!(video_ids(&import.playlists[0]), ["1", "2", "3"]);
+	assert_eq!(import.playlists[0].video_count, 3);
+	assert_eq!(titles(&import.changed), ["Music"]);
+	assert_eq!(
+		import.report,
+		ImportReport { merged: 1, videos_added: 1, ..ImportReport::default() }
+	);
+}
+
+#[test]
+fn leaves_merged_playlists_without_new_videos_unchanged() {
+	let existing = [playlist("a", "Music", &["1", "2"])];
+	let imported = vec![playlist("a", "Music", &["2", "1"])];
+
+	let import = resolve_import(&existing, imported, ImportStrategy::Merge);
+
+	assert_eq!(video_ids(&import.playlists[0]), ["1", "2"]);
+	assert!(import.changed.is_empty());
+	assert_eq!(import.report, ImportReport::default());
+}
+
+#[test]
+fn replaces_videos_of_id_collisions() {
+	let existing = [
+		playlist("a", "Music", &["1", "2"]),
+		playlist("b", "Podcasts", &["3"]),
+	];
+	let imported = vec![playlist("a", "Renamed", &["4"])];
+
+	let import = resolve_import(&existing, imported, ImportStrategy::Replace);
+
+	assert_eq!(titles(&import.playlists), ["Music", "Podcasts"]);
+	assert_eq!(video_ids(&import.playlists[0]), ["4"]);
+	assert_eq!(import.playlists[0].video_count, 1);
+	assert_eq!(video_ids(&import.playlists[1]), ["3"]);
+	assert_eq!(titles(&import.changed), ["Music"]);
+	assert_eq!(
+		import.report,
+		ImportReport {
+			replaced: 1,
+			videos_added: 1,
+			..ImportReport::default()
+		}
+	);
+}
+
+#[test]
+fn keeps_both_with_numbered_titles() {
+	let existing =
+		[playlist("a", "Music", &["1"]), playlist("b", "Music (2)", &["2"])];
+	let imported = vec![
+		playlist("a", "Music", &["3"]),
+		playlist("c", "MUSIC", &["4", "5"]),
+	];
+
+	let import = resolve_import(&existing, imported, ImportStrategy::KeepBoth);
+
+	assert_eq!(
+		titles(&import.playlists),
+		["Music", "Music (2)", "Music (3)", "MUSIC (4)"]
+	);
+	assert_eq!(video_ids(&import.playlists[0]), ["1"]);
+	assert_eq!(video_ids(&import.playlists[2]), ["3"]);
+	assert_ne!(import.playlists[2].id, "a");
+	assert_ne!(import.playlists[3].id, "c");
+	assert_ne!(import.playlists[2].id, import.playlists[3].id);
+	assert_eq!(titles(&import.changed), ["Music (3)", "MUSIC (4)"]);
+	assert_eq!(
+		import.report,
+		ImportReport {
+			kept_both: 2,
+			videos_added: 3,
+			..ImportReport::default()
+		}
+	);
+}
+
+#[test]
+fn resolves_playlists_repeated_within_an_import() {
+	let imported = vec![
+		playlist("a", "Music", &["1"]),
+		playlist("b", "Music", &["1", "2"]),
+	];
+
+	let import = resolve_import(&[], imported, ImportStrategy::Merge);
+
+	assert_eq!(import.playlists.len(), 1);
+	assert_eq!(video_ids(&import.playlists[0]), ["1", "2"]);
+	assert_eq!(
+		import.report,
+		ImportReport {
+			added: 1,
+			merged: 1,
+			videos_added: 2,
+			..ImportReport::default()
+		}
+	);
+}
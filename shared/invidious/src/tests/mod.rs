@@ -1,3 +1,8 @@
+mod import;
+mod profile;
+mod takeout;
+mod webvtt;
+
 // #[cfg(test)]
 // mod tests {
 // 	use gloo::file::Blob;
@@ -0,0 +1,175 @@
+use crate::{
+	read_freetube_history, read_freetube_subscriptions, write_playlist_csv,
+	ImportFormat, InvidiousExport, LocalPlaylist, Subscription,
+	SubscriptionGroup,
+};
+
+const FREETUBE_SUBSCRIPTIONS: &str =
+	include_str!("./files/freetube-subscriptions.db");
+const FREETUBE_PROFILES: &str = include_str!("./files/freetube-profiles.db");
+const FREETUBE_HISTORY: &str = include_str!("./files/freetube-history.db");
+const INVIDIOUS_EXPORT: &str = include_str!("./files/invidious-export.json");
+
+#[test]
+fn detects_playlist_csv() {
+	let csv = include_str!("./files/playlist.csv");
+	assert_eq!(ImportFormat::detect(csv), Some(ImportFormat::PlaylistCsv));
+}
+
+#[test]
+fn detects_exported_playlist_csv() {
+	let playlist = LocalPlaylist::new(
+		Some(String::from("id")),
+		"Music",
+		vec![String::from("E2hZDzJp9Pc")],
+		0,
+	);
+	let csv = write_playlist_csv(&playlist).expect("playlist is written");
+	assert_eq!(ImportFormat::detect(&csv), Some(ImportFormat::PlaylistCsv));
+}
+
+#[test]
+fn detects_new_takeout_playlist_csv() {
+	let csv = "Video ID,Playlist Video Creation Timestamp\n\
+	           E2hZDzJp9Pc,2023-02-15T02:26:58+00:00\n";
+	assert_eq!(ImportFormat::detect(csv), Some(ImportFormat::PlaylistCsv));
+}
+
+#[test]
+fn detects_youtube_subscriptions_csv() {
+	let csv = include_str!("./files/subscriptions.csv");
+	assert_eq!(ImportFormat::detect(csv), Some(ImportFormat::YoutubeCsv));
+}
+
+#[test]
+fn detects_newpipe_subscriptions() {
+	let json = include_str!("./files/subscriptions.json");
+	assert_eq!(ImportFormat::detect(json), Some(ImportFormat::NewPipe));
+}
+
+#[test]
+fn detects_freetube_subscriptions() {
+	assert_eq!(
+		ImportFormat::detect(FREETUBE_SUBSCRIPTIONS),
+		Some(ImportFormat::FreetubeSubscriptions)
+	);
+}
+
+#[test]
+fn detects_freetube_playlists() {
+	let json = include_str!("./files/freetube_playlists.json");
+	assert_eq!(
+		ImportFormat::detect(json),
+		Some(ImportFormat::FreetubePlaylists)
+	);
+}
+
+#[test]
+fn detects_libretube_playlists() {
+	let json = include_str!("./files/libretube_playlists.json");
+	assert_eq!(
+		ImportFormat::detect(json),
+		Some(ImportFormat::LibretubePlaylists)
+	);
+}
+
+#[test]
+fn reads_freetube_subscriptions() {
+	let subscriptions = read_freetube_subscriptions(FREETUBE_SUBSCRIPTIONS);
+
+	assert_eq!(subscriptions.channels.len(), 168);
+	assert_eq!(
+		subscriptions.channels[0],
+		Subscription::new("UCtXuWXsd7TYXwX2ZNLXfojg", "pellekofficial")
+	);
+	assert_eq!(
+		subscriptions.groups,
+		[SubscriptionGroup {
+			name: String::from("test"),
+			channels: Vec::new(),
+		}]
+	);
+}
+
+#[test]
+fn reads_freetube_profiles_as_groups() {
+	let subscriptions = read_freetube_subscriptions(FREETUBE_PROFILES);
+
+	assert_eq!(
+		subscriptions.channels,
+		[
+			Subscription::new("UCRcgy6GzDeccI7dkbbBna3Q", "LEMMiNO"),
+			Subscription::new("UC8mnXeOlR9hC2w8aYqY9WNQ", "Loudwire"),
+			Subscription::new(
+				"UCJ05pLKdBCcZpaQlixNlZAg",
+				"Matthew kiichichaos Heafy"
+			),
+		]
+	);
+	// The second "Music" line replaces the first, and "Documentaries" is
+	// deleted.
+	assert_eq!(
+		subscriptions.groups,
+		[SubscriptionGroup {
+			name: String::from("Music"),
+			channels: vec![
+				String::from("UC8mnXeOlR9hC2w8aYqY9WNQ"),
+				String::from("UCJ05pLKdBCcZpaQlixNlZAg"),
+			],
+		}]
+	);
+}
+
+#[test]
+fn reads_freetube_history() {
+	let history = read_freetube_history(FREETUBE_HISTORY);
+	let ids: Vec<&str> =
+		history.iter().map(|entry| entry.id.as_str()).collect();
+
+	// The repeated line is newer, so it moves "E2hZDzJp9Pc" to the top, and
+	// "Ul0RaD5TxZk" is deleted.
+	assert_eq!(ids, ["E2hZDzJp9Pc", "sIaT8Jl2zpI"]);
+	assert_eq!(history[0].watched, 1_700_007_200);
+	assert!((history[0].progress - 1201f64).abs() < f64::EPSILON);
+	assert_eq!(history[0].length, 1830);
+	assert_eq!(history[0].author_id, "UCRcgy6GzDeccI7dkbbBna3Q");
+	assert_eq!(history[1].watched, 1_700_003_600);
+}
+
+#[test]
+fn reads_invidious_export() {
+	let export =
+		InvidiousExport::read(INVIDIOUS_EXPORT).expect("export is read");
+
+	let subscriptions = export.subscriptions();
+	assert_eq!(
+		subscriptions.channels,
+		[
+			Subscription::new("UCRcgy6GzDeccI7dkbbBna3Q", ""),
+			Subscription::new("UC8mnXeOlR9hC2w8aYqY9WNQ", ""),
+		]
+	);
+	assert!(subscriptions.groups.is_empty());
+
+	let playlists = export.playlists();
+	assert_eq!(playlists.len(), 2);
+	assert_eq!(playlists[0].title, "Metal");
+	assert!(playlists[0].contains("sIaT8Jl2zpI"));
+	assert!(playlists[0].contains("Ul0RaD5TxZk"));
+	assert_eq!(playlists[0].video_count, 2);
+	assert_eq!(playlists[1].title, "Watch later");
+	assert!(playlists[1].videos.is_empty());
+	assert_ne!(playlists[0].id, playlists[1].id);
+}
+
+#[test]
+fn keeps_invidious_history_order() {
+	let export =
+		InvidiousExport::read(INVIDIOUS_EXPORT).expect("export is read");
+	let history = export.history();
+
+	let ids: Vec<&str> =
+		history.iter().map(|entry| entry.id.as_str()).collect();
+	assert_eq!(ids, ["sIaT8Jl2zpI", "E2hZDzJp9Pc", "Ul0RaD5TxZk"]);
+	assert!(history.windows(2).all(|pair| pair[0].watched > pair[1].watched));
+}
@@ -0,0 +1,272 @@
+use futures::executor::block_on;
+use miniz_oxide::deflate::compress_to_vec;
+
+use crate::{read_takeout_history, read_zip, ProfileImport};
+
+const SUBSCRIPTIONS: &str = include_str!("./files/subscriptions.csv");
+const HISTORY: &str = r#"[
+	{
+		"header": "YouTube",
+		"title": "Watched First video",
+		"titleUrl": "https://www.youtube.com/watch?v=E2hZDzJp9Pc",
+		"subtitles": [{
+			"name": "pellekofficial",
+			"url": "https://www.youtube.com/channel/UCtXuWXsd7TYXwX2ZNLXfojg"
+		}],
+		"time": "2023-02-15T02:26:58.000Z"
+	},
+	{
+		"header": "YouTube",
+		"title": "Watched Second video",
+		"titleUrl": "https://www.youtube.com/watch?v=sjC9rxq0LMc&t=10",
+		"time": "2023-02-16T02:26:58.000Z"
+	},
+	{
+		"header": "YouTube",
+		"title": "Watched an ad",
+		"titleUrl": "https://www.youtube.com/watch?v=wsmHCfSZM70",
+		"time": "2023-02-17T02:26:58.000Z",
+		"details": [{ "name": "From Google Ads" }]
+	},
+	{
+		"header": "YouTube",
+		"title": "Watched a video that has been removed",
+		"time": "2023-02-18T02:26:58.000Z"
+	}
+]"#;
+
+struct TestFile<'a> {
+	path: &'a str,
+	contents: &'a [u8],
+	deflate: bool,
+}
+
+impl<'a> TestFile<'a> {
+	const fn stored(path: &'a str, contents: &'a str) -> Self {
+		Self { path, contents: contents.as_bytes(), deflate: false }
+	}
+
+	const fn deflated(path: &'a str, contents: &'a str) -> Self {
+		Self { path, contents: contents.as_bytes(), deflate: true }
+	}
+}
+
+/// Writes a zip archive, using zip64 records for every size and offset when
+/// `zip64` is set.
+#[allow(clippy::cast_possible_truncation)]
+fn build_zip(files: &[TestFile], zip64: bool) -> Vec<u8> {
+	let mut zip = Vec::new();
+	let mut directory = Vec::new();
+
+	for file in files {
+		let data = if file.deflate {
+			compress_to_vec(file.contents, 6)
+		} else {
+			file.contents.to_vec()
+		};
+		let method: u16 = if file.deflate {
+			8
+		} else {
+			0
+		};
+		let offset = zip.len() as u64;
+		let size = file.contents.len() as u64;
+		let compressed_size = data.len() as u64;
+		let name = file.path.as_bytes();
+
+		zip.extend(0x0403_4b50u32.to_le_bytes());
+		zip.extend(20u16.to_le_bytes());
+		zip.extend(0u16.to_le_bytes());
+		zip.extend(method.to_le_bytes());
+		zip.extend([0; 8]);
+		zip.extend((compressed_size as u32).to_le_bytes());
+		zip.extend((size as u32).to_le_bytes());
+		zip.extend((name.len() as u16).to_le_bytes());
+		zip.extend(0u16.to_le_bytes());
+		zip.extend(name);
+		zip.extend(&data);
+
+		let mut extra = Vec::new();
+		if zip64 {
+			extra.extend(0x0001u16.to_le_bytes());
+			extra.extend(24u16.to_le_bytes());
+			extra.extend(size.to_le_bytes());
+			extra.extend(compressed_size.to_le_bytes());
+			extra.extend(offset.to_le_bytes());
+		}
+		let field = |value: u64| {
+			if zip64 {
+				u32::MAX
+			} else {
+				value as u32
+			}
+		};
+
+		directory.extend(0x0201_4b50u32.to_le_bytes());
+		directory.extend(45u16.to_le_bytes());
+		directory.extend(45u16.to_le_bytes());
+		directory.extend(0u16.to_le_bytes());
+		directory.extend(method.to_le_bytes());
+		directory.extend([0; 8]);
+		directory.extend(field(compressed_size).to_le_bytes());
+		directory.extend(field(size).to_le_bytes());
+		directory.extend((name.len() as u16).to_le_bytes());
+		directory.extend((extra.len() as u16).to_le_bytes());
+		directory.extend([0; 10]);
+		directory.extend(field(offset).to_le_bytes());
+		directory.extend(name);
+		directory.extend(extra);
+	}
+
+	let directory_offset = zip.len() as u64;
+	let directory_size = directory.len() as u64;
+	let entries = files.len() as u64;
+	zip.extend(directory);
+
+	if zip64 {
+		let record_offset = zip.len() as u64;
+		zip.extend(0x0606_4b50u32.to_le_bytes());
+		zip.extend(44u64.to_le_bytes());
+		zip.extend(45u16.to_le_bytes());
+		zip.extend(45u16.to_le_bytes());
+		zip.extend([0; 8]);
+		zip.extend(entries.to_le_bytes());
+		zip.extend(entries.to_le_bytes());
+		zip.extend(directory_size.to_le_bytes());
+		zip.extend(directory_offset.to_le_bytes());
+
+		zip.extend(0x0706_4b50u32.to_le_bytes());
+		zip.extend(0u32.to_le_bytes());
+		zip.extend(record_offset.to_le_bytes());
+		zip.extend(1u32.to_le_bytes());
+	}
+
+	let (entries, directory_offset) = if zip64 {
+		(u16::MAX, u32::MAX)
+	} else {
+		(entries as u16, directory_offset as u32)
+	};
+	zip.extend(0x0605_4b50u32.to_le_bytes());
+	zip.extend([0; 4]);
+	zip.extend(entries.to_le_bytes());
+	zip.extend(entries.to_le_bytes());
+	zip.extend((directory_size as u32).to_le_bytes());
+	zip.extend(directory_offset.to_le_bytes());
+	zip.extend(0u16.to_le_bytes());
+	zip
+}
+
+fn takeout_files(deflate: bool) -> Vec<TestFile<'static>> {
+	let file = if deflate {
+		TestFile::deflated
+	} else {
+		TestFile::stored
+	};
+	vec![
+		file("Takeout/YouTube and YouTube Music/", ""),
+		file(
+			"Takeout/YouTube and YouTube Music/subscriptions/subscriptions.csv",
+			SUBSCRIPTIONS,
+		),
+		file(
+			"Takeout/YouTube and YouTube Music/history/watch-history.json",
+			HISTORY,
+		),
+		file("Takeout/archive_browser.html", "<html></html>"),
+	]
+}
+
+fn assert_takeout_entries(zip: &[u8]) {
+	let entries =
+		read_zip(zip, |path| path.ends_with(".csv") || path.ends_with(".json"))
+			.expect("zip is read");
+
+	assert_eq!(entries.len(), 2);
+	assert_eq!(entries[0].name(), "subscriptions.csv");
+	assert_eq!(entries[0].contents, SUBSCRIPTIONS.as_bytes());
+	assert_eq!(entries[1].name(), "watch-history.json");
+	assert_eq!(entries[1].contents, HISTORY.as_bytes());
+}
+
+#[test]
+fn reads_stored_zip() {
+	assert_takeout_entries(&build_zip(&takeout_files(false), false));
+}
+
+#[test]
+fn reads_deflated_zip() {
+	assert_takeout_entries(&build_zip(&takeout_files(true), false));
+}
+
+#[test]
+fn reads_zip64_zip() {
+	assert_takeout_entries(&build_zip(&takeout_files(true), true));
+}
+
+#[test]
+fn rejects_files_inflating_past_their_size() {
+	let mut zip = build_zip(&[TestFile::deflated("a.json", HISTORY)], false);
+	// Shrink the uncompressed size in the central directory header.
+	let header = zip
+		.windows(4)
+		.position(|window| window == 0x0201_4b50u32.to_le_bytes())
+		.expect("central directory header is written");
+	zip[header + 24..header + 28].copy_from_slice(&16u32.to_le_bytes());
+
+	assert!(read_zip(&zip, |_| true).is_err());
+}
+
+#[test]
+fn rejects_corrupt_archives() {
+	assert!(read_zip(b"not a zip", |_| true).is_err());
+
+	let zip = build_zip(&takeout_files(false), false);
+	assert!(read_zip(&zip[..zip.len() / 2], |_| true).is_err());
+
+	let mut zip = build_zip(&takeout_files(false), false);
+	let end = zip.len() - 22;
+	zip[end + 16..end + 20].copy_from_slice(&(u32::MAX - 1).to_le_bytes());
+	assert!(read_zip(&zip, |_| true).is_err());
+}
+
+#[test]
+fn reads_takeout_history() {
+	let history = read_takeout_history(HISTORY).expect("history is read");
+
+	let ids = history.iter().map(|entry| entry.id.as_str()).collect::<Vec<_>>();
+	assert_eq!(ids, ["sjC9rxq0LMc", "E2hZDzJp9Pc"]);
+	assert_eq!(history[0].title, "Second video");
+	assert_eq!(history[1].author, "pellekofficial");
+	assert_eq!(history[1].author_id, "UCtXuWXsd7TYXwX2ZNLXfojg");
+}
+
+#[test]
+fn reads_takeout_files() {
+	let files = vec![
+		(String::from("subscriptions.csv"), SUBSCRIPTIONS.to_string()),
+		(String::from("watch-history.json"), HISTORY.to_string()),
+		(String::from("playlists.csv"), String::from("Playlist ID,Title")),
+	];
+	let takeout =
+		block_on(ProfileImport::read_takeout_files(files)).expect("is read");
+
+	assert!(!takeout.subscriptions.channels.is_empty());
+	assert_eq!(takeout.history.len(), 2);
+	assert!(takeout.playlists.is_empty());
+}
+
+#[test]
+fn reads_takeout_zip() {
+	let zip = build_zip(&takeout_files(true), false);
+	let takeout =
+		block_on(ProfileImport::read_takeout_zip(&zip)).expect("is read");
+
+	assert!(!takeout.subscriptions.channels.is_empty());
+	assert_eq!(takeout.history.len(), 2);
+}
+
+#[test]
+fn rejects_takeout_without_exports() {
+	let files = vec![(String::from("notes.txt"), String::from("notes"))];
+	assert!(block_on(ProfileImport::read_takeout_files(files)).is_err());
+}
@@ -0,0 +1,73 @@
+use crate::{CaptionCue, CaptionTrack};
+
+const CAPTIONS: &str = include_str!("./files/captions.vtt");
+
+fn cue(start: f64, end: f64, text: &str) -> CaptionCue {
+	CaptionCue { start, end, text: text.to_owned() }
+}
+
+#[test]
+fn reads_invidious_captions() {
+	let track = CaptionTrack::parse(CAPTIONS).expect("captions are read");
+
+	assert_eq!(
+		track.cues,
+		[
+			cue(1.12, 4.08, "welcome back to the channel"),
+			cue(4.08, 7.2, "today we're looking at\nRust & WebAssembly"),
+			cue(8.5, 10.0, "short timestamps"),
+			cue(3600.0, 3602.0, "an hour in"),
+		]
+	);
+}
+
+#[test]
+fn reads_bom_and_crlf() {
+	let vtt = "\u{feff}WEBVTT\r\n\r\n00:00:01.000 --> 00:00:02.000\r\n\
+	           Hello\r\nthere\r\n";
+	let track = CaptionTrack::parse(vtt).expect("captions are read");
+
+	assert_eq!(track.cues, [cue(1.0, 2.0, "Hello\nthere")]);
+}
+
+#[test]
+fn reads_cue_ids_and_settings() {
+	let vtt = "WEBVTT - Title\n\n\
+	           intro\n00:01.000 --> 00:02.500 align:middle line:-1\nHi\n";
+	let track = CaptionTrack::parse(vtt).expect("captions are read");
+
+	assert_eq!(track.cues, [cue(1.0, 2.5, "Hi")]);
+}
+
+#[test]
+fn skips_malformed_timings() {
+	let vtt = "WEBVTT\n\n\
+	           -00:01.000 --> 00:02.000\nnegative\n\n\
+	           1:00:00:01.000 --> 1:00:00:02.000\ntoo many parts\n\n\
+	           00:aa.000 --> 00:02.000\nnot a number\n\n\
+	           00:03.000 --> \nno end\n";
+	let track = CaptionTrack::parse(vtt).expect("captions are read");
+
+	assert!(track.cues.is_empty());
+}
+
+#[test]
+fn rejects_missing_header() {
+	assert!(CaptionTrack::parse("").is_err());
+	assert!(CaptionTrack::parse("WEBVTTX\n").is_err());
+	assert!(
+		CaptionTrack::parse("00:00:01.000 --> 00:00:02.000\nHello\n").is_err()
+	);
+}
+
+#[test]
+fn finds_cues_at_time() {
+	let track = CaptionTrack::parse(CAPTIONS).expect("captions are read");
+
+	let texts = |time| {
+		track.cues_at(time).map(|cue| cue.text.as_str()).collect::<Vec<_>>()
+	};
+	assert_eq!(texts(0.5), Vec::<&str>::new());
+	assert_eq!(texts(4.08), ["today we're looking at\nRust & WebAssembly"]);
+	assert_eq!(texts(3601.0), ["an hour in"]);
+}
@@ -0,0 +1,181 @@
+use rustytube_error::RustyTubeError;
+use serde::{Deserialize, Serialize};
+use utils::{
+	get_unix_time, idb_clear, idb_delete, idb_get_all, idb_put, HISTORY_STORE,
+};
+
+use crate::Video;
+
+/// Oldest entries are dropped once the history grows past this size.
+pub const MAX_HISTORY_ENTRIES: usize = 2000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct WatchHistory {
+	/// Ordered from most to least recently watched.
+	pub entries: Vec<HistoryEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+	pub id: String,
+	pub title: String,
+	pub author: String,
+	#[serde(rename = "authorId")]
+	pub author_id: String,
+	pub thumbnail: String,
+	pub length: u32,
+	/// Unix timestamp in seconds.
+	pub watched: u64,
+	/// Playback position in seconds.
+	pub progress: f64,
+}
+
+impl HistoryEntry {
+	pub fn new(video: &Video) -> Self {
+		Self {
+			id: video.id.clone(),
+			title: video.title.clone(),
+			author: video.author.clone(),
+			author_id: video.author_id.clone(),
+			thumbnail: video
+				.thumbnails
+				.get(3)
+				.or_else(|| video.thumbnails.first())
+				.map(|thumb| thumb.url.clone())
+				.unwrap_or_default(),
+			length: video.length,
+			watched: get_unix_time(),
+			progress: 0f64,
+		}
+	}
+
+	/// An entry known only by its id, as in exports from other apps. The
+	/// rest is filled in by [`HistoryEntry::fill_details`].
+	pub fn from_id(id: &str, watched: u64) -> Self {
+		Self {
+			id: id.to_string(),
+			title: String::new(),
+			author: String::new(),
+			author_id: String::new(),
+			thumbnail: String::new(),
+			length: 0,
+			watched,
+			progress: 0f64,
+		}
+	}
+
+	pub fn has_details(&self) -> bool {
+		!self.thumbnail.is_empty()
+	}
+
+	/// Copies the details from `video`, keeping the watch time and progress.
+	pub fn fill_details(&mut self, video: &Video) {
+		let details = Self::new(video);
+		self.title = details.title;
+		self.author = details.author;
+		self.author_id = details.author_id;
+		self.thumbnail = details.thumbnail;
+		self.length = details.length;
+	}
+
+	pub async fn save(&self) -> Result<(), RustyTubeError> {
+		idb_put(HISTORY_STORE, &self.id, self).await
+	}
+
+	pub async fn delete(id: &str) -> Result<(), RustyTubeError> {
+		idb_delete(HISTORY_STORE, id).await
+	}
+}
+
+impl WatchHistory {
+	pub async fn load() -> Result<Self, RustyTubeError> {
+		let mut entries = idb_get_all::<HistoryEntry>(HISTORY_STORE).await?;
+		entries.sort_by(|a, b| b.watched.cmp(&a.watched));
+		Ok(Self { entries })
+	}
+
+	/// Deletes every entry from the database.
+	pub async fn delete_all() -> Result<(), RustyTubeError> {
+		idb_clear(HISTORY_STORE).await
+	}
+
+	/// Adds imported entries that are not in the history yet, keeping it
+	/// ordered by watch time.
+	///
+	/// Returns the entries added and the entries dropped to stay within
+	/// `MAX_HISTORY_ENTRIES`.
+	pub fn merge(
+		&mut self,
+		entries: Vec<HistoryEntry>,
+	) -> (Vec<HistoryEntry>, Vec<HistoryEntry>) {
+		let mut added: Vec<HistoryEntry> = entries
+			.into_iter()
+			.filter(|entry| !self.contains(&entry.id))
+			.collect();
+		self.entries.extend(added.iter().cloned());
+		self.entries.sort_by(|a, b| b.watched.cmp(&a.watched));
+		let dropped =
+			self.entries.split_off(MAX_HISTORY_ENTRIES.min(self.entries.len()));
+		added.retain(|entry| !dropped.iter().any(|old| old.id == entry.id));
+		(added, dropped)
+	}
+
+	/// Moves an already watched video back to the top, keeping its progress.
+	///
+	/// Returns the entries dropped to stay within `MAX_HISTORY_ENTRIES`.
+	pub fn add(&mut self, mut entry: HistoryEntry) -> Vec<HistoryEntry> {
+		if let Some(index) =
+			self.entries.iter().position(|existing| existing.id.eq(&entry.id))
+		{
+			entry.progress = self.entries.remove(index).progress;
+		}
+		self.entries.insert(0, entry);
+		self.entries.split_off(MAX_HISTORY_ENTRIES.min(self.entries.len()))
+	}
+
+	pub fn get(&self, id: &str) -> Option<&HistoryEntry> {
+		self.entries.iter().find(|entry| entry.id.eq(id))
+	}
+
+	pub fn get_mut(&mut self, id: &str) -> Option<&mut HistoryEntry> {
+		self.entries.iter_mut().find(|entry| entry.id.eq(id))
+	}
+
+	pub fn remove(&mut self, id: &str) {
+		self.entries.retain(|entry| !entry.id.eq(id));
+	}
+
+	pub fn clear(&mut self) {
+		self.entries.clear();
+	}
+
+	pub fn set_progress(&mut self, id: &str, progress: f64) {
+		if let Some(entry) =
+			self.entries.iter_mut().find(|entry| entry.id.eq(id))
+		{
+			entry.progress = progress;
+		}
+	}
+
+	pub fn contains(&self, id: &str) -> bool {
+		self.entries.iter().any(|entry| entry.id.eq(id))
+	}
+
+	/// Case-insensitive match against video titles and channel names.
+	pub fn search(&self, query: &str) -> Vec<HistoryEntry> {
+		let query = query.trim().to_lowercase();
+		self.entries
+			.iter()
+			.filter(|entry| {
+				query.is_empty()
+					|| entry.title.to_lowercase().contains(&query)
+					|| entry.author.to_lowercase().contains(&query)
+			})
+			.cloned()
+			.collect()
+	}
+
+	pub fn to_json_string(&self) -> Result<String, RustyTubeError> {
+		Ok(serde_json::to_string_pretty(self)?)
+	}
+}
@@ -1,16 +1,20 @@
 #![allow(unused_imports)]
 
+mod history;
 mod mixes;
 mod playlists;
 mod popular;
+mod profile;
 mod search;
 mod stats;
 mod suggestions;
 mod trending;
 
+pub use history::*;
 pub use mixes::*;
 pub use playlists::*;
 pub use popular::*;
+pub use profile::*;
 pub use search::*;
 pub use stats::*;
 pub use suggestions::*;
@@ -1,10 +1,9 @@
 use csv::StringRecord;
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
-use utils::get_current_time;
+use utils::get_unix_time;
 
 use crate::universal::playlists::LocalPlaylist;
-use crate::universal::playlists::LocalPlaylistItem;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CsvPlaylistItem {
@@ -21,62 +20,86 @@ pub struct CsvPlaylist {
 }
 
 impl From<CsvPlaylist> for LocalPlaylist {
-	#[allow(clippy::cast_possible_truncation)]
-	#[allow(clippy::cast_sign_loss)]
 	fn from(val: CsvPlaylist) -> Self {
-		let title = val.title;
-		let video_count = val.videos.len() as u32;
-		let updated = get_current_time().unwrap_or_default() as u64;
-		let created = updated;
-		let mut videos = Vec::new();
-
-		val.videos.into_iter().for_each(|video| {
-			videos.push(video.into());
-		});
-
-		Self { title, video_count, updated, created, videos }
-	}
-}
-
-impl From<CsvPlaylistItem> for LocalPlaylistItem {
-	fn from(val: CsvPlaylistItem) -> Self {
-		let id = val.id;
-		Self { id }
+		let ids = val.videos.into_iter().map(|video| video.id).collect();
+		Self::new(None, &val.title, ids, get_unix_time())
 	}
 }
 
+/// Reads a Takeout playlist CSV. Older Takeouts start with a metadata
+/// header and row before the videos; newer ones hold only the videos.
 pub async fn read_playlist_csv(
 	title: &str,
 	playlist_csv_bytes: &[u8],
 ) -> Result<LocalPlaylist, RustyTubeError> {
 	let mut playlist_csv = csv::ReaderBuilder::new()
+		.has_headers(false)
 		.flexible(true)
 		.from_reader(playlist_csv_bytes);
 	let mut playlist_items: Vec<CsvPlaylistItem> = Vec::new();
 
-	let _playlist_header = StringRecord::from(vec![
+	let playlist_videos_header =
+		StringRecord::from(vec!["Video ID", "Time Added"]);
+
+	let mut in_videos = false;
+	for record in playlist_csv.records() {
+		let mut record = record?;
+		let first = record.get(0).map(str::trim).unwrap_or_default();
+		if first.eq_ignore_ascii_case("Video ID") {
+			in_videos = true;
+		} else if in_videos && !first.is_empty() {
+			record.trim();
+			let playlist_item: CsvPlaylistItem =
+				record.deserialize(Some(&playlist_videos_header))?;
+			playlist_items.push(playlist_item);
+		}
+	}
+
+	Ok(CsvPlaylist { title: title.to_string(), videos: playlist_items }.into())
+}
+
+/// Serialises a playlist in the YouTube Takeout CSV layout: a metadata
+/// header and row, then one `Video ID`/`Time Added` row per video.
+pub fn write_playlist_csv(
+	playlist: &LocalPlaylist,
+) -> Result<String, RustyTubeError> {
+	let mut writer =
+		csv::WriterBuilder::new().flexible(true).from_writer(vec![]);
+	let time = utc_time(playlist.updated);
+
+	writer.write_record([
 		"Playlist ID",
 		"Channel ID",
 		"Time Created",
 		"Time Updated",
+		"Title",
 		"Description",
 		"Visibility",
-	]);
-	let playlist_videos_header =
-		StringRecord::from(vec!["Video ID", "Time Added"]);
-
-	let mut index = 0;
-	for (mut index, record) in playlist_csv.records().enumerate() {
-		match index {
-			0 | 1 => {}
-			_ => {
-				let playlist_item: CsvPlaylistItem =
-					record?.deserialize(Some(&playlist_videos_header))?;
-				playlist_items.push(playlist_item);
-			}
-		}
-		index += 1;
+	])?;
+	writer.write_record([
+		"",
+		"",
+		&time,
+		&time,
+		&playlist.title,
+		"",
+		"Private",
+	])?;
+	writer.write_record(["Video ID", "Time Added"])?;
+	for video in &playlist.videos {
+		writer.write_record([video.id.as_str(), &time])?;
 	}
 
-	Ok(CsvPlaylist { title: title.to_string(), videos: playlist_items }.into())
+	let bytes = writer
+		.into_inner()
+		.map_err(|err| RustyTubeError::Csv(err.to_string()))?;
+	String::from_utf8(bytes).map_err(|err| RustyTubeError::Csv(err.to_string()))
+}
+
+fn utc_time(secs: u64) -> String {
+	i64::try_from(secs)
+		.ok()
+		.and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+		.map(|time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+		.unwrap_or_default()
 }
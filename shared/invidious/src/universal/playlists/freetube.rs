@@ -1,8 +1,8 @@
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
-use utils::get_current_time;
+use utils::get_unix_time;
 
-use crate::{LocalPlaylist, LocalPlaylistItem, LOCAL_PLAYLIST_PREFIX};
+use crate::{LocalPlaylist, Video};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FreetubePlaylists {
@@ -11,9 +11,19 @@ pub struct FreetubePlaylists {
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FreetubePlaylist {
+	#[serde(rename = "_id", default, skip_serializing_if = "Option::is_none")]
+	pub id: Option<String>,
 	#[serde(rename = "playlistName")]
 	pub playlist_name: String,
 	pub videos: Vec<FreetubePlaylistItem>,
+	#[serde(default)]
+	pub protected: bool,
+	#[serde(default)]
+	pub description: String,
+	#[serde(rename = "createdAt", default)]
+	pub created_at: u64,
+	#[serde(rename = "lastUpdatedAt", default)]
+	pub last_updated_at: u64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -24,14 +34,32 @@ pub struct FreetubePlaylistItem {
 	pub author: String,
 	#[serde(rename = "authorId")]
 	pub author_id: String,
+	#[serde(rename = "lengthSeconds", default)]
+	pub length: u32,
+	#[serde(rename = "timeAdded", default)]
+	pub time_added: u64,
+	#[serde(rename = "type", default = "video_type")]
+	pub item_type: String,
 }
 
+fn video_type() -> String {
+	String::from("video")
+}
+
+/// Reads either a JSON array of playlists or FreeTube's `playlists.db`,
+/// which stores one playlist object per line.
 pub async fn read_freetube_playlists(
 	playlist_json: &str,
 ) -> Result<Vec<LocalPlaylist>, RustyTubeError> {
-	// let playlists: FreetubePlaylists = serde_json::from_str(playlist_json)?;
-	// Ok(playlists.into()
-	let playlists: Vec<FreetubePlaylist> = serde_json::from_str(playlist_json)?;
+	let playlists: Vec<FreetubePlaylist> =
+		match serde_json::from_str(playlist_json) {
+			Ok(playlists) => playlists,
+			Err(_) => playlist_json
+				.lines()
+				.filter(|line| !line.trim().is_empty())
+				.map(serde_json::from_str)
+				.collect::<Result<_, _>>()?,
+		};
 	let mut local_playlists = Vec::new();
 	playlists
 		.into_iter()
@@ -52,26 +80,76 @@ impl From<FreetubePlaylists> for Vec<LocalPlaylist> {
 }
 
 impl From<FreetubePlaylist> for LocalPlaylist {
-	#[allow(clippy::cast_possible_truncation)]
-	#[allow(clippy::cast_sign_loss)]
 	fn from(val: FreetubePlaylist) -> Self {
-		let title = format!("{}{}", LOCAL_PLAYLIST_PREFIX, &val.playlist_name);
-		let video_count = val.videos.len() as u32;
-		let updated = get_current_time().unwrap_or_default() as u64;
-		let created = updated;
-		let mut videos = Vec::new();
+		let created = match val.created_at / 1000 {
+			0 => get_unix_time(),
+			created => created,
+		};
+		let ids = val.videos.into_iter().map(|video| video.id).collect();
+		let mut playlist = Self::new(val.id, &val.playlist_name, ids, created);
+		playlist.updated = playlist.updated.max(val.last_updated_at / 1000);
+		playlist
+	}
+}
 
-		val.videos.into_iter().for_each(|video| {
-			videos.push(video.into());
-		});
+/// Serialises playlists in FreeTube's `playlists.db` format.
+///
+/// FreeTube needs the title and author of every item, so the videos are
+/// fetched from `server`; items that fail to load keep only their id.
+pub async fn write_freetube_playlists(
+	server: &str,
+	playlists: &[LocalPlaylist],
+) -> Result<String, RustyTubeError> {
+	let mut lines = Vec::new();
+	for playlist in playlists {
+		let freetube = FreetubePlaylist::from_local(server, playlist).await;
+		lines.push(serde_json::to_string(&freetube)?);
+	}
+	Ok(lines.join("\n"))
+}
+
+impl FreetubePlaylist {
+	async fn from_local(server: &str, playlist: &LocalPlaylist) -> Self {
+		let ids: Vec<String> =
+			playlist.videos.iter().map(|video| video.id.clone()).collect();
+		let fetched = LocalPlaylist::fetch_videos(server, &ids).await;
+		let created = playlist.created.saturating_mul(1000);
+		let updated = playlist.updated.saturating_mul(1000);
+
+		let videos = ids
+			.into_iter()
+			.zip(fetched)
+			.map(|(id, video)| {
+				FreetubePlaylistItem::new(id, video.ok(), updated)
+			})
+			.collect();
 
-		Self { title, video_count, updated, created, videos }
+		Self {
+			id: Some(playlist.id.clone()),
+			playlist_name: playlist.title.clone(),
+			videos,
+			protected: false,
+			description: String::new(),
+			created_at: created,
+			last_updated_at: updated,
+		}
 	}
 }
 
-impl From<FreetubePlaylistItem> for LocalPlaylistItem {
-	fn from(val: FreetubePlaylistItem) -> Self {
-		let id = val.id;
-		Self { id }
+impl FreetubePlaylistItem {
+	fn new(id: String, video: Option<Video>, time_added: u64) -> Self {
+		let (title, author, author_id, length) = video.map_or_else(
+			|| (String::new(), String::new(), String::new(), 0),
+			|video| (video.title, video.author, video.author_id, video.length),
+		);
+		Self {
+			id,
+			title,
+			author,
+			author_id,
+			length,
+			time_added,
+			item_type: video_type(),
+		}
 	}
 }
@@ -0,0 +1,110 @@
+use std::collections::HashSet;
+
+use utils::new_id;
+
+use crate::universal::LocalPlaylist;
+
+/// What to do with an imported playlist that has the same id or title as
+/// one that already exists.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImportStrategy {
+	/// Append the videos that are not in the existing playlist yet.
+	#[default]
+	Merge,
+	/// Swap the existing videos for the imported ones.
+	Replace,
+	/// Add the import as a separate playlist with a numbered title.
+	KeepBoth,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImportReport {
+	pub added: usize,
+	pub merged: usize,
+	pub replaced: usize,
+	pub kept_both: usize,
+	pub videos_added: usize,
+}
+
+pub struct PlaylistImport {
+	/// Every playlist after the import.
+	pub playlists: Vec<LocalPlaylist>,
+	/// The playlists that were added or modified and need saving.
+	pub changed: Vec<LocalPlaylist>,
+	pub report: ImportReport,
+}
+
+/// Combines imported playlists with the existing ones, resolving conflicts
+/// with `strategy`.
+pub fn resolve_import(
+	existing: &[LocalPlaylist],
+	imported: Vec<LocalPlaylist>,
+	strategy: ImportStrategy,
+) -> PlaylistImport {
+	let mut playlists = existing.to_vec();
+	let mut changed = HashSet::new();
+	let mut report = ImportReport::default();
+
+	for mut playlist in imported {
+		let conflict = playlists.iter().position(|existing| {
+			existing.id == playlist.id || existing.has_title(&playlist.title)
+		});
+
+		let Some(index) = conflict else {
+			report.added += 1;
+			report.videos_added += playlist.videos.len();
+			changed.insert(playlist.id.clone());
+			playlists.push(playlist);
+			continue;
+		};
+
+		match strategy {
+			ImportStrategy::Merge => {
+				let existing = &mut playlists[index];
+				let added = playlist
+					.videos
+					.iter()
+					.filter(|video| existing.add_video(&video.id))
+					.count();
+				if added > 0 {
+					report.merged += 1;
+					report.videos_added += added;
+					changed.insert(existing.id.clone());
+				}
+			}
+			ImportStrategy::Replace => {
+				let existing = &mut playlists[index];
+				report.replaced += 1;
+				report.videos_added += playlist.videos.len();
+				existing.videos = playlist.videos;
+				existing.touch();
+				changed.insert(existing.id.clone());
+			}
+			ImportStrategy::KeepBoth => {
+				playlist.id = new_id();
+				playlist.title = unique_title(&playlists, &playlist.title);
+				report.kept_both += 1;
+				report.videos_added += playlist.videos.len();
+				changed.insert(playlist.id.clone());
+				playlists.push(playlist);
+			}
+		}
+	}
+
+	let changed = playlists
+		.iter()
+		.filter(|playlist| changed.contains(&playlist.id))
+		.cloned()
+		.collect();
+
+	PlaylistImport { playlists, changed, report }
+}
+
+fn unique_title(playlists: &[LocalPlaylist], title: &str) -> String {
+	(2..)
+		.map(|count| format!("{title} ({count})"))
+		.find(|candidate| {
+			!playlists.iter().any(|playlist| playlist.has_title(candidate))
+		})
+		.unwrap_or_else(|| title.to_string())
+}
@@ -1,10 +1,8 @@
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
-use utils::get_current_time;
+use utils::get_unix_time;
 
-use crate::universal::{
-	LocalPlaylist, LocalPlaylistItem, LOCAL_PLAYLIST_PREFIX,
-};
+use crate::universal::{LocalPlaylist, YOUTUBE_WATCH_URL};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct LibretubePlaylists {
@@ -33,20 +31,13 @@ impl From<LibretubePlaylists> for Vec<LocalPlaylist> {
 }
 
 impl From<LibretubePlaylist> for LocalPlaylist {
-	#[allow(clippy::cast_possible_truncation)]
-	#[allow(clippy::cast_sign_loss)]
 	fn from(val: LibretubePlaylist) -> Self {
-		let title = format!("{}{}", LOCAL_PLAYLIST_PREFIX, val.name);
-		let video_count = val.videos.len() as u32;
-		let updated = get_current_time().unwrap_or_default() as u64;
-		let created = updated;
-
-		let mut videos: Vec<LocalPlaylistItem> = Vec::new();
-		val.videos
-			.into_iter()
-			.for_each(|video| videos.push(LocalPlaylistItem { id: video }));
-
-		Self { title, video_count, updated, created, videos }
+		let ids = val
+			.videos
+			.iter()
+			.map(|video| video.rsplit("v=").next().unwrap_or(video).to_string())
+			.collect();
+		Self::new(None, &val.name, ids, get_unix_time())
 	}
 }
 
@@ -56,3 +47,30 @@ pub async fn read_libretube_playlists(
 	let playlists: LibretubePlaylists = serde_json::from_str(playlist_json)?;
 	Ok(playlists.into())
 }
+
+/// Serialises playlists in the Piped format LibreTube imports and exports.
+pub fn write_libretube_playlists(
+	playlists: &[LocalPlaylist],
+) -> Result<String, RustyTubeError> {
+	let playlists = LibretubePlaylists {
+		format: String::from("Piped"),
+		version: 1,
+		playlists: playlists.iter().map(LibretubePlaylist::from).collect(),
+	};
+	Ok(serde_json::to_string_pretty(&playlists)?)
+}
+
+impl From<&LocalPlaylist> for LibretubePlaylist {
+	fn from(val: &LocalPlaylist) -> Self {
+		Self {
+			name: val.title.clone(),
+			playlist_type: String::from("playlist"),
+			visibility: String::from("private"),
+			videos: val
+				.videos
+				.iter()
+				.map(|video| format!("{YOUTUBE_WATCH_URL}{}", video.id))
+				.collect(),
+		}
+	}
+}
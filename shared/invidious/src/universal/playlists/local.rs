@@ -1,32 +1,21 @@
 use futures::future::join_all;
-use gloo::file::{
-	futures::{read_as_bytes, read_as_text},
-	Blob,
-};
+use gloo::file::File;
 
 use rustytube_error::RustyTubeError;
 use serde::{Deserialize, Serialize};
 use utils::{
-	get_current_time_rfc, load_all_from_browser_storage,
-	save_to_browser_storage,
+	get_unix_time, idb_delete, idb_get_all, idb_put, new_id, PLAYLISTS_STORE,
 };
 
-use crate::{
-	fetch,
-	universal::{
-		playlists::{
-			freetube::read_freetube_playlists,
-			libretube::read_libretube_playlists,
-		},
-		read_playlist_csv,
-	},
-	CommonThumbnail, Video,
-};
+use crate::{fetch, CommonThumbnail, ImportFormat, ProfileImport, Video};
 
-pub const LOCAL_PLAYLIST_PREFIX: &str = "rt_playlist_";
+pub const YOUTUBE_WATCH_URL: &str = "https://www.youtube.com/watch?v=";
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct LocalPlaylist {
+	/// Storage key, kept when the playlist is renamed.
+	#[serde(default)]
+	pub id: String,
 	pub title: String,
 	#[serde(rename = "videoCount")]
 	pub video_count: u32,
@@ -38,7 +27,7 @@ pub struct LocalPlaylist {
 
 impl PartialEq for LocalPlaylist {
 	fn eq(&self, other: &Self) -> bool {
-		self.created == other.created
+		self.id == other.id
 	}
 }
 
@@ -62,47 +51,98 @@ pub struct LocalPlaylistVideo {
 }
 
 impl LocalPlaylist {
-	#[allow(clippy::cast_possible_truncation)]
-	#[allow(clippy::cast_sign_loss)]
 	pub fn create(title: &str) -> Result<Self, RustyTubeError> {
-		let title = title.to_string();
-		let video_count = 0;
-		let updated = 0;
-		let videos: Vec<LocalPlaylistItem> = Vec::new();
-		let created = utils::get_current_time().unwrap_or_default() as u64;
+		Ok(Self::new(None, title, Vec::new(), get_unix_time()))
+	}
 
-		// let performance = gloo::utils::window().performance();
+	/// Builds a playlist from imported data, keeping the source id if there
+	/// is one and dropping repeated videos.
+	pub fn new(
+		id: Option<String>,
+		title: &str,
+		ids: Vec<String>,
+		created: u64,
+	) -> Self {
+		let mut playlist = Self {
+			id: id.filter(|id| !id.is_empty()).unwrap_or_else(new_id),
+			title: title.to_string(),
+			video_count: 0,
+			updated: created,
+			created,
+			videos: Vec::new(),
+		};
+		for id in ids {
+			playlist.add_video(&id);
+		}
+		playlist.updated = created;
+		playlist
+	}
 
-		Ok(Self { title, video_count, updated, created, videos })
+	pub async fn save(&self) -> Result<(), RustyTubeError> {
+		idb_put(PLAYLISTS_STORE, &self.id, self).await
 	}
 
-	pub fn save(&self) -> Result<(), RustyTubeError> {
-		let playlist_json = serde_json::to_string_pretty(&self)?;
-		let key = format!("{}{}", LOCAL_PLAYLIST_PREFIX, &self.title);
+	/// Removes the stored playlist with the given id.
+	pub async fn delete(id: &str) -> Result<(), RustyTubeError> {
+		idb_delete(PLAYLISTS_STORE, id).await
+	}
 
-		save_to_browser_storage(&key, &playlist_json)?;
-		Ok(())
+	/// Appends a video, returning `false` when it is already in the playlist.
+	pub fn add_video(&mut self, id: &str) -> bool {
+		if self.contains(id) {
+			return false;
+		}
+		self.videos.push(LocalPlaylistItem { id: id.to_string() });
+		self.touch();
+		true
 	}
 
-	pub fn load_local_playlists() -> Result<Vec<Self>, RustyTubeError> {
-		let mut playlists_vec: Vec<Self> = Vec::new();
+	pub fn remove_video(&mut self, id: &str) {
+		self.videos.retain(|video| video.id != id);
+		self.touch();
+	}
 
-		let storage_map = load_all_from_browser_storage()?;
-		for item in &storage_map {
-			if item.0.starts_with(LOCAL_PLAYLIST_PREFIX) {
-				if let Ok(playlist) = serde_json::from_value(item.1.to_owned())
-				{
-					playlists_vec.push(playlist);
-				}
-			};
+	/// Moves the item at `from` to position `to`, clamping `to` to the end.
+	pub fn move_video(&mut self, from: usize, to: usize) {
+		if from >= self.videos.len() || from == to {
+			return;
 		}
+		let video = self.videos.remove(from);
+		let to = to.min(self.videos.len());
+		self.videos.insert(to, video);
+		self.touch();
+	}
+
+	pub fn rename(&mut self, title: &str) {
+		self.title = title.to_string();
+		self.touch();
+	}
+
+	/// Titles are compared trimmed and ignoring case, so "Music" and " music"
+	/// are the same playlist.
+	pub fn has_title(&self, title: &str) -> bool {
+		self.title.trim().eq_ignore_ascii_case(title.trim())
+	}
 
-		Ok(playlists_vec)
+	pub fn contains(&self, id: &str) -> bool {
+		self.videos.iter().any(|video| video.id == id)
 	}
 
-	pub fn save_playlists(playlists: &Vec<Self>) -> Result<(), RustyTubeError> {
+	#[allow(clippy::cast_possible_truncation)]
+	pub(crate) fn touch(&mut self) {
+		self.video_count = self.videos.len() as u32;
+		self.updated = get_unix_time();
+	}
+
+	pub async fn load_local_playlists() -> Result<Vec<Self>, RustyTubeError> {
+		idb_get_all::<Self>(PLAYLISTS_STORE).await
+	}
+
+	pub async fn save_playlists(
+		playlists: &[Self],
+	) -> Result<(), RustyTubeError> {
 		for playlist in playlists {
-			playlist.save()?;
+			playlist.save().await?;
 		}
 		Ok(())
 	}
@@ -111,15 +151,8 @@ impl LocalPlaylist {
 		&self,
 		server: &str,
 	) -> Result<Video, RustyTubeError> {
-		let video_url = format!(
-			"{}/api/v1/videos/{}/",
-			server,
-			self.videos
-				.first()
-				.cloned()
-				.expect("Playlist should not be empty.")
-				.id
-		);
+		let first = self.videos.first().ok_or(RustyTubeError::EmptyPlaylist)?;
+		let video_url = format!("{}/api/v1/videos/{}/", server, first.id);
 		let video_json = fetch(&video_url).await?;
 		Ok(serde_json::from_str::<Video>(&video_json)?)
 	}
@@ -127,13 +160,21 @@ impl LocalPlaylist {
 	pub async fn fetch_playlist_videos(
 		&self,
 		server: &str,
+	) -> Vec<Result<Video, RustyTubeError>> {
+		let ids = self.videos.iter().map(|video| video.id.clone());
+		Self::fetch_videos(server, &ids.collect::<Vec<String>>()).await
+	}
+
+	/// Fetches the given videos in order, one result per id.
+	pub async fn fetch_videos(
+		server: &str,
+		ids: &[String],
 	) -> Vec<Result<Video, RustyTubeError>> {
 		let mut videos = vec![];
 
-		for video in self.videos.clone() {
+		for id in ids {
 			let future = async move {
-				let video_url =
-					format!("{}/api/v1/videos/{}/", server, video.id);
+				let video_url = format!("{server}/api/v1/videos/{id}/");
 				let video_json = fetch(&video_url).await?;
 				Ok(serde_json::from_str::<Video>(&video_json)?)
 			};
@@ -143,89 +184,24 @@ impl LocalPlaylist {
 		join_all(videos).await
 	}
 
+	/// Parses the playlists from any supported export, detected by content,
+	/// without saving them; see [`resolve_import`](crate::resolve_import)
+	/// for merging the result.
+	///
 	/// # Errors
 	///
-	/// - Playlist parse error.
+	/// - File read error.
+	/// - Unrecognised export, or one without playlists.
 	pub async fn read_playlists(
-		file: Blob,
+		file: File,
 	) -> Result<Vec<Self>, RustyTubeError> {
-		let mime = file.raw_mime_type();
-
-		let mut local_playlists: Vec<Self> = Vec::new();
-
-		if mime.eq_ignore_ascii_case("text/csv") {
-			match read_csv(&file).await {
-				Ok(playlist) => {
-					local_playlists.push(playlist);
-					Self::save_playlists(&local_playlists)?;
-					Ok(local_playlists)
-				}
-				Err(_) => match read_freetube(&file).await {
-					Ok(mut playlists) => {
-						local_playlists.append(&mut playlists);
-						Self::save_playlists(&local_playlists)?;
-						Ok(local_playlists)
-					}
-					Err(_) => match read_libretube(&file).await {
-						Ok(mut playlists) => {
-							local_playlists.append(&mut playlists);
-							Self::save_playlists(&local_playlists)?;
-							Ok(local_playlists)
-						}
-						Err(_) => Err(RustyTubeError::PlaylistParse),
-					},
-				},
-			}
-		} else {
-			match read_freetube(&file).await {
-				Ok(mut playlists) => {
-					local_playlists.append(&mut playlists);
-					Self::save_playlists(&local_playlists)?;
-					Ok(local_playlists)
-				}
-				Err(_) => match read_libretube(&file).await {
-					Ok(mut playlists) => {
-						local_playlists.append(&mut playlists);
-						Self::save_playlists(&local_playlists)?;
-						Ok(local_playlists)
-					}
-					Err(_) => match read_csv(&file).await {
-						Ok(playlist) => {
-							local_playlists.push(playlist);
-							Self::save_playlists(&local_playlists)?;
-							Ok(local_playlists)
-						}
-						Err(_) => Err(RustyTubeError::PlaylistParse),
-					},
-				},
-			}
+		let import = ProfileImport::read(&file).await?;
+		match import.format {
+			ImportFormat::Invidious
+			| ImportFormat::FreetubePlaylists
+			| ImportFormat::LibretubePlaylists
+			| ImportFormat::PlaylistCsv => Ok(import.playlists),
+			_ => Err(RustyTubeError::PlaylistParse),
 		}
 	}
 }
-
-async fn read_csv(file: &Blob) -> Result<LocalPlaylist, RustyTubeError> {
-	let bytes = read_as_bytes(file).await?;
-	let slice = bytes.as_slice();
-	let playlist = read_playlist_csv(&get_current_time_rfc()?, slice).await?;
-	Ok(playlist)
-}
-
-async fn read_libretube(
-	file: &Blob,
-) -> Result<Vec<LocalPlaylist>, RustyTubeError> {
-	let mut local_playlists: Vec<LocalPlaylist> = Vec::new();
-	let json_string = read_as_text(file).await?;
-	let mut playlists = read_libretube_playlists(&json_string).await?;
-	local_playlists.append(&mut playlists);
-	Ok(local_playlists)
-}
-
-async fn read_freetube(
-	file: &Blob,
-) -> Result<Vec<LocalPlaylist>, RustyTubeError> {
-	let mut local_playlists: Vec<LocalPlaylist> = Vec::new();
-	let json_string = read_as_text(file).await?;
-	let mut playlists = read_freetube_playlists(&json_string).await?;
-	local_playlists.append(&mut playlists);
-	Ok(local_playlists)
-}
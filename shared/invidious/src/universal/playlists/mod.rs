@@ -1,10 +1,12 @@
 mod csv_playlist;
 mod freetube;
+mod import;
 mod libretube;
 mod local;
 
 pub use csv_playlist::*;
 pub use freetube::*;
+pub use import::*;
 pub use libretube::*;
 pub use local::*;
 
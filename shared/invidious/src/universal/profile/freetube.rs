@@ -0,0 +1,212 @@
+use rustytube_error::RustyTubeError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::newest_first;
+use crate::{HistoryEntry, Subscription, SubscriptionGroup, Subscriptions};
+
+/// The id of the profile FreeTube always has, holding every subscription.
+const FREETUBE_ALL_CHANNELS: &str = "allChannels";
+const FREETUBE_ALL_CHANNELS_NAME: &str = "All Channels";
+
+/// A channel from `profiles.db`, or a line of the older `subscriptions.db`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FreetubeChannel {
+	#[serde(alias = "channelId")]
+	pub id: String,
+	#[serde(alias = "channelName", default)]
+	pub name: String,
+	#[serde(default)]
+	pub thumbnail: String,
+}
+
+impl From<&Subscription> for FreetubeChannel {
+	fn from(val: &Subscription) -> Self {
+		Self {
+			id: val.id.clone(),
+			name: val.name.clone(),
+			thumbnail: String::new(),
+		}
+	}
+}
+
+/// A line of FreeTube's `profiles.db`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FreetubeProfile {
+	#[serde(rename = "_id")]
+	pub id: String,
+	pub name: String,
+	#[serde(rename = "bgColor", default)]
+	pub bg_color: String,
+	#[serde(rename = "textColor", default)]
+	pub text_color: String,
+	#[serde(default)]
+	pub subscriptions: Vec<FreetubeChannel>,
+}
+
+impl Default for FreetubeProfile {
+	fn default() -> Self {
+		Self {
+			id: String::new(),
+			name: String::new(),
+			bg_color: "#000000".to_string(),
+			text_color: "#FFFFFF".to_string(),
+			subscriptions: Vec::new(),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FreetubeHistoryEntry {
+	#[serde(rename = "videoId")]
+	pub id: String,
+	#[serde(default)]
+	pub title: String,
+	#[serde(default)]
+	pub author: String,
+	#[serde(rename = "authorId", default)]
+	pub author_id: String,
+	#[serde(rename = "lengthSeconds", default)]
+	pub length: f64,
+	/// Milliseconds since the Unix epoch.
+	#[serde(rename = "timeWatched", default)]
+	pub time_watched: f64,
+	#[serde(rename = "watchProgress", default)]
+	pub progress: f64,
+}
+
+/// Reads every channel from each profile, skipping repeats and lines that
+/// are not channels. Profiles other than FreeTube's default one become
+/// subscription groups.
+pub fn read_freetube_subscriptions(db: &str) -> Subscriptions {
+	let mut subscriptions = Subscriptions::default();
+
+	for record in read_nedb(db) {
+		let records = match record.get("subscriptions") {
+			Some(Value::Array(subscriptions)) => subscriptions.clone(),
+			_ => vec![record.clone()],
+		};
+		let channels: Vec<FreetubeChannel> = records
+			.into_iter()
+			.filter_map(|record| serde_json::from_value(record).ok())
+			.collect();
+
+		for channel in &channels {
+			if !subscriptions.channels.iter().any(|sub| sub.id == channel.id) {
+				subscriptions
+					.channels
+					.push(Subscription::new(&channel.id, &channel.name));
+			}
+		}
+		if let Ok(profile) = serde_json::from_value::<FreetubeProfile>(record) {
+			if profile.id != FREETUBE_ALL_CHANNELS
+				&& subscriptions.group(&profile.name).is_none()
+			{
+				subscriptions.groups.push(SubscriptionGroup {
+					name: profile.name,
+					channels: channels
+						.into_iter()
+						.map(|channel| channel.id)
+						.collect(),
+				});
+			}
+		}
+	}
+
+	subscriptions
+}
+
+/// Serialises subscriptions as a FreeTube `profiles.db`: the default
+/// profile with every channel, then a profile for each group.
+///
+/// # Errors
+///
+/// - Json serialisation error.
+pub fn write_freetube_subscriptions(
+	subscriptions: &Subscriptions,
+) -> Result<String, RustyTubeError> {
+	let all_channels = FreetubeProfile {
+		id: FREETUBE_ALL_CHANNELS.to_string(),
+		name: FREETUBE_ALL_CHANNELS_NAME.to_string(),
+		subscriptions: subscriptions
+			.channels
+			.iter()
+			.map(FreetubeChannel::from)
+			.collect(),
+		..Default::default()
+	};
+	let groups = subscriptions.groups.iter().map(|group| FreetubeProfile {
+		id: group.name.clone(),
+		name: group.name.clone(),
+		subscriptions: subscriptions
+			.channels
+			.iter()
+			.filter(|sub| group.contains(&sub.id))
+			.map(FreetubeChannel::from)
+			.collect(),
+		..Default::default()
+	});
+
+	let mut db = String::new();
+	for profile in std::iter::once(all_channels).chain(groups) {
+		db.push_str(&serde_json::to_string(&profile)?);
+		db.push('\n');
+	}
+	Ok(db)
+}
+
+/// Reads `history.db`, most recently watched first.
+pub fn read_freetube_history(db: &str) -> Vec<HistoryEntry> {
+	let mut history: Vec<HistoryEntry> = read_nedb(db)
+		.into_iter()
+		.filter_map(|record| {
+			serde_json::from_value::<FreetubeHistoryEntry>(record).ok()
+		})
+		.map(HistoryEntry::from)
+		.collect();
+	newest_first(&mut history);
+	history
+}
+
+/// Reads the records of a `NeDB` file. `NeDB` appends a record again each
+/// time it changes and marks removed ones with `$$deleted`, so only the last
+/// copy of each `_id` is kept, in the place of the first.
+fn read_nedb(db: &str) -> Vec<Value> {
+	let mut records: Vec<Value> = Vec::new();
+
+	for line in db.lines().filter(|line| !line.trim().is_empty()) {
+		let Ok(record) = serde_json::from_str::<Value>(line) else {
+			continue;
+		};
+		let deleted = record.get("$$deleted") == Some(&Value::Bool(true));
+		let previous = record.get("_id").and_then(|id| {
+			records.iter().position(|record| record.get("_id") == Some(id))
+		});
+
+		match (previous, deleted) {
+			(Some(index), true) => {
+				records.remove(index);
+			}
+			(Some(index), false) => records[index] = record,
+			(None, false) => records.push(record),
+			(None, true) => {}
+		}
+	}
+
+	records
+}
+
+impl From<FreetubeHistoryEntry> for HistoryEntry {
+	#[allow(clippy::cast_possible_truncation)]
+	#[allow(clippy::cast_sign_loss)]
+	fn from(val: FreetubeHistoryEntry) -> Self {
+		let mut entry =
+			Self::from_id(&val.id, (val.time_watched / 1000f64) as u64);
+		entry.title = val.title;
+		entry.author = val.author;
+		entry.author_id = val.author_id;
+		entry.length = val.length as u32;
+		entry.progress = val.progress;
+		entry
+	}
+}
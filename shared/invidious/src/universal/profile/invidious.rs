@@ -0,0 +1,69 @@
+use rustytube_error::RustyTubeError;
+use serde::{Deserialize, Serialize};
+use utils::get_unix_time;
+
+use crate::{HistoryEntry, LocalPlaylist, Subscription, Subscriptions};
+
+/// The JSON export from an Invidious account's data control page.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct InvidiousExport {
+	/// Channel ids only; names are looked up after importing.
+	#[serde(default)]
+	pub subscriptions: Vec<String>,
+	/// Video ids, most recently watched first.
+	#[serde(default)]
+	pub watch_history: Vec<String>,
+	#[serde(default)]
+	pub playlists: Vec<InvidiousExportPlaylist>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InvidiousExportPlaylist {
+	pub title: String,
+	#[serde(default)]
+	pub videos: Vec<String>,
+}
+
+impl InvidiousExport {
+	/// # Errors
+	///
+	/// - Json parse error.
+	pub fn read(json: &str) -> Result<Self, RustyTubeError> {
+		Ok(serde_json::from_str(json)?)
+	}
+
+	pub fn subscriptions(&self) -> Subscriptions {
+		let channels = self
+			.subscriptions
+			.iter()
+			.map(|id| Subscription::new(id, ""))
+			.collect();
+		Subscriptions { channels, ..Default::default() }
+	}
+
+	pub fn playlists(&self) -> Vec<LocalPlaylist> {
+		let now = get_unix_time();
+		self.playlists
+			.iter()
+			.map(|playlist| {
+				LocalPlaylist::new(
+					None,
+					&playlist.title,
+					playlist.videos.clone(),
+					now,
+				)
+			})
+			.collect()
+	}
+
+	/// The export has no watch times, so entries are spaced a second apart
+	/// to keep their order.
+	pub fn history(&self) -> Vec<HistoryEntry> {
+		let now = get_unix_time();
+		self.watch_history
+			.iter()
+			.zip(0..)
+			.map(|(id, age)| HistoryEntry::from_id(id, now.saturating_sub(age)))
+			.collect()
+	}
+}
@@ -0,0 +1,241 @@
+mod freetube;
+mod invidious;
+mod takeout;
+mod zip;
+
+pub use freetube::*;
+pub use invidious::*;
+pub use takeout::*;
+pub use zip::*;
+
+use std::collections::HashSet;
+
+use gloo::file::{futures::read_as_text, File};
+use rustytube_error::RustyTubeError;
+use serde_json::{Map, Value};
+
+use crate::{
+	read_freetube_playlists, read_libretube_playlists, read_playlist_csv,
+	HistoryEntry, LocalPlaylist, NewpipeSubscriptions, Subscriptions,
+	YoutubeSubscriptions,
+};
+
+/// The exports that can be imported, told apart by their content.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportFormat {
+	/// Invidious account export with subscriptions, history and playlists.
+	Invidious,
+	NewPipe,
+	YoutubeCsv,
+	/// Takeout's `watch-history.json`.
+	YoutubeHistory,
+	/// A Google Takeout zip or folder, made up of the YouTube formats.
+	Takeout,
+	/// FreeTube `profiles.db` or the older `subscriptions.db`.
+	FreetubeSubscriptions,
+	FreetubeHistory,
+	FreetubePlaylists,
+	LibretubePlaylists,
+	PlaylistCsv,
+}
+
+impl ImportFormat {
+	pub fn detect(text: &str) -> Option<Self> {
+		let text = text.trim_start_matches('\u{feff}').trim();
+
+		match serde_json::from_str::<Value>(text) {
+			Ok(Value::Object(json)) => return detect_json(&json),
+			Ok(Value::Array(json)) => return Some(detect_json_array(&json)),
+			_ => {}
+		}
+
+		let first_line = text.lines().next()?.trim();
+		if let Ok(Value::Object(line)) = serde_json::from_str(first_line) {
+			return detect_database_line(&line);
+		}
+		// Playlist headers also have a `Channel ID` column, so they are
+		// checked first.
+		if first_line.contains("Playlist ID") || first_line.contains("Video ID")
+		{
+			return Some(Self::PlaylistCsv);
+		}
+		if first_line.contains("Channel ID")
+			|| first_line.contains("Channel Id")
+		{
+			return Some(Self::YoutubeCsv);
+		}
+		None
+	}
+}
+
+fn detect_json(json: &Map<String, Value>) -> Option<ImportFormat> {
+	let has_invidious_subs = json
+		.get("subscriptions")
+		.and_then(Value::as_array)
+		.and_then(|subs| subs.first())
+		.is_some_and(Value::is_string);
+
+	if json.contains_key("watch_history")
+		|| json.contains_key("preferences")
+		|| has_invidious_subs
+	{
+		Some(ImportFormat::Invidious)
+	} else if json.contains_key("app_version") {
+		Some(ImportFormat::NewPipe)
+	} else if json.contains_key("format") && json.contains_key("playlists") {
+		Some(ImportFormat::LibretubePlaylists)
+	} else {
+		// A FreeTube database with a single record.
+		detect_database_line(json)
+	}
+}
+
+fn detect_json_array(json: &[Value]) -> ImportFormat {
+	let is_history =
+		json.first().and_then(Value::as_object).is_some_and(|entry| {
+			entry.contains_key("titleUrl") || entry.contains_key("header")
+		});
+	if is_history {
+		ImportFormat::YoutubeHistory
+	} else {
+		ImportFormat::FreetubePlaylists
+	}
+}
+
+/// FreeTube databases hold one JSON object per line.
+fn detect_database_line(line: &Map<String, Value>) -> Option<ImportFormat> {
+	if line.contains_key("playlistName") {
+		Some(ImportFormat::FreetubePlaylists)
+	} else if line.contains_key("timeWatched")
+		|| line.contains_key("watchProgress")
+	{
+		Some(ImportFormat::FreetubeHistory)
+	} else if ["subscriptions", "channelId", "channelName"]
+		.iter()
+		.any(|key| line.contains_key(*key))
+	{
+		Some(ImportFormat::FreetubeSubscriptions)
+	} else {
+		None
+	}
+}
+
+/// Everything read from an export file, whichever parts it contains.
+#[derive(Clone, Debug)]
+pub struct ProfileImport {
+	pub format: ImportFormat,
+	pub subscriptions: Subscriptions,
+	pub playlists: Vec<LocalPlaylist>,
+	/// Ordered from most to least recently watched.
+	pub history: Vec<HistoryEntry>,
+}
+
+impl ProfileImport {
+	fn new(format: ImportFormat) -> Self {
+		Self {
+			format,
+			subscriptions: Subscriptions::default(),
+			playlists: Vec::new(),
+			history: Vec::new(),
+		}
+	}
+
+	/// # Errors
+	///
+	/// - File read error.
+	/// - Unrecognised or malformed export.
+	pub async fn read(file: &File) -> Result<Self, RustyTubeError> {
+		let text = read_as_text(file).await?;
+		let name = file.name();
+		let title = name.rsplit_once('.').map_or(name.as_str(), |name| name.0);
+		Self::parse(&text, title).await
+	}
+
+	/// Parses an export, using `title` for formats that do not name their
+	/// playlist.
+	///
+	/// # Errors
+	///
+	/// - Unrecognised or malformed export.
+	pub async fn parse(
+		text: &str,
+		title: &str,
+	) -> Result<Self, RustyTubeError> {
+		let format =
+			ImportFormat::detect(text).ok_or(RustyTubeError::ImportFormat)?;
+		let text = text.trim_start_matches('\u{feff}');
+		let mut import = Self::new(format);
+
+		match format {
+			ImportFormat::Invidious => {
+				let export = InvidiousExport::read(text)?;
+				import.subscriptions = export.subscriptions();
+				import.playlists = export.playlists();
+				import.history = export.history();
+			}
+			ImportFormat::NewPipe => {
+				import.subscriptions =
+					NewpipeSubscriptions::read_subs_from_file(text)?.into();
+			}
+			ImportFormat::YoutubeCsv => {
+				import.subscriptions =
+					YoutubeSubscriptions::read_subs_from_csv(text.as_bytes())?
+						.into();
+			}
+			ImportFormat::YoutubeHistory => {
+				import.history = read_takeout_history(text)?;
+			}
+			ImportFormat::Takeout => return Err(RustyTubeError::ImportFormat),
+			ImportFormat::FreetubeSubscriptions => {
+				import.subscriptions = read_freetube_subscriptions(text);
+			}
+			ImportFormat::FreetubeHistory => {
+				import.history = read_freetube_history(text);
+			}
+			ImportFormat::FreetubePlaylists => {
+				import.playlists = read_freetube_playlists(text).await?;
+			}
+			ImportFormat::LibretubePlaylists => {
+				import.playlists = read_libretube_playlists(text).await?;
+			}
+			ImportFormat::PlaylistCsv => {
+				import
+					.playlists
+					.push(read_playlist_csv(title, text.as_bytes()).await?);
+			}
+		}
+		Ok(import)
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.subscriptions.channels.is_empty()
+			&& self.playlists.is_empty()
+			&& self.history.is_empty()
+	}
+
+	/// Adds the contents of another import, skipping repeated channels and
+	/// videos.
+	pub fn append(&mut self, other: Self) {
+		for channel in other.subscriptions.channels {
+			if !self
+				.subscriptions
+				.channels
+				.iter()
+				.any(|sub| sub.id == channel.id)
+			{
+				self.subscriptions.channels.push(channel);
+			}
+		}
+		self.playlists.extend(other.playlists);
+		self.history.extend(other.history);
+		newest_first(&mut self.history);
+	}
+}
+
+/// Sorts history from most to least recently watched, keeping only the
+/// latest watch of each video.
+fn newest_first(history: &mut Vec<HistoryEntry>) {
+	history.sort_by(|a, b| b.watched.cmp(&a.watched));
+	let mut seen = HashSet::new();
+	history.retain(|entry| seen.insert(entry.id.clone()));
+}
@@ -0,0 +1,152 @@
+use chrono::DateTime;
+use rustytube_error::RustyTubeError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{newest_first, read_zip, ImportFormat, ProfileImport};
+use crate::HistoryEntry;
+
+/// Newer Takeouts list every playlist's metadata here, next to a
+/// `<title>-videos.csv` file for each playlist.
+const PLAYLIST_INDEX: &str = "playlists.csv";
+const PLAYLIST_VIDEOS_SUFFIX: &str = "-videos";
+
+/// An entry of Takeout's `watch-history.json`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TakeoutHistoryEntry {
+	#[serde(default)]
+	pub title: String,
+	/// Missing for videos that have since been removed.
+	#[serde(rename = "titleUrl")]
+	pub title_url: Option<String>,
+	#[serde(default)]
+	pub subtitles: Vec<TakeoutChannel>,
+	/// RFC 3339 watch time.
+	pub time: String,
+	/// Only present on ads.
+	#[serde(default)]
+	pub details: Vec<Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TakeoutChannel {
+	pub name: String,
+	pub url: Option<String>,
+}
+
+/// Reads `watch-history.json`, most recently watched first. Ads and removed
+/// videos are skipped.
+///
+/// # Errors
+///
+/// - Json parse error.
+pub fn read_takeout_history(
+	json: &str,
+) -> Result<Vec<HistoryEntry>, RustyTubeError> {
+	let entries = serde_json::from_str::<Vec<TakeoutHistoryEntry>>(json)?;
+	let mut history = entries
+		.into_iter()
+		.filter_map(TakeoutHistoryEntry::into_entry)
+		.collect();
+	newest_first(&mut history);
+	Ok(history)
+}
+
+impl TakeoutHistoryEntry {
+	fn into_entry(self) -> Option<HistoryEntry> {
+		if !self.details.is_empty() {
+			return None;
+		}
+		let url = self.title_url?;
+		let (_, query) = url.split_once("v=")?;
+		let id = query.split('&').next()?;
+		let watched = DateTime::parse_from_rfc3339(&self.time).ok()?;
+
+		let mut entry = HistoryEntry::from_id(
+			id,
+			u64::try_from(watched.timestamp()).unwrap_or_default(),
+		);
+		entry.title = self
+			.title
+			.strip_prefix("Watched ")
+			.unwrap_or(&self.title)
+			.to_string();
+		if let Some(channel) = self.subtitles.into_iter().next() {
+			entry.author = channel.name;
+			entry.author_id = channel
+				.url
+				.as_deref()
+				.and_then(|url| url.rsplit('/').next())
+				.unwrap_or_default()
+				.to_string();
+		}
+		Some(entry)
+	}
+}
+
+impl ProfileImport {
+	/// Reads the playlists, watch history and subscriptions from a Google
+	/// Takeout zip. Everything else in the archive is ignored.
+	///
+	/// # Errors
+	///
+	/// - Zip read error.
+	/// - Malformed export file, or none found.
+	pub async fn read_takeout_zip(
+		bytes: &[u8],
+	) -> Result<Self, RustyTubeError> {
+		let files = read_zip(bytes, is_takeout_file)?
+			.into_iter()
+			.map(|entry| {
+				let text =
+					String::from_utf8_lossy(&entry.contents).into_owned();
+				(entry.name().to_string(), text)
+			})
+			.collect();
+		Self::read_takeout_files(files).await
+	}
+
+	/// Reads the files of an extracted Takeout folder, given as name and
+	/// contents pairs. Files are recognised by their content, so localised
+	/// Takeouts work too.
+	///
+	/// # Errors
+	///
+	/// - Malformed export file, or none found.
+	pub async fn read_takeout_files(
+		files: Vec<(String, String)>,
+	) -> Result<Self, RustyTubeError> {
+		let mut takeout = Self::new(ImportFormat::Takeout);
+
+		for (name, text) in files {
+			if name.eq_ignore_ascii_case(PLAYLIST_INDEX) {
+				continue;
+			}
+			let title =
+				name.rsplit_once('.').map_or(name.as_str(), |name| name.0);
+			let title =
+				title.strip_suffix(PLAYLIST_VIDEOS_SUFFIX).unwrap_or(title);
+
+			if matches!(
+				ImportFormat::detect(&text),
+				Some(
+					ImportFormat::YoutubeCsv
+						| ImportFormat::YoutubeHistory
+						| ImportFormat::PlaylistCsv
+				)
+			) {
+				takeout.append(Self::parse(&text, title).await?);
+			}
+		}
+
+		if takeout.is_empty() {
+			return Err(RustyTubeError::ImportFormat);
+		}
+		Ok(takeout)
+	}
+}
+
+fn is_takeout_file(path: &str) -> bool {
+	let path = path.to_lowercase();
+	path.ends_with(".csv") || path.ends_with(".json")
+}
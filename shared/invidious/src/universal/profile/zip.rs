@@ -0,0 +1,256 @@
+use miniz_oxide::inflate::decompress_to_vec_with_limit;
+use rustytube_error::RustyTubeError;
+
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+const ZIP64_END_OF_CENTRAL_DIRECTORY: u32 = 0x0606_4b50;
+const ZIP64_LOCATOR: u32 = 0x0706_4b50;
+const CENTRAL_DIRECTORY_HEADER: u32 = 0x0201_4b50;
+const LOCAL_FILE_HEADER: u32 = 0x0403_4b50;
+const ZIP64_EXTRA_FIELD: u16 = 0x0001;
+
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+const ENCRYPTED_FLAG: u16 = 1;
+
+/// Sizes and offsets past this are stored in the zip64 extra field.
+const ZIP64_MARKER: u32 = u32::MAX;
+
+/// Largest file that is extracted, well past any Takeout export but small
+/// enough to fit in wasm memory.
+const MAX_FILE_SIZE: u64 = 512 * 1024 * 1024;
+
+/// A file extracted from a zip archive.
+#[derive(Clone, Debug)]
+pub struct ZipEntry {
+	/// Path inside the archive, using `/` as the separator.
+	pub path: String,
+	pub contents: Vec<u8>,
+}
+
+impl ZipEntry {
+	pub fn name(&self) -> &str {
+		self.path.rsplit('/').next().unwrap_or(&self.path)
+	}
+}
+
+/// Extracts the files whose path `wanted` accepts. Only stored and deflated
+/// files are supported, which covers Google Takeout archives.
+///
+/// # Errors
+///
+/// - Not a zip archive, or a truncated one.
+/// - A wanted file is encrypted, too large or fails to inflate.
+pub fn read_zip(
+	bytes: &[u8],
+	wanted: impl Fn(&str) -> bool,
+) -> Result<Vec<ZipEntry>, RustyTubeError> {
+	let directory = CentralDirectory::find(bytes)?;
+	let mut entries = Vec::new();
+	let mut pos = directory.offset;
+
+	for _ in 0..directory.entries {
+		let header = FileHeader::read(bytes, pos)?;
+		pos = header.next;
+		if header.path.ends_with('/') || !wanted(&header.path) {
+			continue;
+		}
+		if header.flags & ENCRYPTED_FLAG != 0 {
+			return Err(zip_error("encrypted archives are not supported"));
+		}
+		let contents = header.extract(bytes)?;
+		entries.push(ZipEntry { path: header.path, contents });
+	}
+	Ok(entries)
+}
+
+struct CentralDirectory {
+	entries: u64,
+	offset: usize,
+}
+
+impl CentralDirectory {
+	fn find(bytes: &[u8]) -> Result<Self, RustyTubeError> {
+		// The end record is 22 bytes plus a comment of up to 64 KiB.
+		let end = bytes.len().checked_sub(22).ok_or_else(not_a_zip)?;
+		let start = end.saturating_sub(usize::from(u16::MAX));
+		let record = (start..=end)
+			.rev()
+			.find(|pos| {
+				read_u32(bytes, *pos).ok() == Some(END_OF_CENTRAL_DIRECTORY)
+			})
+			.ok_or_else(not_a_zip)?;
+
+		let entries = read_u16(bytes, at(record, 10)?)?;
+		let offset = read_u32(bytes, at(record, 16)?)?;
+		if offset != ZIP64_MARKER {
+			return Ok(Self {
+				entries: entries.into(),
+				offset: to_usize(offset.into())?,
+			});
+		}
+
+		let locator = record.checked_sub(20).ok_or_else(not_a_zip)?;
+		if read_u32(bytes, locator)? != ZIP64_LOCATOR {
+			return Err(not_a_zip());
+		}
+		let record = to_usize(read_u64(bytes, at(locator, 8)?)?)?;
+		if read_u32(bytes, record)? != ZIP64_END_OF_CENTRAL_DIRECTORY {
+			return Err(not_a_zip());
+		}
+		Ok(Self {
+			entries: read_u64(bytes, at(record, 32)?)?,
+			offset: to_usize(read_u64(bytes, at(record, 48)?)?)?,
+		})
+	}
+}
+
+struct FileHeader {
+	path: String,
+	flags: u16,
+	method: u16,
+	compressed_size: u64,
+	/// Declared uncompressed size, which inflating never goes past.
+	size: u64,
+	local_offset: u64,
+	/// Position of the next central directory header.
+	next: usize,
+}
+
+impl FileHeader {
+	fn read(bytes: &[u8], pos: usize) -> Result<Self, RustyTubeError> {
+		if read_u32(bytes, pos)? != CENTRAL_DIRECTORY_HEADER {
+			return Err(zip_error("corrupt central directory"));
+		}
+		let flags = read_u16(bytes, at(pos, 8)?)?;
+		let method = read_u16(bytes, at(pos, 10)?)?;
+		let compressed_size = read_u32(bytes, at(pos, 20)?)?;
+		let size = read_u32(bytes, at(pos, 24)?)?;
+		let name_len = usize::from(read_u16(bytes, at(pos, 28)?)?);
+		let extra_len = usize::from(read_u16(bytes, at(pos, 30)?)?);
+		let comment_len = usize::from(read_u16(bytes, at(pos, 32)?)?);
+		let local_offset = read_u32(bytes, at(pos, 42)?)?;
+
+		let name_start = at(pos, 46)?;
+		let extra_start = at(name_start, name_len)?;
+		let name = slice(bytes, name_start, name_len)?;
+		let extra = slice(bytes, extra_start, extra_len)?;
+
+		// Zip64 values appear in this order, but only for the fields that
+		// overflowed.
+		let mut zip64 = zip64_values(extra);
+		let size = widen(size, &mut zip64);
+		let compressed_size = widen(compressed_size, &mut zip64);
+		let local_offset = widen(local_offset, &mut zip64);
+
+		Ok(Self {
+			path: String::from_utf8_lossy(name).replace('\\', "/"),
+			flags,
+			method,
+			compressed_size,
+			size,
+			local_offset,
+			next: at(at(extra_start, extra_len)?, comment_len)?,
+		})
+	}
+
+	fn extract(&self, bytes: &[u8]) -> Result<Vec<u8>, RustyTubeError> {
+		let pos = to_usize(self.local_offset)?;
+		if read_u32(bytes, pos)? != LOCAL_FILE_HEADER {
+			return Err(zip_error("corrupt file header"));
+		}
+		if self.size > MAX_FILE_SIZE {
+			return Err(zip_error(&format!(
+				"{}: file is too large",
+				self.path
+			)));
+		}
+		let name_len = usize::from(read_u16(bytes, at(pos, 26)?)?);
+		let extra_len = usize::from(read_u16(bytes, at(pos, 28)?)?);
+		let data_start = at(at(at(pos, 30)?, name_len)?, extra_len)?;
+		let data = slice(bytes, data_start, to_usize(self.compressed_size)?)?;
+		let size = to_usize(self.size)?;
+
+		match self.method {
+			STORED if data.len() == size => Ok(data.to_vec()),
+			STORED => Err(zip_error(&format!("{}: size mismatch", self.path))),
+			DEFLATED => decompress_to_vec_with_limit(data, size)
+				.map_err(|err| zip_error(&format!("{}: {err:?}", self.path))),
+			method => Err(zip_error(&format!(
+				"{}: unsupported compression method {method}",
+				self.path
+			))),
+		}
+	}
+}
+
+/// The values in the zip64 extra field, if there is one.
+fn zip64_values(mut extra: &[u8]) -> impl Iterator<Item = u64> + '_ {
+	while let (Ok(id), Ok(len)) = (read_u16(extra, 0), read_u16(extra, 2)) {
+		let len = usize::from(len);
+		if id == ZIP64_EXTRA_FIELD {
+			extra = extra.get(4..4 + len).unwrap_or_default();
+			break;
+		}
+		extra = extra.get(4 + len..).unwrap_or_default();
+	}
+	extra
+		.chunks_exact(8)
+		.filter_map(|value| value.try_into().ok().map(u64::from_le_bytes))
+}
+
+fn widen(value: u32, zip64: &mut impl Iterator<Item = u64>) -> u64 {
+	let widened = u64::from(value);
+	if value == ZIP64_MARKER {
+		zip64.next().unwrap_or(widened)
+	} else {
+		widened
+	}
+}
+
+/// `pos + offset`, failing instead of overflowing on corrupt offsets.
+fn at(pos: usize, offset: usize) -> Result<usize, RustyTubeError> {
+	pos.checked_add(offset).ok_or_else(|| zip_error("corrupt offset"))
+}
+
+fn slice(
+	bytes: &[u8],
+	pos: usize,
+	len: usize,
+) -> Result<&[u8], RustyTubeError> {
+	pos.checked_add(len)
+		.and_then(|end| bytes.get(pos..end))
+		.ok_or_else(|| zip_error("unexpected end of archive"))
+}
+
+fn read_u16(bytes: &[u8], pos: usize) -> Result<u16, RustyTubeError> {
+	Ok(u16::from_le_bytes(array(bytes, pos)?))
+}
+
+fn read_u32(bytes: &[u8], pos: usize) -> Result<u32, RustyTubeError> {
+	Ok(u32::from_le_bytes(array(bytes, pos)?))
+}
+
+fn read_u64(bytes: &[u8], pos: usize) -> Result<u64, RustyTubeError> {
+	Ok(u64::from_le_bytes(array(bytes, pos)?))
+}
+
+fn array<const N: usize>(
+	bytes: &[u8],
+	pos: usize,
+) -> Result<[u8; N], RustyTubeError> {
+	slice(bytes, pos, N)?
+		.try_into()
+		.map_err(|_| zip_error("unexpected end of archive"))
+}
+
+fn to_usize(value: u64) -> Result<usize, RustyTubeError> {
+	usize::try_from(value).map_err(|_| zip_error("archive is too large"))
+}
+
+fn not_a_zip() -> RustyTubeError {
+	zip_error("not a zip archive")
+}
+
+fn zip_error(error: &str) -> RustyTubeError {
+	RustyTubeError::Zip(error.to_string())
+}
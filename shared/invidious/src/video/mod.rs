@@ -1,7 +1,9 @@
 mod annotations;
 mod captions;
 mod video;
+mod webvtt;
 
 pub use annotations::*;
 pub use captions::*;
 pub use video::*;
+pub use webvtt::*;
@@ -0,0 +1,137 @@
+use html_escape::decode_html_entities;
+use rustytube_error::RustyTubeError;
+
+use crate::{fetch::fetch, hidden::Caption};
+
+const HEADER: &str = "WEBVTT";
+const TIMING_SEPARATOR: &str = "-->";
+
+/// Text shown from `start` until `end`, both in seconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptionCue {
+	pub start: f64,
+	pub end: f64,
+	/// Plain text, with formatting tags removed and lines separated by `\n`.
+	pub text: String,
+}
+
+/// The cues of a WebVTT file, ordered by start time.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CaptionTrack {
+	pub cues: Vec<CaptionCue>,
+}
+
+impl CaptionTrack {
+	pub async fn fetch(
+		server: &str,
+		caption: &Caption,
+	) -> Result<Self, RustyTubeError> {
+		let vtt = fetch(&caption.url(server)).await?;
+		Self::parse(&vtt)
+	}
+
+	/// Reads the cues of a WebVTT file. `NOTE`, `STYLE` and `REGION` blocks,
+	/// cue settings and cues with malformed timings are skipped.
+	///
+	/// # Errors
+	///
+	/// - Missing `WEBVTT` header.
+	pub fn parse(vtt: &str) -> Result<Self, RustyTubeError> {
+		let vtt = vtt.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+		let mut blocks = blocks(&vtt);
+
+		let header = blocks.next().and_then(|block| block.first().copied());
+		if !header.map_or(false, is_header) {
+			return Err(RustyTubeError::WebVtt(String::from(
+				"Missing WEBVTT header.",
+			)));
+		}
+
+		let mut cues = blocks.filter_map(CaptionCue::parse).collect::<Vec<_>>();
+		cues.sort_by(|a, b| a.start.total_cmp(&b.start));
+		Ok(Self { cues })
+	}
+
+	/// The cues shown at `time`, in seconds.
+	pub fn cues_at(&self, time: f64) -> impl Iterator<Item = &CaptionCue> {
+		self.cues
+			.iter()
+			.take_while(move |cue| cue.start <= time)
+			.filter(move |cue| cue.end > time)
+	}
+}
+
+impl CaptionCue {
+	/// Reads a block of lines, which starts with the timings or with a cue
+	/// identifier followed by the timings.
+	fn parse(block: Vec<&str>) -> Option<Self> {
+		let timings = block
+			.iter()
+			.take(2)
+			.position(|line| line.contains(TIMING_SEPARATOR))?;
+		let (start, end) = block[timings].split_once(TIMING_SEPARATOR)?;
+		let start = parse_timestamp(start.trim())?;
+		let end = parse_timestamp(end.split_whitespace().next()?)?;
+
+		let text = block[timings + 1..]
+			.iter()
+			.map(|line| strip_tags(line))
+			.collect::<Vec<_>>()
+			.join("\n");
+		let text = decode_html_entities(text.trim()).to_string();
+
+		(end > start && !text.is_empty()).then_some(Self { start, end, text })
+	}
+}
+
+fn is_header(line: &str) -> bool {
+	line.strip_prefix(HEADER)
+		.map_or(false, |rest| rest.is_empty() || rest.starts_with([' ', '\t']))
+}
+
+/// Groups the lines into blocks separated by blank lines.
+fn blocks(vtt: &str) -> impl Iterator<Item = Vec<&str>> {
+	let mut lines = vtt.lines().peekable();
+	std::iter::from_fn(move || {
+		while lines.next_if(|line| line.trim().is_empty()).is_some() {}
+		let mut block = Vec::new();
+		while let Some(line) = lines.next_if(|line| !line.trim().is_empty()) {
+			block.push(line);
+		}
+		(!block.is_empty()).then_some(block)
+	})
+}
+
+/// Reads `hh:mm:ss.ttt` or `mm:ss.ttt` as seconds.
+fn parse_timestamp(timestamp: &str) -> Option<f64> {
+	let mut parts = timestamp.rsplit(':');
+	let seconds = parts.next()?;
+	if !seconds.contains('.') || seconds.starts_with(['-', '+']) {
+		return None;
+	}
+	let mut total = seconds.parse::<f64>().ok()?;
+	let mut unit = 60f64;
+	for (index, part) in parts.enumerate() {
+		if index > 1 {
+			return None;
+		}
+		total += f64::from(part.parse::<u32>().ok()?) * unit;
+		unit *= 60f64;
+	}
+	Some(total)
+}
+
+/// Removes tags such as `<b>`, `<c.colour>` and `<00:00:01.000>`.
+fn strip_tags(line: &str) -> String {
+	let mut text = String::with_capacity(line.len());
+	let mut in_tag = false;
+	for char in line.chars() {
+		match char {
+			'<' => in_tag = true,
+			'>' if in_tag => in_tag = false,
+			_ if !in_tag => text.push(char),
+			_ => (),
+		}
+	}
+	text
+}
@@ -17,7 +17,9 @@ pub struct Query {
 	service: Option<String>,
 }
 
-#[derive(Clone, Copy, Default, Deserialize, Serialize)]
+#[derive(
+	Clone, Copy, Default, Debug, PartialEq, Eq, Deserialize, Serialize,
+)]
 pub enum Category {
 	#[default]
 	Sponsor,
@@ -28,24 +30,53 @@ pub enum Category {
 	Preview,
 	OffTopicMusic,
 	Filler,
+	/// The point of interest, or highlight, of a video.
+	Highlight,
+	/// Titled sections of a video.
+	Chapter,
 }
 
-impl Display for Category {
-	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Category {
+	pub const ALL: [Self; 10] = [
+		Self::Sponsor,
+		Self::SelfPromotion,
+		Self::Interaction,
+		Self::Intro,
+		Self::Outro,
+		Self::Preview,
+		Self::OffTopicMusic,
+		Self::Filler,
+		Self::Highlight,
+		Self::Chapter,
+	];
+
+	/// The name used by the API, and in `Segment::category`.
+	#[must_use]
+	pub const fn name(&self) -> &'static str {
 		match self {
-			Self::Sponsor => write!(f, "\"sponsor\""),
-			Self::SelfPromotion => write!(f, "\"selfpromo\""),
-			Self::Interaction => write!(f, "\"interaction\""),
-			Self::Intro => write!(f, "\"intro\""),
-			Self::Outro => write!(f, "\"outro\""),
-			Self::Preview => write!(f, "\"preview\""),
-			Self::OffTopicMusic => write!(f, "\"music_offtopic\""),
-			Self::Filler => write!(f, "\"filler\""),
+			Self::Sponsor => "sponsor",
+			Self::SelfPromotion => "selfpromo",
+			Self::Interaction => "interaction",
+			Self::Intro => "intro",
+			Self::Outro => "outro",
+			Self::Preview => "preview",
+			Self::OffTopicMusic => "music_offtopic",
+			Self::Filler => "filler",
+			Self::Highlight => "poi_highlight",
+			Self::Chapter => "chapter",
 		}
 	}
 }
 
-#[derive(Clone, Copy, Default, Deserialize, Serialize)]
+impl Display for Category {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "\"{}\"", self.name())
+	}
+}
+
+#[derive(
+	Clone, Copy, Default, Debug, PartialEq, Eq, Deserialize, Serialize,
+)]
 pub enum Action {
 	#[default]
 	Skip,
@@ -55,18 +86,26 @@ pub enum Action {
 	Chapter,
 }
 
-impl Display for Action {
-	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Action {
+	/// The name used by the API, and in `Segment::action`.
+	#[must_use]
+	pub const fn name(&self) -> &'static str {
 		match self {
-			Self::Skip => write!(f, "\"skip\""),
-			Self::Mute => write!(f, "\"mute\""),
-			Self::Full => write!(f, "\"full\""),
-			Self::PointOfInterest => write!(f, "\"poi\""),
-			Self::Chapter => write!(f, "\"chapter\""),
+			Self::Skip => "skip",
+			Self::Mute => "mute",
+			Self::Full => "full",
+			Self::PointOfInterest => "poi",
+			Self::Chapter => "chapter",
 		}
 	}
 }
 
+impl Display for Action {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "\"{}\"", self.name())
+	}
+}
+
 impl Query {
 	pub const fn create(
 		video_id: String,
@@ -0,0 +1,288 @@
+mod migrations;
+
+use std::cell::RefCell;
+
+use js_sys::{Array, Function, Promise};
+use rustytube_error::RustyTubeError;
+use serde::{de::DeserializeOwned, Serialize};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+	IdbDatabase, IdbObjectStore, IdbOpenDbRequest, IdbRequest,
+	IdbTransactionMode, IdbVersionChangeEvent,
+};
+
+use self::migrations::MIGRATIONS;
+
+pub const DB_NAME: &str = "rustytube";
+
+/// Responses cached by `invidious::fetch`, keyed by url.
+pub const API_CACHE_STORE: &str = "api_cache";
+pub const CONFIG_STORE: &str = "config";
+pub const SUBSCRIPTIONS_STORE: &str = "subscriptions";
+/// Local playlists, keyed by id.
+pub const PLAYLISTS_STORE: &str = "playlists";
+/// Watch history entries, keyed by video id.
+pub const HISTORY_STORE: &str = "history";
+pub const POSITIONS_STORE: &str = "positions";
+/// Trending, popular and instance lists kept for offline use.
+pub const RESOURCES_STORE: &str = "resources";
+/// The last fetched videos of each subscribed channel, keyed by channel id.
+pub const FEEDS_STORE: &str = "feeds";
+/// Running statistics, such as the time saved by skipping segments.
+pub const STATS_STORE: &str = "stats";
+
+thread_local! {
+	static DATABASE: RefCell<Option<IdbDatabase>> = const { RefCell::new(None) };
+}
+
+/// The current schema version.
+#[allow(clippy::cast_possible_truncation)]
+pub const fn db_version() -> u32 {
+	MIGRATIONS.len() as u32
+}
+
+async fn database() -> Result<IdbDatabase, RustyTubeError> {
+	if let Some(db) = DATABASE.with(|db| db.borrow().clone()) {
+		return Ok(db);
+	}
+
+	let factory = web_sys::window()
+		.ok_or(RustyTubeError::NoWindow)?
+		.indexed_db()?
+		.ok_or(RustyTubeError::NoIndexedDb)?;
+	let request = factory.open_with_u32(DB_NAME, db_version())?;
+
+	// A failed migration aborts the upgrade, keeping the old version, and
+	// its error is reported instead of the abort.
+	let failed = Promise::new(&mut |_resolve: Function, reject: Function| {
+		let on_upgrade =
+			Closure::once_into_js(move |event: IdbVersionChangeEvent| {
+				if let Err(error) = migrate(&event) {
+					let error = JsValue::from_str(&error.to_string());
+					let _ = reject.call1(&JsValue::UNDEFINED, &error);
+				}
+			});
+		request.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+	});
+
+	// Another tab holding an older version open stops the upgrade until it
+	// closes, so fail instead of waiting forever.
+	let blocked = Promise::new(&mut |_resolve: Function, reject: Function| {
+		let on_blocked = Closure::once_into_js(move || {
+			let error = JsValue::from_str("Database upgrade blocked.");
+			let _ = reject.call1(&JsValue::UNDEFINED, &error);
+		});
+		request.set_onblocked(Some(on_blocked.unchecked_ref()));
+	});
+	let opened = Promise::race(&Array::of3(
+		&failed,
+		&request_promise(&request),
+		&blocked,
+	));
+
+	let db = JsFuture::from(opened)
+		.await
+		.map_err(|error| {
+			error.as_string().map_or_else(
+				|| RustyTubeError::from(error),
+				RustyTubeError::BrowserStorage,
+			)
+		})?
+		.dyn_into::<IdbDatabase>()
+		.map_err(|_| RustyTubeError::DynInto)?;
+
+	// Let newer versions opened in other tabs upgrade the database.
+	let versioned_db = db.clone();
+	let on_version_change = Closure::once_into_js(move || {
+		versioned_db.close();
+		DATABASE.with(|cached| *cached.borrow_mut() = None);
+	});
+	db.set_onversionchange(Some(on_version_change.unchecked_ref()));
+
+	DATABASE.with(|cached| cached.replace(Some(db.clone())));
+	Ok(db)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn migrate(event: &IdbVersionChangeEvent) -> Result<(), RustyTubeError> {
+	let request = event
+		.target()
+		.ok_or(RustyTubeError::TargetNotFound)?
+		.dyn_into::<IdbOpenDbRequest>()
+		.map_err(|_| RustyTubeError::DynInto)?;
+	let db = request
+		.result()?
+		.dyn_into::<IdbDatabase>()
+		.map_err(|_| RustyTubeError::DynInto)?;
+	let transaction =
+		request.transaction().ok_or(RustyTubeError::NoIndexedDb)?;
+	let old_version = event.old_version() as usize;
+
+	let migrated = MIGRATIONS
+		.iter()
+		.skip(old_version)
+		.try_for_each(|migration| migration(&db, &transaction));
+	if let Err(error) = migrated {
+		let _ = transaction.abort();
+		return Err(error.into());
+	}
+	Ok(())
+}
+
+/// Resolves once the request succeeds or fails.
+async fn request_result(
+	request: &IdbRequest,
+) -> Result<JsValue, RustyTubeError> {
+	Ok(JsFuture::from(request_promise(request)).await?)
+}
+
+fn request_promise(request: &IdbRequest) -> Promise {
+	Promise::new(&mut |resolve: Function, reject: Function| {
+		let success_request = request.clone();
+		let on_success = Closure::once_into_js(move || {
+			let result = success_request.result().unwrap_or(JsValue::UNDEFINED);
+			let _ = resolve.call1(&JsValue::UNDEFINED, &result);
+		});
+		let error_request = request.clone();
+		let on_error = Closure::once_into_js(move || {
+			let error = error_request
+				.error()
+				.ok()
+				.flatten()
+				.map_or(JsValue::UNDEFINED, JsValue::from);
+			let _ = reject.call1(&JsValue::UNDEFINED, &error);
+		});
+		request.set_onsuccess(Some(on_success.unchecked_ref()));
+		request.set_onerror(Some(on_error.unchecked_ref()));
+	})
+}
+
+async fn object_store(
+	store: &str,
+	mode: IdbTransactionMode,
+) -> Result<IdbObjectStore, RustyTubeError> {
+	let transaction =
+		database().await?.transaction_with_str_and_mode(store, mode)?;
+	Ok(transaction.object_store(store)?)
+}
+
+/// # Errors
+///
+/// - `IndexedDB` errors.
+/// - Serde errors.
+pub async fn idb_get<T>(
+	store: &str,
+	key: &str,
+) -> Result<Option<T>, RustyTubeError>
+where
+	T: DeserializeOwned,
+{
+	let request = object_store(store, IdbTransactionMode::Readonly)
+		.await?
+		.get(&JsValue::from_str(key))?;
+	let value = request_result(&request).await?;
+	Ok(value.as_string().map(|json| serde_json::from_str(&json)).transpose()?)
+}
+
+/// # Errors
+///
+/// - `IndexedDB` errors.
+/// - Serde errors.
+pub async fn idb_put<T>(
+	store: &str,
+	key: &str,
+	value: &T,
+) -> Result<(), RustyTubeError>
+where
+	T: Serialize,
+{
+	let json = serde_json::to_string(value)?;
+	let request = object_store(store, IdbTransactionMode::Readwrite)
+		.await?
+		.put_with_key(&JsValue::from_str(&json), &JsValue::from_str(key))?;
+	request_result(&request).await?;
+	Ok(())
+}
+
+/// # Errors
+///
+/// - `IndexedDB` errors.
+pub async fn idb_delete(store: &str, key: &str) -> Result<(), RustyTubeError> {
+	let request = object_store(store, IdbTransactionMode::Readwrite)
+		.await?
+		.delete(&JsValue::from_str(key))?;
+	request_result(&request).await?;
+	Ok(())
+}
+
+/// # Errors
+///
+/// - `IndexedDB` errors.
+pub async fn idb_clear(store: &str) -> Result<(), RustyTubeError> {
+	let request =
+		object_store(store, IdbTransactionMode::Readwrite).await?.clear()?;
+	request_result(&request).await?;
+	Ok(())
+}
+
+/// Records that fail to deserialise are skipped.
+///
+/// # Errors
+///
+/// - `IndexedDB` errors.
+pub async fn idb_get_all<T>(store: &str) -> Result<Vec<T>, RustyTubeError>
+where
+	T: DeserializeOwned,
+{
+	Ok(get_all_json(store)
+		.await?
+		.iter()
+		.filter_map(|json| serde_json::from_str(json).ok())
+		.collect())
+}
+
+/// Every record with its key. Records that fail to deserialise are skipped.
+///
+/// # Errors
+///
+/// - `IndexedDB` errors.
+pub async fn idb_entries<T>(
+	store: &str,
+) -> Result<Vec<(String, T)>, RustyTubeError>
+where
+	T: DeserializeOwned,
+{
+	// Both requests share a transaction, so keys and values line up.
+	let object_store =
+		object_store(store, IdbTransactionMode::Readonly).await?;
+	let keys_request = object_store.get_all_keys()?;
+	let values_request = object_store.get_all()?;
+	let keys = Array::from(&request_result(&keys_request).await?);
+	let values = Array::from(&request_result(&values_request).await?);
+
+	Ok(keys
+		.iter()
+		.zip(values.iter())
+		.filter_map(|(key, value)| {
+			let value = serde_json::from_str(&value.as_string()?).ok()?;
+			Some((key.as_string()?, value))
+		})
+		.collect())
+}
+
+/// Approximate size of the store's contents in bytes.
+///
+/// # Errors
+///
+/// - `IndexedDB` errors.
+pub async fn idb_size(store: &str) -> Result<usize, RustyTubeError> {
+	Ok(get_all_json(store).await?.iter().map(String::len).sum())
+}
+
+async fn get_all_json(store: &str) -> Result<Vec<String>, RustyTubeError> {
+	let request =
+		object_store(store, IdbTransactionMode::Readonly).await?.get_all()?;
+	let values = Array::from(&request_result(&request).await?);
+	Ok(values.iter().filter_map(|value| value.as_string()).collect())
+}
@@ -0,0 +1,232 @@
+use gloo::storage::{LocalStorage, Storage};
+use serde_json::Value;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{IdbDatabase, IdbObjectStore, IdbTransaction};
+
+use super::{
+	API_CACHE_STORE, CONFIG_STORE, FEEDS_STORE, HISTORY_STORE, PLAYLISTS_STORE,
+	POSITIONS_STORE, RESOURCES_STORE, STATS_STORE, SUBSCRIPTIONS_STORE,
+};
+use crate::{get_unix_time, new_id};
+
+pub type Migration = fn(&IdbDatabase, &IdbTransaction) -> Result<(), JsValue>;
+
+/// Schema migrations, where `MIGRATIONS[n]` upgrades the database from
+/// version `n` to `n + 1`.
+pub const MIGRATIONS: &[Migration] = &[
+	create_api_cache,
+	create_entity_stores,
+	key_playlists_by_id,
+	create_feeds_store,
+	create_stats_store,
+];
+
+const LEGACY_CONFIG_KEY: &str = "RUSTYTUBE_CONFIG";
+const LEGACY_SUBSCRIPTIONS_KEY: &str = "subscriptions";
+const LEGACY_HISTORY_KEY: &str = "watch_history";
+const LEGACY_POSITIONS_KEY: &str = "watch_positions";
+const LEGACY_PLAYLIST_PREFIX: &str = "rt_playlist_";
+/// Responses that were mirrored to `LocalStorage` and are refetched instead.
+const LEGACY_RESOURCE_KEYS: [&str; 4] =
+	["instances", "trending_videos", "popular_videos", "playlists"];
+/// Timestamps below this (2001) came from `performance.now()`.
+const MIN_UNIX_TIME: u64 = 1_000_000_000;
+
+fn create_api_cache(
+	db: &IdbDatabase,
+	_: &IdbTransaction,
+) -> Result<(), JsValue> {
+	db.create_object_store(API_CACHE_STORE)?;
+	Ok(())
+}
+
+/// Adds a store per entity and moves everything previously kept in
+/// `LocalStorage` into them.
+fn create_entity_stores(
+	db: &IdbDatabase,
+	transaction: &IdbTransaction,
+) -> Result<(), JsValue> {
+	for store in [
+		CONFIG_STORE,
+		SUBSCRIPTIONS_STORE,
+		PLAYLISTS_STORE,
+		HISTORY_STORE,
+		POSITIONS_STORE,
+		RESOURCES_STORE,
+	] {
+		db.create_object_store(store)?;
+	}
+	import_local_storage(transaction)
+}
+
+fn import_local_storage(transaction: &IdbTransaction) -> Result<(), JsValue> {
+	let storage = LocalStorage::raw();
+	let mut imported = Vec::new();
+
+	for index in 0..storage.length()? {
+		let Some(key) = storage.key(index)? else {
+			continue;
+		};
+		let Some(json) = storage.get_item(&key)? else {
+			continue;
+		};
+		if import_item(transaction, &key, &json)? {
+			imported.push(key);
+		}
+	}
+
+	// LocalStorage is only cleared once the import has been committed.
+	let on_complete = Closure::once_into_js(move || {
+		for key in imported {
+			LocalStorage::delete(key);
+		}
+	});
+	transaction.set_oncomplete(Some(on_complete.unchecked_ref()));
+	Ok(())
+}
+
+/// Returns whether the item was moved into `IndexedDB`.
+fn import_item(
+	transaction: &IdbTransaction,
+	key: &str,
+	json: &str,
+) -> Result<bool, JsValue> {
+	if let Some(title) = key.strip_prefix(LEGACY_PLAYLIST_PREFIX) {
+		return import_playlist(transaction, title, json);
+	}
+
+	match key {
+		LEGACY_CONFIG_KEY => put(transaction, CONFIG_STORE, key, json)?,
+		LEGACY_SUBSCRIPTIONS_KEY => {
+			put(transaction, SUBSCRIPTIONS_STORE, key, json)?;
+		}
+		LEGACY_POSITIONS_KEY => put(transaction, POSITIONS_STORE, key, json)?,
+		LEGACY_HISTORY_KEY => return import_history(transaction, json),
+		_ if LEGACY_RESOURCE_KEYS.contains(&key) => {}
+		_ => return Ok(false),
+	}
+	Ok(true)
+}
+
+/// Playlists were stored as a JSON string inside a JSON string.
+fn import_playlist(
+	transaction: &IdbTransaction,
+	title: &str,
+	json: &str,
+) -> Result<bool, JsValue> {
+	let playlist = match serde_json::from_str::<Value>(json) {
+		Ok(Value::String(playlist)) => playlist,
+		Ok(playlist) => playlist.to_string(),
+		Err(_) => return Ok(false),
+	};
+	put(transaction, PLAYLISTS_STORE, title, &playlist)?;
+	Ok(true)
+}
+
+/// Playlists were keyed by title. This gives each one a stable `id` to be
+/// keyed by instead, strips the legacy prefix that imports left in titles
+/// and replaces timestamps that were not wall-clock times.
+fn key_playlists_by_id(
+	_: &IdbDatabase,
+	transaction: &IdbTransaction,
+) -> Result<(), JsValue> {
+	let store = transaction.object_store(PLAYLISTS_STORE)?;
+	let request = store.get_all()?;
+	let records = request.clone();
+
+	let on_success = Closure::once_into_js(move || {
+		let Ok(records) = records.result() else {
+			return;
+		};
+		if store.clear().is_err() {
+			return;
+		}
+		for record in js_sys::Array::from(&records).iter() {
+			if let Some(json) = record.as_string() {
+				let _ = put_playlist(&store, &json);
+			}
+		}
+	});
+	request.set_onsuccess(Some(on_success.unchecked_ref()));
+	Ok(())
+}
+
+fn create_feeds_store(
+	db: &IdbDatabase,
+	_: &IdbTransaction,
+) -> Result<(), JsValue> {
+	db.create_object_store(FEEDS_STORE)?;
+	Ok(())
+}
+
+fn create_stats_store(
+	db: &IdbDatabase,
+	_: &IdbTransaction,
+) -> Result<(), JsValue> {
+	db.create_object_store(STATS_STORE)?;
+	Ok(())
+}
+
+fn put_playlist(store: &IdbObjectStore, json: &str) -> Result<(), JsValue> {
+	let Ok(mut playlist) = serde_json::from_str::<Value>(json) else {
+		return Ok(());
+	};
+	if !playlist.is_object() {
+		return Ok(());
+	}
+
+	if let Some(title) = playlist["title"]
+		.as_str()
+		.and_then(|title| title.strip_prefix(LEGACY_PLAYLIST_PREFIX))
+		.map(ToString::to_string)
+	{
+		playlist["title"] = Value::from(title);
+	}
+	for field in ["created", "viewCount"] {
+		let time = playlist[field].as_u64().unwrap_or_default();
+		if time < MIN_UNIX_TIME {
+			playlist[field] = Value::from(get_unix_time());
+		}
+	}
+	let id = match playlist["id"].as_str() {
+		Some(id) if !id.is_empty() => id.to_string(),
+		_ => new_id(),
+	};
+	playlist["id"] = Value::from(id.clone());
+
+	store.put_with_key(
+		&JsValue::from_str(&playlist.to_string()),
+		&JsValue::from_str(&id),
+	)?;
+	Ok(())
+}
+
+/// Splits the history into one record per video.
+fn import_history(
+	transaction: &IdbTransaction,
+	json: &str,
+) -> Result<bool, JsValue> {
+	let Ok(history) = serde_json::from_str::<Value>(json) else {
+		return Ok(false);
+	};
+	let entries = history["entries"].as_array().cloned().unwrap_or_default();
+
+	for entry in entries {
+		if let Some(id) = entry["id"].as_str() {
+			put(transaction, HISTORY_STORE, id, &entry.to_string())?;
+		}
+	}
+	Ok(true)
+}
+
+fn put(
+	transaction: &IdbTransaction,
+	store: &str,
+	key: &str,
+	json: &str,
+) -> Result<(), JsValue> {
+	transaction
+		.object_store(store)?
+		.put_with_key(&JsValue::from_str(json), &JsValue::from_str(key))?;
+	Ok(())
+}
@@ -1,7 +1,7 @@
 mod utils {
 	use std::{collections::HashMap, ops::Div, time::Duration};
 
-	use chrono::DateTime;
+	use chrono::{DateTime, Utc};
 	use gloo::{
 		storage::{LocalStorage, Storage},
 		utils::document,
@@ -28,6 +28,12 @@ mod utils {
 		Ok(element)
 	}
 
+	/// Whether the frontend is running inside the Tauri desktop app.
+	pub fn is_tauri() -> bool {
+		web_sys::window()
+			.map_or(false, |window| window.get("__TAURI_IPC__").is_some())
+	}
+
 	/// # Errors
 	///
 	/// - `LocalStorage` save failure.
@@ -82,6 +88,27 @@ mod utils {
 		Ok(current_time)
 	}
 
+	/// Wall-clock seconds since the Unix epoch.
+	pub fn get_unix_time() -> u64 {
+		u64::try_from(Utc::now().timestamp()).unwrap_or_default()
+	}
+
+	/// A random identifier that stays unique across devices, for records that
+	/// must keep their key when their other fields change.
+	#[allow(clippy::cast_possible_truncation)]
+	#[allow(clippy::cast_sign_loss)]
+	pub fn new_id() -> String {
+		#[cfg(target_arch = "wasm32")]
+		let random = (js_sys::Math::random() * f64::from(u32::MAX)) as u32;
+		// Outside the browser, e.g. in tests, `js_sys` is not available.
+		#[cfg(not(target_arch = "wasm32"))]
+		let random = {
+			use std::hash::BuildHasher;
+			std::collections::hash_map::RandomState::new().hash_one(()) as u32
+		};
+		format!("{:x}{random:08x}", Utc::now().timestamp_millis())
+	}
+
 	/// # Errors
 	///
 	/// - No `Window`.
@@ -143,4 +170,7 @@ mod utils {
 	}
 }
 
+mod idb;
+
+pub use idb::*;
 pub use utils::*;
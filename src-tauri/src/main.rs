@@ -1,8 +1,59 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+use tauri::api::http::{ClientBuilder, HttpRequestBuilder, ResponseType};
+
+/// Fetches a channel's RSS feed outside of the webview, where CORS does not
+/// apply.
+///
+/// Only `/feed/channel/{id}` on the given Invidious server can be requested.
+#[tauri::command]
+async fn fetch_feed(
+	server: String,
+	channel_id: String,
+) -> Result<String, String> {
+	let server = server.trim_end_matches('/');
+	let host = server
+		.strip_prefix("https://")
+		.or_else(|| server.strip_prefix("http://"))
+		.ok_or_else(|| format!("Unsupported server: {server}"))?;
+	if host.is_empty() || host.contains(['/', '?', '#', '@']) {
+		return Err(format!("Unsupported server: {server}"));
+	}
+	if !is_channel_id(&channel_id) {
+		return Err(format!("Invalid channel id: {channel_id}"));
+	}
+	let url = format!("{server}/feed/channel/{channel_id}");
+
+	let client = ClientBuilder::new()
+		.max_redirections(5)
+		.build()
+		.map_err(|err| err.to_string())?;
+	let request = HttpRequestBuilder::new("GET", &url)
+		.map_err(|err| err.to_string())?
+		.response_type(ResponseType::Text);
+	let response = client.send(request).await.map_err(|err| err.to_string())?;
+
+	let status = response.status();
+	if !status.is_success() {
+		return Err(format!("Feed request failed with status {status}"));
+	}
+	let raw = response.bytes().await.map_err(|err| err.to_string())?;
+	String::from_utf8(raw.data).map_err(|err| err.to_string())
+}
+
+/// Channel ids are `UC` followed by 22 url-safe base64 characters.
+fn is_channel_id(id: &str) -> bool {
+	id.len() == 24
+		&& id.starts_with("UC")
+		&& id.bytes().all(|byte| {
+			byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_'
+		})
+}
+
 fn main() {
 	tauri::Builder::default()
+		.invoke_handler(tauri::generate_handler![fetch_feed])
 		.run(tauri::generate_context!())
 		.expect("error while running tauri application");
 }